@@ -1,3 +1,20 @@
+use std::io::Write;
+
 fn main() {
     embuild::espidf::sysenv::output();
+    compress_index_html();
+}
+
+/// Gzip the web UI into OUT_DIR so the firmware can serve it compressed
+fn compress_index_html() {
+    let src = "static/index.html";
+    println!("cargo:rerun-if-changed={}", src);
+
+    let html = std::fs::read(src).expect("read static/index.html");
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::best());
+    encoder.write_all(&html).expect("gzip index.html");
+    let gz = encoder.finish().expect("gzip index.html");
+
+    let out = std::path::Path::new(&std::env::var("OUT_DIR").unwrap()).join("index.html.gz");
+    std::fs::write(out, gz).expect("write index.html.gz");
 }
@@ -15,7 +15,7 @@ pub const PIN_DC: i32 = 22;       // D4  - Display data/command
 #[allow(dead_code)]
 pub const PIN_RST: i32 = 23;      // D5  - Display reset
 #[allow(dead_code)]
-pub const PIN_BL: i32 = 20;       // D9  - Display backlight (MISO pin, unused for SPI read)
+pub const PIN_BL: i32 = 20;       // D9  - Display backlight (LEDC PWM; must be an output-capable GPIO)
 //
 // Rotary Encoder (KY-040):
 #[allow(dead_code)]
@@ -27,6 +27,7 @@ pub const PIN_ENC_SW: i32 = 2;    // D2  - Encoder switch (also light sleep wake
 // Display settings
 pub const DISPLAY_WIDTH: u32 = 160;
 pub const DISPLAY_HEIGHT: u32 = 128;
+pub const BACKLIGHT_PWM_HZ: u32 = 20_000; // Above hearing range, so the backlight driver doesn't whine when dimmed
 
 // SoftAP WiFi settings
 pub const AP_SSID: &str = "DaysTracker";
@@ -36,22 +37,127 @@ pub const AP_IP: &str = "192.168.4.1";
 
 // HTTP server
 pub const HTTP_PORT: u16 = 80;
+pub const MDNS_HOSTNAME: &str = "daystracker"; // Station mode: http://daystracker.local
+pub const HTTP_MAX_URI_HANDLERS: usize = 40; // ESP-IDF default is 32; each route + method uses one
+
+// Live updates (GET /api/events). The HTTP server handles one request at a time, so each
+// event stream is held open only briefly; the browser reconnects after SSE_RETRY_MS.
+pub const SSE_HOLD_MS: u64 = 1000;
+pub const SSE_POLL_MS: u64 = 100;
+pub const SSE_RETRY_MS: u32 = 2000;
+// A comment line this often while nothing changes: a closed page fails the write, which ends
+// its stream early instead of holding the server for the rest of SSE_HOLD_MS
+pub const SSE_KEEPALIVE_MS: u64 = 300;
+pub const SSE_MAX_STREAMS: usize = 2; // Open event streams at once; more are turned away with 503
 
 // Timing
 pub const POLL_INTERVAL_MS: u64 = 1;
 pub const IDLE_TIMEOUT_SECS: u64 = 15; // TODO: increase for normal use after power testing
 pub const QR_IDLE_TIMEOUT_SECS: u64 = 120; // 2 minutes for QR/web UI screens
+// More steps than this within any one second = faulty encoder, rotation ignored. Well above a
+// hard flick (a few dozen clicks a second, which acceleration turns into bigger jumps).
+pub const ENCODER_MAX_STEPS_PER_SEC: u32 = 200;
+// Quadrature transitions per physical click: 4 (one full cycle) on the common KY-040, 2 or 1
+// on encoders with more detents per cycle
+pub const ENCODER_STEPS_PER_DETENT: u8 = 4;
+const _: () = assert!(
+    matches!(ENCODER_STEPS_PER_DETENT, 1 | 2 | 4),
+    "ENCODER_STEPS_PER_DETENT must be 1, 2 or 4"
+);
+pub const ENCODER_ACCEL_WINDOW_MS: u64 = 80; // Clicks in the same direction closer together than this count as a fast spin
+pub const ENCODER_ACCEL_STEPS: u8 = 4; // Items moved per click during a fast spin (lists only)
+// Two short presses within this window are a double press (go to the dashboard). Every short
+// press is reported this much later while the encoder waits for a second one; 0 = off.
+pub const DOUBLE_PRESS_WINDOW_MS: u64 = 250;
 pub const COMPLETING_DURATION_MS: u64 = 500;
+pub const COMPLETING_TIMEOUT_MS: u64 = COMPLETING_DURATION_MS * 3; // Give up on a stuck completion after this
+pub const COMPLETION_DWELL_MAX_MS: u32 = 2000; // Cap for the "Done!" dwell setting
+pub const LONG_PRESS_MIN_MS: u32 = 300; // Range for the long-press setting
+pub const LONG_PRESS_MAX_MS: u32 = 3000;
+pub const BUTTON_DEBOUNCE_MAX_MS: u32 = 1000; // Cap for the button debounce setting
+pub const BRIGHTNESS_MIN_PCT: u8 = 5; // Lowest backlight setting, so the screen can't be set invisible
+pub const COMPLETE_LOCK_MS: u64 = 1000; // Ignore repeat completions of the same task within this window
+pub const TOAST_DURATION_MS: u64 = 1500; // How long transient messages stay on screen (and undo is offered)
+pub const NAME_SCROLL_INTERVAL_MS: u64 = 60; // Frame time for a card name too long to fit
+pub const NAME_SCROLL_STEP_PX: u32 = 2;
+pub const NAME_SCROLL_PAUSE_MS: u64 = 1500; // Hold at the start of the name before (re)scrolling
+pub const SELF_TEST_HOLD_MS: u64 = 1000; // Hold button this long at boot to enter self-test
 
 // Storage
 pub const STORAGE_PARTITION: &str = "storage";
 pub const TASKS_FILE: &str = "/storage/tasks.json";
 pub const HISTORY_FILE: &str = "/storage/history.json";
+pub const LEGACY_SETTINGS_FILE: &str = "/storage/settings.json"; // Before settings moved to NVS; migrated at boot
+pub const NOTE_MAX_CHARS: usize = 100; // Completion note length cap
+pub const BATCH_BODY_MAX_BYTES: usize = 8192; // POST /api/tasks/batch request size cap
+pub const RESTORE_BODY_MAX_BYTES: usize = 128 * 1024; // POST /api/restore request size cap (held in RAM while parsing)
+pub const UPCOMING_DEFAULT_DAYS: i64 = 7;     // GET /api/upcoming window when ?days= is omitted
+pub const UPCOMING_MAX_DAYS: i64 = 60;
+pub const HISTORY_API_MAX_RECORDS: usize = 500; // GET /api/history returns at most this many (newest)
+
+// Debug log buffer (debug feature only) - kept small, lives in internal RAM
+#[cfg(feature = "debug")]
+pub const LOG_BUFFER_LINES: usize = 50;
+#[cfg(feature = "debug")]
+pub const LOG_LINE_MAX_LEN: usize = 120;
+#[cfg(feature = "debug")]
+pub const DEBUG_RENDER_HOLD_SECS: u64 = 10; // Remote-rendered view stays up this long without input
 
 // NVS (Non-Volatile Storage) for WiFi credentials
 pub const NVS_NAMESPACE: &str = "wifi";
 pub const NVS_KEY_SSID: &str = "ssid";
 pub const NVS_KEY_PASSWORD: &str = "password";
+pub const NVS_KEY_STA_VERIFIED: &str = "sta_verified"; // Saved creds have connected at least once
+pub const NVS_KEY_STA_FAILED: &str = "sta_failed";     // Last station attempt failed; booted into AP
+
+// Station connect: tries before giving up (at boot, and when reconnecting after sleep),
+// waiting STA_RETRY_BASE_MS after the first failure and doubling each time
+pub const STA_CONNECT_ATTEMPTS: u32 = 4;
+pub const STA_RETRY_BASE_MS: u64 = 1000;
+pub const STA_RECONNECT_DELAY_SECS: u64 = 3; // After waking, so the encoder responds first
+
+// Station retry from AP mode (only for creds that worked before); 0 = never retry
+pub const AP_STA_RETRY_MINS: u64 = 10;
+
+// Station signal indicator: RSSI (dBm) at or above each value lights one more bar
+pub const RSSI_BAR_THRESHOLDS_DBM: [i8; 3] = [-80, -70, -60];
+
+// NVS for display mounting (rotation/offset)
+pub const NVS_DISPLAY_NAMESPACE: &str = "display";
+pub const NVS_KEY_ROTATION: &str = "rotation";
+pub const NVS_KEY_OFFSET_X: &str = "offset_x";
+pub const NVS_KEY_OFFSET_Y: &str = "offset_y";
+pub const NVS_KEY_SPI_FREQ: &str = "spi_hz";
+
+// NVS for the per-device name
+pub const NVS_DEVICE_NAMESPACE: &str = "device";
+pub const NVS_KEY_DEVICE_NAME: &str = "name";
+pub const DEVICE_NAME_MAX_CHARS: usize = 24; // Fits one line on the settings screen
+pub const NVS_KEY_TZ_OFFSET: &str = "tz_offset"; // Local UTC offset in minutes, same namespace
+pub const TZ_OFFSET_MIN_MINUTES: i32 = -12 * 60;
+pub const TZ_OFFSET_MAX_MINUTES: i32 = 14 * 60;
+pub const NVS_KEY_API_PIN: &str = "api_pin"; // Optional PIN for the REST API, same namespace
+pub const NVS_KEY_INVERT_ENCODER: &str = "enc_invert"; // Encoder wired backwards, same namespace
+pub const NVS_KEY_SETTINGS: &str = "settings"; // User settings as JSON, same namespace
+pub const SETTINGS_NVS_MAX_BYTES: usize = 1024; // Read buffer for the settings JSON
+pub const API_PIN_MIN_LEN: usize = 4;
+pub const API_PIN_MAX_LEN: usize = 12;
+// This many wrong PINs in a row lock the API (429) for API_PIN_LOCKOUT_SECS; each further
+// wrong PIN starts the lockout again
+pub const API_PIN_MAX_FAILURES: u32 = 5;
+pub const API_PIN_LOCKOUT_SECS: u64 = 60;
+
+// Custom callout shown on a task card when it is due (two lines of small text)
+pub const DUE_MESSAGE_MAX_CHARS: usize = 40;
+
+// Category tag shown in the corner of a task card (only the first few characters fit)
+pub const CATEGORY_MAX_CHARS: usize = 16;
 
 // SPI clock speed
 pub const SPI_FREQ_HZ: u32 = 32_000_000;
+
+// Display self-heal for marginal wiring (off by default; trades a little power for robustness)
+pub const DISPLAY_SELF_HEAL: bool = false;
+pub const DISPLAY_REFRESH_SECS: u64 = 30; // Full re-flush this often even when nothing changed
+pub const DISPLAY_ERROR_LIMIT: u32 = 3; // Consecutive failed flushes before dropping SPI speed
+pub const SPI_FALLBACK_FREQ_HZ: u32 = 16_000_000;
@@ -0,0 +1,165 @@
+/// Per-device display name (e.g. "Kitchen"), UTC offset, API PIN, encoder direction and
+/// user settings, stored in NVS
+///
+/// The name lets multi-device households tell units apart on screen and in the
+/// companion app. Defaults to a name derived from the WiFi MAC. The offset decides
+/// when the date rolls over when the time comes from NTP rather than a phone. The
+/// PIN, when set, is required by every /api endpoint. The encoder direction is part of
+/// the wiring, so like the rest it survives a data reset. Settings (GET/PUT /api/settings)
+/// live here too, so all device configuration is in one store.
+extern crate alloc;
+
+use alloc::format;
+use alloc::string::String;
+
+use std::sync::atomic::{AtomicBool, AtomicU16};
+use std::sync::{Arc, Mutex};
+
+use esp_idf_svc::nvs::{EspDefaultNvsPartition, EspNvs};
+
+use crate::config;
+use crate::models::Settings;
+
+/// Device configuration shared with the main loop: changed over HTTP (and saved to NVS
+/// there), picked up by the main loop on its next tick
+#[derive(Default)]
+pub struct DeviceSettings {
+    /// Device name set via PUT /api/device
+    pub name: Mutex<String>,
+    /// Encoder direction set via PUT /api/device
+    pub invert_encoder: AtomicBool,
+    /// Rotation saved via PUT /api/display; the main loop flips the image if it differs from boot
+    pub rotation_deg: AtomicU16,
+    /// Set by PUT /api/settings; the main loop re-reads settings only when this is set
+    pub settings_changed: AtomicBool,
+}
+
+pub type SharedDevice = Arc<DeviceSettings>;
+
+/// "DaysTracker-A1B2" from the last two bytes of the station MAC
+pub fn default_device_name() -> String {
+    let mut mac = [0u8; 6];
+    let err = unsafe {
+        esp_idf_svc::sys::esp_read_mac(mac.as_mut_ptr(), esp_idf_svc::sys::esp_mac_type_t_ESP_MAC_WIFI_STA)
+    };
+    if err != esp_idf_svc::sys::ESP_OK {
+        return String::from("DaysTracker");
+    }
+    format!("DaysTracker-{:02X}{:02X}", mac[4], mac[5])
+}
+
+/// Load the device name from NVS, falling back to the MAC-derived default
+pub fn load_device_name(nvs_partition: &Option<EspDefaultNvsPartition>) -> String {
+    let stored = nvs_partition.as_ref().and_then(|part| {
+        let nvs = EspNvs::new(part.clone(), config::NVS_DEVICE_NAMESPACE, true).ok()?;
+        let mut buf = [0u8; 64];
+        let name = nvs.get_str(config::NVS_KEY_DEVICE_NAME, &mut buf).ok()??;
+        Some(String::from(name))
+    });
+
+    match stored {
+        Some(name) if !name.is_empty() => name,
+        _ => default_device_name(),
+    }
+}
+
+/// Save the device name to NVS
+pub fn save_device_name(
+    nvs_partition: &EspDefaultNvsPartition,
+    name: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut nvs = EspNvs::new(nvs_partition.clone(), config::NVS_DEVICE_NAMESPACE, true)?;
+    nvs.set_str(config::NVS_KEY_DEVICE_NAME, name)?;
+    log::info!("Saved device name: {}", name);
+    Ok(())
+}
+
+/// Load the saved UTC offset (minutes east of UTC), if one has been set
+pub fn load_tz_offset(nvs_partition: &Option<EspDefaultNvsPartition>) -> Option<i32> {
+    let nvs = EspNvs::new(nvs_partition.as_ref()?.clone(), config::NVS_DEVICE_NAMESPACE, true).ok()?;
+    nvs.get_i32(config::NVS_KEY_TZ_OFFSET).ok()?
+}
+
+/// Save the UTC offset (minutes east of UTC) to NVS
+pub fn save_tz_offset(
+    nvs_partition: &EspDefaultNvsPartition,
+    minutes: i32,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut nvs = EspNvs::new(nvs_partition.clone(), config::NVS_DEVICE_NAMESPACE, true)?;
+    nvs.set_i32(config::NVS_KEY_TZ_OFFSET, minutes)?;
+    log::info!("Saved UTC offset: {:+} min", minutes);
+    Ok(())
+}
+
+/// Load the API PIN, if one has been set
+pub fn load_api_pin(nvs_partition: &Option<EspDefaultNvsPartition>) -> Option<String> {
+    let nvs = EspNvs::new(nvs_partition.as_ref()?.clone(), config::NVS_DEVICE_NAMESPACE, true).ok()?;
+    let mut buf = [0u8; 32];
+    let pin = nvs.get_str(config::NVS_KEY_API_PIN, &mut buf).ok()??;
+    Some(String::from(pin)).filter(|p| !p.is_empty())
+}
+
+/// Save the API PIN to NVS, or remove it (None) to leave the API open
+pub fn save_api_pin(
+    nvs_partition: &EspDefaultNvsPartition,
+    pin: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut nvs = EspNvs::new(nvs_partition.clone(), config::NVS_DEVICE_NAMESPACE, true)?;
+    match pin {
+        Some(pin) => {
+            nvs.set_str(config::NVS_KEY_API_PIN, pin)?;
+            log::info!("API PIN set");
+        }
+        None => {
+            nvs.remove(config::NVS_KEY_API_PIN)?;
+            log::info!("API PIN cleared");
+        }
+    }
+    Ok(())
+}
+
+/// Load whether the encoder direction is inverted (false if never set)
+pub fn load_invert_encoder(nvs_partition: &Option<EspDefaultNvsPartition>) -> bool {
+    nvs_partition
+        .as_ref()
+        .and_then(|part| EspNvs::new(part.clone(), config::NVS_DEVICE_NAMESPACE, true).ok())
+        .and_then(|nvs| nvs.get_u8(config::NVS_KEY_INVERT_ENCODER).ok().flatten())
+        .is_some_and(|v| v != 0)
+}
+
+/// Save whether the encoder direction is inverted to NVS
+pub fn save_invert_encoder(
+    nvs_partition: &EspDefaultNvsPartition,
+    invert: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut nvs = EspNvs::new(nvs_partition.clone(), config::NVS_DEVICE_NAMESPACE, true)?;
+    nvs.set_u8(config::NVS_KEY_INVERT_ENCODER, invert as u8)?;
+    log::info!("Saved encoder direction: {}", if invert { "inverted" } else { "normal" });
+    Ok(())
+}
+
+/// Load user settings from NVS (None if never saved, or unreadable)
+pub fn load_settings(nvs_partition: &Option<EspDefaultNvsPartition>) -> Option<Settings> {
+    let nvs = EspNvs::new(nvs_partition.as_ref()?.clone(), config::NVS_DEVICE_NAMESPACE, true).ok()?;
+    let mut buf = [0u8; config::SETTINGS_NVS_MAX_BYTES];
+    let json = nvs.get_str(config::NVS_KEY_SETTINGS, &mut buf).ok()??;
+    match serde_json::from_str(json) {
+        Ok(settings) => Some(settings),
+        Err(e) => {
+            log::warn!("Failed to parse saved settings, using defaults: {}", e);
+            None
+        }
+    }
+}
+
+/// Save user settings to NVS (as JSON, so fields added later fall back to their defaults)
+pub fn save_settings(
+    nvs_partition: &EspDefaultNvsPartition,
+    settings: &Settings,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let json = serde_json::to_string(settings)?;
+    let mut nvs = EspNvs::new(nvs_partition.clone(), config::NVS_DEVICE_NAMESPACE, true)?;
+    nvs.set_str(config::NVS_KEY_SETTINGS, &json)?;
+    log::info!("Saved settings");
+    Ok(())
+}
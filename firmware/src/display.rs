@@ -11,17 +11,96 @@ use embedded_graphics_core::{
 
 use crate::config::{DISPLAY_HEIGHT, DISPLAY_WIDTH};
 
+/// Dirty region bounds: (x0, y0, x1, y1) with exclusive ends
+type Bounds = (u32, u32, u32, u32);
+
+const FULL_SCREEN: Bounds = (0, 0, DISPLAY_WIDTH, DISPLAY_HEIGHT);
+
 /// Framebuffer for 160x128 display
 /// Implements DrawTarget so embedded-graphics can draw to it
+///
+/// Tracks the bounding box of pixels written since the last flush, so the flush
+/// can push just that region instead of the whole screen.
 pub struct FrameBuffer {
     buf: Box<[Rgb565; (DISPLAY_WIDTH * DISPLAY_HEIGHT) as usize]>,
+    /// What the panel currently shows. Every view redraw starts with a clear, so the
+    /// written region is trimmed against this to the pixels that actually changed.
+    shown: Box<[Rgb565; (DISPLAY_WIDTH * DISPLAY_HEIGHT) as usize]>,
+    dirty: Option<Bounds>,
+    /// Panel contents unknown (boot, failed flush): push the dirty region untrimmed
+    force_full: bool,
+    /// Panel turned 180° from the rotation it was set up with at boot: flush rotates the image
+    flipped: bool,
 }
 
 impl FrameBuffer {
     pub fn new() -> Self {
         Self {
             buf: Box::new([Rgb565::new(0, 0, 0); (DISPLAY_WIDTH * DISPLAY_HEIGHT) as usize]),
+            shown: Box::new([Rgb565::new(0, 0, 0); (DISPLAY_WIDTH * DISPLAY_HEIGHT) as usize]),
+            dirty: Some(FULL_SCREEN),
+            force_full: true,
+            flipped: false,
+        }
+    }
+
+    fn mark_dirty(&mut self, x0: u32, y0: u32, x1: u32, y1: u32) {
+        self.dirty = Some(match self.dirty {
+            Some((dx0, dy0, dx1, dy1)) => (dx0.min(x0), dy0.min(y0), dx1.max(x1), dy1.max(y1)),
+            None => (x0, y0, x1, y1),
+        });
+    }
+
+    /// Repaint the whole panel on the next flush, e.g. after a failed transfer
+    pub fn mark_all_dirty(&mut self) {
+        self.dirty = Some(FULL_SCREEN);
+        self.force_full = true;
+    }
+
+    /// Rotate the image by 180° on flush (repaints the whole panel when it changes)
+    pub fn set_flipped(&mut self, flipped: bool) {
+        if flipped != self.flipped {
+            self.flipped = flipped;
+            self.mark_all_dirty();
+        }
+    }
+
+    pub fn is_flipped(&self) -> bool {
+        self.flipped
+    }
+
+    /// Take the region changed since the last call as (x, y, w, h), or None if the
+    /// panel is already up to date. The caller is expected to flush it.
+    pub fn take_dirty_rect(&mut self) -> Option<(u32, u32, u32, u32)> {
+        let written = self.dirty.take()?;
+        let (x0, y0, x1, y1) = if core::mem::take(&mut self.force_full) {
+            written
+        } else {
+            self.changed_bounds(written)?
+        };
+        for y in y0..y1 {
+            let row = (y * DISPLAY_WIDTH) as usize;
+            let span = row + x0 as usize..row + x1 as usize;
+            self.shown[span.clone()].copy_from_slice(&self.buf[span]);
         }
+        Some((x0, y0, x1 - x0, y1 - y0))
+    }
+
+    /// Bounding box of the pixels within `area` that differ from the panel
+    fn changed_bounds(&self, area: Bounds) -> Option<Bounds> {
+        let (x0, y0, x1, y1) = area;
+        let mut bounds: Option<Bounds> = None;
+        for y in y0..y1 {
+            let row = (y * DISPLAY_WIDTH) as usize;
+            let differs = |x: &u32| self.buf[row + *x as usize] != self.shown[row + *x as usize];
+            let Some(first) = (x0..x1).find(differs) else { continue };
+            let last = (x0..x1).rev().find(differs).unwrap_or(first);
+            bounds = Some(match bounds {
+                Some((bx0, by0, bx1, _)) => (bx0.min(first), by0, bx1.max(last + 1), y + 1),
+                None => (first, y, last + 1, y + 1),
+            });
+        }
+        bounds
     }
 
     /// Get raw pixel data as u16 slice for SPI transfer
@@ -38,24 +117,36 @@ impl FrameBuffer {
     /// Clear the buffer with a color
     pub fn clear_color(&mut self, color: Rgb565) {
         self.buf.fill(color);
+        self.mark_dirty(0, 0, DISPLAY_WIDTH, DISPLAY_HEIGHT);
     }
 
     /// Set a pixel directly
     pub fn set_pixel(&mut self, x: u32, y: u32, color: Rgb565) {
         if x < DISPLAY_WIDTH && y < DISPLAY_HEIGHT {
             self.buf[(y * DISPLAY_WIDTH + x) as usize] = color;
+            self.mark_dirty(x, y, x + 1, y + 1);
         }
     }
 
-    /// Draw a filled rectangle
+    /// Read back a pixel (tests check what was drawn)
+    #[cfg(test)]
+    pub fn pixel(&self, x: u32, y: u32) -> Rgb565 {
+        self.buf[(y * DISPLAY_WIDTH + x) as usize]
+    }
+
+    /// Draw a filled rectangle (clipped to the screen)
     pub fn fill_rect(&mut self, x: u32, y: u32, w: u32, h: u32, color: Rgb565) {
         let x_end = (x + w).min(DISPLAY_WIDTH);
         let y_end = (y + h).min(DISPLAY_HEIGHT);
+        if x >= x_end || y >= y_end {
+            return;
+        }
+        // Buffer is row-major, so each row of the rect is one contiguous slice
         for py in y..y_end {
-            for px in x..x_end {
-                self.buf[(py * DISPLAY_WIDTH + px) as usize] = color;
-            }
+            let row = (py * DISPLAY_WIDTH) as usize;
+            self.buf[row + x as usize..row + x_end as usize].fill(color);
         }
+        self.mark_dirty(x, y, x_end, y_end);
     }
 
     /// Draw a horizontal line
@@ -97,7 +188,7 @@ impl DrawTarget for FrameBuffer {
             let x = coord.x;
             let y = coord.y;
             if x >= 0 && x < DISPLAY_WIDTH as i32 && y >= 0 && y < DISPLAY_HEIGHT as i32 {
-                self.buf[(y as u32 * DISPLAY_WIDTH + x as u32) as usize] = color;
+                self.set_pixel(x as u32, y as u32, color);
             }
         }
         Ok(())
@@ -0,0 +1,93 @@
+/// Runtime display mounting options (rotation + panel offset + SPI speed)
+///
+/// Stored in NVS so the same firmware works on differently-mounted panels.
+/// Read once at boot; changes take effect after a restart, except rotation: 90 and 270
+/// are 180° apart, so a switch between them is applied at once by flipping the flush.
+use esp_idf_svc::nvs::{EspDefaultNvsPartition, EspNvs};
+use mipidsi::options::Rotation;
+
+use crate::config;
+
+/// Display rotation and RAM offset passed to the mipidsi builder
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisplayConfig {
+    pub rotation_deg: u16, // 90 or 270 (UI is laid out landscape)
+    pub offset_x: u16,
+    pub offset_y: u16,
+    pub spi_freq_hz: u32, // Lowered by the self-heal after repeated SPI errors
+}
+
+impl Default for DisplayConfig {
+    fn default() -> Self {
+        Self {
+            rotation_deg: 90,
+            offset_x: 2,
+            offset_y: 1,
+            spi_freq_hz: config::SPI_FREQ_HZ,
+        }
+    }
+}
+
+impl DisplayConfig {
+    /// Only landscape rotations fit the 160x128 framebuffer
+    pub fn is_valid_rotation(deg: u16) -> bool {
+        matches!(deg, 90 | 270)
+    }
+
+    /// SPI can be slowed for long wires, never pushed past the stock speed
+    pub fn is_valid_spi_freq(hz: u32) -> bool {
+        (1_000_000..=config::SPI_FREQ_HZ).contains(&hz)
+    }
+
+    pub fn rotation(&self) -> Rotation {
+        match self.rotation_deg {
+            270 => Rotation::Deg270,
+            _ => Rotation::Deg90,
+        }
+    }
+}
+
+/// Load display config from NVS, falling back to defaults for anything missing
+pub fn load_display_config(nvs_partition: &EspDefaultNvsPartition) -> DisplayConfig {
+    let defaults = DisplayConfig::default();
+    let nvs = match EspNvs::new(nvs_partition.clone(), config::NVS_DISPLAY_NAMESPACE, true) {
+        Ok(nvs) => nvs,
+        Err(_) => return defaults,
+    };
+
+    let rotation_deg = nvs
+        .get_u16(config::NVS_KEY_ROTATION)
+        .ok()
+        .flatten()
+        .filter(|d| DisplayConfig::is_valid_rotation(*d))
+        .unwrap_or(defaults.rotation_deg);
+    let offset_x = nvs.get_u16(config::NVS_KEY_OFFSET_X).ok().flatten().unwrap_or(defaults.offset_x);
+    let offset_y = nvs.get_u16(config::NVS_KEY_OFFSET_Y).ok().flatten().unwrap_or(defaults.offset_y);
+    let spi_freq_hz = nvs
+        .get_u32(config::NVS_KEY_SPI_FREQ)
+        .ok()
+        .flatten()
+        .filter(|hz| DisplayConfig::is_valid_spi_freq(*hz))
+        .unwrap_or(defaults.spi_freq_hz);
+
+    DisplayConfig {
+        rotation_deg,
+        offset_x,
+        offset_y,
+        spi_freq_hz,
+    }
+}
+
+/// Save display config to NVS (applied on next boot)
+pub fn save_display_config(
+    nvs_partition: &EspDefaultNvsPartition,
+    display: &DisplayConfig,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut nvs = EspNvs::new(nvs_partition.clone(), config::NVS_DISPLAY_NAMESPACE, true)?;
+    nvs.set_u16(config::NVS_KEY_ROTATION, display.rotation_deg)?;
+    nvs.set_u16(config::NVS_KEY_OFFSET_X, display.offset_x)?;
+    nvs.set_u16(config::NVS_KEY_OFFSET_Y, display.offset_y)?;
+    nvs.set_u32(config::NVS_KEY_SPI_FREQ, display.spi_freq_hz)?;
+    log::info!("Saved display config: {:?}", display);
+    Ok(())
+}
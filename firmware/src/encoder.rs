@@ -2,50 +2,81 @@
 ///
 /// Provides low-latency input handling for the KY-040 rotary encoder:
 /// - Clockwise/counter-clockwise rotation detection
-/// - Short press / long press differentiation
-/// - Backlight control via GPIO
-use esp_idf_hal::gpio::{Input, InputPin, Output, OutputPin, Pin, PinDriver, Pull};
+/// - Short press / long press / double press differentiation
+/// - Backlight brightness via an LEDC PWM channel
+use esp_idf_hal::gpio::{Input, InputPin, OutputPin, Pin, PinDriver, Pull};
+use esp_idf_hal::ledc::LedcDriver;
 use esp_idf_hal::peripheral::Peripheral;
+use std::collections::VecDeque;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
-/// Long press threshold in seconds
+use crate::config;
+use crate::models::Settings;
+use crate::quadrature::QuadratureDecoder;
+
+/// Long press threshold in seconds (default; see `set_press_timing`)
 const LONG_PRESS_TIME: f64 = 0.5;
 
-/// Debounce time for button in seconds
+/// Debounce time for button in seconds (default; see `set_press_timing`)
 const BUTTON_DEBOUNCE: f64 = 0.2;
 
+/// Hold progress is reported in this many steps (so the display isn't flushed every poll)
+const PRESS_PROGRESS_STEPS: u8 = 10;
+
+/// Steps a hold must reach before progress is shown, so ordinary taps don't flash the bar
+const PRESS_PROGRESS_MIN_STEP: u8 = 3;
+
 /// Events produced by the encoder
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum EncoderEvent {
-    Clockwise,
-    CounterClockwise,
+    /// Rotation by one click; the count is above 1 during a fast spin (acceleration)
+    Clockwise(u8),
+    CounterClockwise(u8),
     ShortPress,
     LongPress,
+    /// Two short presses within DOUBLE_PRESS_WINDOW_MS (neither is reported as a ShortPress)
+    DoublePress,
+    /// Button still held: fraction of the long-press threshold reached (1.0 = release for long press).
+    /// 0.0 means a shown hold ended without a press event, so the indicator should be cleared.
+    PressProgress(f32),
 }
 
 /// Rotary encoder with button and backlight control
-pub struct Encoder<'d, CLK: Pin, DT: Pin, SW: Pin, BL: Pin> {
+pub struct Encoder<'d, CLK: Pin, DT: Pin, SW: Pin> {
     clk: PinDriver<'d, CLK, Input>,
     dt: PinDriver<'d, DT, Input>,
     sw: PinDriver<'d, SW, Input>,
-    backlight: PinDriver<'d, BL, Output>,
-    last_clk: bool,
+    backlight: LedcDriver<'d>,
+    quadrature: QuadratureDecoder,
     button_press_time: Option<Instant>,
+    press_progress_step: u8, // Last hold step reported while the button is down
     last_button_time: Instant,
+    pending_short_press: Option<Instant>, // Release of a short press that may become a double press
+    queued_event: Option<EncoderEvent>, // Held back so a pending short press is reported first
     last_activity: Instant,
     backlight_on: Arc<AtomicBool>,
+    brightness: u8, // Percent used while the screen is on
+    dimmed: bool,   // Lowered for idle; any activity restores `brightness`
+    recent_steps: VecDeque<Instant>, // Rotation steps in the last second (at most the limit + 1)
+    rotation_faulty: bool,
+    wake_on_rotation: bool,
+    invert_direction: bool, // Swap clockwise/counter-clockwise for encoders wired the other way
+    long_press_time: f64, // Seconds
+    button_debounce: f64, // Seconds
+    last_detent: Option<(i32, Instant)>, // Direction and time of the previous click, for acceleration
 }
 
-impl<'d, CLK: InputPin + OutputPin, DT: InputPin + OutputPin, SW: InputPin + OutputPin, BL: OutputPin> Encoder<'d, CLK, DT, SW, BL> {
-    /// Create a new encoder instance
+impl<'d, CLK: InputPin + OutputPin, DT: InputPin + OutputPin, SW: InputPin + OutputPin> Encoder<'d, CLK, DT, SW> {
+    /// Create a new encoder instance. `backlight` is the PWM channel driving the display's BL pin.
     pub fn new(
         clk_pin: impl Peripheral<P = CLK> + 'd,
         dt_pin: impl Peripheral<P = DT> + 'd,
         sw_pin: impl Peripheral<P = SW> + 'd,
-        bl_pin: impl Peripheral<P = BL> + 'd,
+        mut backlight: LedcDriver<'d>,
         backlight_on: Arc<AtomicBool>,
+        invert_direction: bool,
     ) -> Result<Self, esp_idf_hal::sys::EspError> {
         let mut clk = PinDriver::input(clk_pin)?;
         clk.set_pull(Pull::Up)?;
@@ -56,8 +87,7 @@ impl<'d, CLK: InputPin + OutputPin, DT: InputPin + OutputPin, SW: InputPin + Out
         let mut sw = PinDriver::input(sw_pin)?;
         sw.set_pull(Pull::Up)?;
 
-        let mut backlight = PinDriver::output(bl_pin)?;
-        backlight.set_high()?; // Active-high: HIGH = backlight ON
+        backlight.set_duty(backlight.get_max_duty())?; // Active-high: full duty = full brightness
 
         let now = Instant::now();
 
@@ -66,32 +96,63 @@ impl<'d, CLK: InputPin + OutputPin, DT: InputPin + OutputPin, SW: InputPin + Out
             dt,
             sw,
             backlight,
-            last_clk: true, // Pull-up, so high is default
+            quadrature: QuadratureDecoder::new(config::ENCODER_STEPS_PER_DETENT),
             button_press_time: None,
+            press_progress_step: 0,
             last_button_time: now,
+            pending_short_press: None,
+            queued_event: None,
             last_activity: now,
             backlight_on,
+            brightness: 100,
+            dimmed: false,
+            recent_steps: VecDeque::with_capacity(config::ENCODER_MAX_STEPS_PER_SEC as usize + 1),
+            rotation_faulty: false,
+            wake_on_rotation: true,
+            invert_direction,
+            long_press_time: LONG_PRESS_TIME,
+            button_debounce: BUTTON_DEBOUNCE,
+            last_detent: None,
         })
     }
 
     /// Poll for encoder events (non-blocking)
     pub fn poll(&mut self) -> Option<EncoderEvent> {
-        // Check rotation
-        let clk_state = self.clk.is_high();
+        if let Some(event) = self.queued_event.take() {
+            return Some(event);
+        }
+
+        // No second press came in time: report the held-back short press. A debounce at least
+        // as long as the window (set for unsteady hands) turns double press off.
+        let mut window = Duration::from_millis(config::DOUBLE_PRESS_WINDOW_MS);
+        if self.button_debounce >= window.as_secs_f64() {
+            window = Duration::ZERO;
+        }
+        if let Some(released) = self.pending_short_press {
+            if self.button_press_time.is_none() && released.elapsed() > window {
+                self.pending_short_press = None;
+                return Some(EncoderEvent::ShortPress);
+            }
+        }
 
-        // Detect falling edge on CLK
-        if !clk_state && self.last_clk {
-            self.last_clk = clk_state;
+        // Check rotation: one step each time the encoder settles in the next detent
+        if let Some(step) = self.quadrature.update(self.clk.is_high(), self.dt.is_high()) {
+            // Screen off and rotation isn't allowed to wake it: drop the step entirely
+            if !self.wake_on_rotation && !self.is_backlight_on() {
+                return None;
+            }
+
+            // Ignore rotation storms from a faulty encoder (and don't let them keep the screen awake)
+            if !self.rotation_allowed() {
+                return None;
+            }
             self.record_activity();
 
-            // DT high = clockwise, DT low = counter-clockwise
-            return Some(if self.dt.is_high() {
-                EncoderEvent::Clockwise
-            } else {
-                EncoderEvent::CounterClockwise
-            });
+            // Flip before acceleration, so it sees the direction the user turned
+            let step = if self.invert_direction { -step } else { step };
+            let event = Some(self.rotation_event(step));
+            return self.after_pending_press(event);
         }
-        self.last_clk = clk_state;
 
         // Check button state (active low with pull-up)
         let button_pressed = self.sw.is_low();
@@ -103,22 +164,44 @@ impl<'d, CLK: InputPin + OutputPin, DT: InputPin + OutputPin, SW: InputPin + Out
                 self.button_press_time = Some(now);
                 self.record_activity();
             }
+            // Button held: report progress toward the long-press threshold
+            (true, Some(press_time)) => {
+                let fraction = (now.duration_since(press_time).as_secs_f64() / self.long_press_time).min(1.0);
+                let step = (fraction * PRESS_PROGRESS_STEPS as f64) as u8;
+                if step >= PRESS_PROGRESS_MIN_STEP && step > self.press_progress_step {
+                    self.press_progress_step = step;
+                    return Some(EncoderEvent::PressProgress(step as f32 / PRESS_PROGRESS_STEPS as f32));
+                }
+            }
             // Button just released
             (false, Some(press_time)) => {
                 self.button_press_time = None;
+                let progress_shown = self.press_progress_step > 0;
+                self.press_progress_step = 0;
+                let duration = now.duration_since(press_time);
+                let long = duration.as_secs_f64() >= self.long_press_time;
 
-                // Debounce check
-                if now.duration_since(self.last_button_time).as_secs_f64() < BUTTON_DEBOUNCE {
-                    return None;
+                // Debounce check, for the second press of a double press as much as any other:
+                // a release too soon after the last one is contact bounce
+                if now.duration_since(self.last_button_time).as_secs_f64() < self.button_debounce {
+                    return progress_shown.then_some(EncoderEvent::PressProgress(0.0));
                 }
                 self.last_button_time = now;
 
-                let duration = now.duration_since(press_time).as_secs_f64();
-                return Some(if duration >= LONG_PRESS_TIME {
-                    EncoderEvent::LongPress
-                } else {
-                    EncoderEvent::ShortPress
-                });
+                if self.pending_short_press.is_some() && !long {
+                    self.pending_short_press = None;
+                    return Some(EncoderEvent::DoublePress);
+                }
+
+                if long {
+                    return self.after_pending_press(Some(EncoderEvent::LongPress));
+                }
+                if window.is_zero() {
+                    return Some(EncoderEvent::ShortPress);
+                }
+                // Wait for a possible second press; clear a hold indicator meanwhile
+                self.pending_short_press = Some(now);
+                return progress_shown.then_some(EncoderEvent::PressProgress(0.0));
             }
             _ => {}
         }
@@ -126,22 +209,141 @@ impl<'d, CLK: InputPin + OutputPin, DT: InputPin + OutputPin, SW: InputPin + Out
         None
     }
 
-    /// Set backlight state (active-high: HIGH = on, LOW = off)
-    pub fn set_backlight(&mut self, on: bool) {
-        if on {
-            let _ = self.backlight.set_high();
+    /// Keep events in order: if a short press is still held back, report it now and `event` next
+    fn after_pending_press(&mut self, event: Option<EncoderEvent>) -> Option<EncoderEvent> {
+        if event.is_some() && self.pending_short_press.take().is_some() {
+            self.queued_event = event;
+            return Some(EncoderEvent::ShortPress);
+        }
+        event
+    }
+
+    /// Turn one detent into a rotation event, several items at once during a fast spin
+    fn rotation_event(&mut self, step: i32) -> EncoderEvent {
+        // Quick clicks in the same direction are a fast spin: move several items per click
+        let now = Instant::now();
+        let fast = matches!(
+            self.last_detent,
+            Some((dir, at)) if dir == step && now.duration_since(at) < Duration::from_millis(config::ENCODER_ACCEL_WINDOW_MS)
+        );
+        self.last_detent = Some((step, now));
+        let count = if fast { config::ENCODER_ACCEL_STEPS.max(1) } else { 1 };
+
+        if step > 0 {
+            EncoderEvent::Clockwise(count)
         } else {
-            let _ = self.backlight.set_low();
+            EncoderEvent::CounterClockwise(count)
+        }
+    }
+
+    /// Count a rotation step against the steps of the last second (a sliding window, so a
+    /// storm straddling a window boundary can't slip through at twice the limit).
+    /// Returns false while the encoder is producing an implausible rate of steps.
+    fn rotation_allowed(&mut self) -> bool {
+        let max_per_sec = config::ENCODER_MAX_STEPS_PER_SEC as usize;
+        let now = Instant::now();
+
+        // Steps are counted even while ignored, so the encoder stays blocked until it settles
+        while self
+            .recent_steps
+            .front()
+            .is_some_and(|&at| now.duration_since(at) >= Duration::from_secs(1))
+        {
+            self.recent_steps.pop_front();
+        }
+        if self.recent_steps.len() > max_per_sec {
+            self.recent_steps.pop_front(); // Only whether the limit is passed matters
         }
-        self.backlight_on.store(on, Ordering::SeqCst);
+        self.recent_steps.push_back(now);
+
+        let storm = self.recent_steps.len() > max_per_sec;
+        if storm && !self.rotation_faulty {
+            log::warn!(
+                "Encoder sent >{} steps/sec, ignoring rotation until it settles (faulty encoder?)",
+                max_per_sec
+            );
+        } else if !storm && self.rotation_faulty {
+            log::info!("Encoder rotation back to normal, accepting input");
+        }
+        self.rotation_faulty = storm;
+
+        !self.rotation_faulty
+    }
+
+    /// Check if the button is physically held right now (used for boot gestures)
+    pub fn is_button_held(&self) -> bool {
+        self.sw.is_low()
+    }
+
+    /// Apply the user settings the encoder cares about (wake, press timing, brightness)
+    pub fn apply_settings(&mut self, settings: &Settings) {
+        self.set_wake_on_rotation(settings.wake_on_rotation);
+        self.set_press_timing(settings.long_press_ms, settings.button_debounce_ms);
+        self.set_brightness_level(settings.brightness);
+    }
+
+    /// Choose whether rotation wakes the screen (otherwise only a press does)
+    pub fn set_wake_on_rotation(&mut self, enabled: bool) {
+        self.wake_on_rotation = enabled;
+    }
+
+    /// Swap which way counts as clockwise (for encoders wired with CLK and DT reversed)
+    pub fn set_invert_direction(&mut self, invert: bool) {
+        if invert != self.invert_direction {
+            self.invert_direction = invert;
+            self.last_detent = None;
+        }
+    }
+
+    /// Set how long a hold must last to count as a long press, and the button debounce window
+    pub fn set_press_timing(&mut self, long_press_ms: u32, debounce_ms: u32) {
+        self.long_press_time = long_press_ms as f64 / 1000.0;
+        self.button_debounce = debounce_ms as f64 / 1000.0;
+    }
+
+    /// Drive the backlight at `pct` percent (0 = off, 100 = full)
+    pub fn set_brightness(&mut self, pct: u8) {
+        let pct = pct.min(100) as u32;
+        let _ = self.backlight.set_duty(self.backlight.get_max_duty() * pct / 100);
+        self.backlight_on.store(pct > 0, Ordering::SeqCst);
+    }
+
+    /// Turn the backlight on (at the configured brightness, 100% by default) or off
+    pub fn set_backlight(&mut self, on: bool) {
+        self.dimmed = false;
+        self.set_brightness(if on { self.brightness } else { 0 });
+    }
+
+    /// Set the brightness used while the screen is on; applied now unless it's off or dimmed
+    pub fn set_brightness_level(&mut self, pct: u8) {
+        let pct = pct.clamp(1, 100);
+        if pct != self.brightness {
+            self.brightness = pct;
+            if self.is_backlight_on() && !self.dimmed {
+                self.set_brightness(pct);
+            }
+        }
+    }
+
+    /// Lower the backlight to `pct` (never above the normal level) until the next activity
+    pub fn dim(&mut self, pct: u8) {
+        if self.is_backlight_on() && !self.dimmed {
+            self.dimmed = true;
+            self.set_brightness(pct.clamp(1, self.brightness));
+        }
+    }
+
+    /// Check if the backlight is lowered for idle
+    pub fn is_dimmed(&self) -> bool {
+        self.dimmed
     }
 
     /// Record user activity
     fn record_activity(&mut self) {
         self.last_activity = Instant::now();
 
-        // Wake up screen if it was off
-        if !self.backlight_on.load(Ordering::SeqCst) {
+        // Wake up screen if it was off, or restore it if it was dimmed
+        if !self.backlight_on.load(Ordering::SeqCst) || self.dimmed {
             self.set_backlight(true);
         }
     }
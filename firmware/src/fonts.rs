@@ -1,12 +1,11 @@
 /// Bitmap font data for kiosk display
 ///
-/// 5x7 standard bitmap font and 12x18 large number font.
+/// 5x7 standard bitmap font, 12x18 large number font and the boot logo.
 /// Data lives in flash (const), not RAM.
 
 /// 5x7 bitmap font width
 pub const FONT_WIDTH: u32 = 5;
 /// 5x7 bitmap font height
-#[allow(dead_code)]
 pub const FONT_HEIGHT: u32 = 7;
 
 /// Large number font dimensions (smoother, friendlier numbers)
@@ -15,7 +14,7 @@ pub const BIG_NUM_HEIGHT: u32 = 18;
 
 /// Get bitmap for a character (5x7 font)
 /// Numbers designed with rounded, friendly appearance
-pub fn get_char_bitmap(ch: char) -> [u8; 7] {
+pub const fn get_char_bitmap(ch: char) -> [u8; 7] {
     match ch {
         ' ' => [0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000],
         '!' => [0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00000, 0b00100],
@@ -86,6 +85,63 @@ pub fn get_char_bitmap(ch: char) -> [u8; 7] {
     }
 }
 
+/// Lit pixels in one glyph row as (start column, length) spans; a 5-wide row has at most 3
+#[derive(Clone, Copy)]
+pub struct GlyphRuns {
+    runs: [[(u8, u8); 3]; 7],
+    counts: [u8; 7],
+}
+
+impl GlyphRuns {
+    const fn from_bitmap(bitmap: [u8; 7]) -> Self {
+        let mut runs = [[(0u8, 0u8); 3]; 7];
+        let mut counts = [0u8; 7];
+        let mut row = 0;
+        while row < 7 {
+            let bits = bitmap[row];
+            let mut col = 0u8;
+            while col < FONT_WIDTH as u8 {
+                if (bits >> (FONT_WIDTH as u8 - 1 - col)) & 1 == 1 {
+                    let start = col;
+                    while col < FONT_WIDTH as u8 && (bits >> (FONT_WIDTH as u8 - 1 - col)) & 1 == 1 {
+                        col += 1;
+                    }
+                    runs[row][counts[row] as usize] = (start, col - start);
+                    counts[row] += 1;
+                } else {
+                    col += 1;
+                }
+            }
+            row += 1;
+        }
+        Self { runs, counts }
+    }
+
+    /// Spans for one row (0..FONT_HEIGHT)
+    pub fn row(&self, row: usize) -> &[(u8, u8)] {
+        &self.runs[row][..self.counts[row] as usize]
+    }
+}
+
+/// Printable ASCII (' '..='~') expanded to runs at compile time; last slot is the fallback box
+static CHAR_RUNS: [GlyphRuns; 96] = {
+    let mut table = [GlyphRuns::from_bitmap([0; 7]); 96];
+    let mut i = 0;
+    while i < 96 {
+        table[i] = GlyphRuns::from_bitmap(get_char_bitmap((b' ' + i as u8) as char));
+        i += 1;
+    }
+    table
+};
+
+/// Get pre-expanded pixel runs for a character (5x7 font)
+pub fn get_char_runs(ch: char) -> &'static GlyphRuns {
+    match ch {
+        ' '..='~' => &CHAR_RUNS[ch as usize - ' ' as usize],
+        _ => &CHAR_RUNS[95], // b' ' + 95 = DEL, which maps to the fallback box
+    }
+}
+
 /// Get large bitmap for numbers (12x18 smooth, rounded font)
 /// Designed to look friendly and modern, not robotic
 pub fn get_big_num_bitmap(ch: char) -> [u16; 18] {
@@ -311,7 +367,67 @@ pub fn get_big_num_bitmap(ch: char) -> [u16; 18] {
             0b000000000000,
             0b000000000000,
         ],
+        ':' => [
+            0b000000000000,
+            0b000000000000,
+            0b000000000000,
+            0b000000000000,
+            0b000011100000,
+            0b000011100000,
+            0b000011100000,
+            0b000000000000,
+            0b000000000000,
+            0b000000000000,
+            0b000011100000,
+            0b000011100000,
+            0b000011100000,
+            0b000000000000,
+            0b000000000000,
+            0b000000000000,
+            0b000000000000,
+            0b000000000000,
+        ],
         // Fallback - empty
         _ => [0; 18],
     }
 }
+
+/// Boot splash logo (calendar with a check mark)
+pub const LOGO_WIDTH: u32 = 32;
+pub const LOGO_HEIGHT: u32 = 32;
+
+/// 1bpp, row-major, MSB-first; 4 bytes per row
+pub const LOGO: [u8; 128] = [
+    0b00000000, 0b00000000, 0b00000000, 0b00000000,
+    0b00000000, 0b11100000, 0b00000111, 0b00000000,
+    0b00000000, 0b11100000, 0b00000111, 0b00000000,
+    0b00000000, 0b11100000, 0b00000111, 0b00000000,
+    0b00111111, 0b11111111, 0b11111111, 0b11111100,
+    0b00111111, 0b11111111, 0b11111111, 0b11111100,
+    0b00111111, 0b11111111, 0b11111111, 0b11111100,
+    0b00111111, 0b11111111, 0b11111111, 0b11111100,
+    0b00111111, 0b11111111, 0b11111111, 0b11111100,
+    0b00111111, 0b11111111, 0b11111111, 0b11111100,
+    0b00111111, 0b11111111, 0b11111111, 0b11111100,
+    0b00110000, 0b00000000, 0b00000000, 0b00001100,
+    0b00110000, 0b00000000, 0b00000000, 0b00001100,
+    0b00110000, 0b00000000, 0b00000000, 0b00001100,
+    0b00110000, 0b00000000, 0b00000110, 0b00001100,
+    0b00110000, 0b00000000, 0b00001110, 0b00001100,
+    0b00110000, 0b00000000, 0b00011100, 0b00001100,
+    0b00110000, 0b00000000, 0b00111000, 0b00001100,
+    0b00110000, 0b01100000, 0b01110000, 0b00001100,
+    0b00110000, 0b01110000, 0b11100000, 0b00001100,
+    0b00110000, 0b00111001, 0b11000000, 0b00001100,
+    0b00110000, 0b00011111, 0b10000000, 0b00001100,
+    0b00110000, 0b00001111, 0b00000000, 0b00001100,
+    0b00110000, 0b00000110, 0b00000000, 0b00001100,
+    0b00110000, 0b00000000, 0b00000000, 0b00001100,
+    0b00110000, 0b00000000, 0b00000000, 0b00001100,
+    0b00110000, 0b00000000, 0b00000000, 0b00001100,
+    0b00110000, 0b00000000, 0b00000000, 0b00001100,
+    0b00110000, 0b00000000, 0b00000000, 0b00001100,
+    0b00111111, 0b11111111, 0b11111111, 0b11111100,
+    0b00111111, 0b11111111, 0b11111111, 0b11111100,
+    0b00000000, 0b00000000, 0b00000000, 0b00000000,
+];
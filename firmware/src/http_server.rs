@@ -8,10 +8,12 @@ use alloc::format;
 use alloc::string::String;
 use alloc::vec::Vec;
 
-use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
 
-use esp_idf_svc::http::server::{Configuration as HttpConfig, EspHttpServer};
-use esp_idf_svc::http::Method;
+use esp_idf_svc::http::server::{Configuration as HttpConfig, EspHttpConnection, EspHttpServer, Request};
+use esp_idf_svc::http::{Headers, Method};
 use esp_idf_svc::nvs::EspDefaultNvsPartition;
 use esp_idf_svc::wifi::{BlockingWifi, EspWifi};
 
@@ -19,20 +21,129 @@ use chrono::NaiveDate;
 use serde_json::json;
 
 use crate::config;
-use crate::models::RecurrenceType;
-use crate::storage::Storage;
+use crate::device::{self, DeviceSettings, SharedDevice};
+use crate::display_config::{self, DisplayConfig};
+use crate::models::{check_recurrence_update, check_weekday_mask, local_date, CompletionQuota, DashboardMetric, RecurrenceType, Settings, Task, UrgencyThresholds, WeekStart};
+use crate::storage::{Backup, CompleteResult, NewTask, RestoreError, Storage, TaskSort, TaskUpdate};
+use crate::theme;
+#[cfg(feature = "debug")]
+use crate::views::ViewState;
 use crate::wifi::{self, WiFiMode};
 
 /// Shared state between HTTP server and main thread
 pub type SharedStorage = Arc<Mutex<Storage>>;
 
-/// Shared time source - seconds since epoch, set by phone
-pub type SharedTime = Arc<Mutex<Option<i64>>>;
+/// Wall-clock time as last synced (phone or NTP), advanced by the monotonic clock
+#[derive(Debug, Clone, Copy)]
+pub struct SyncedTime {
+    epoch_secs: i64,        // UTC seconds since epoch at sync
+    utc_offset_secs: i64,   // Local offset from UTC (saved setting)
+    synced_at: Instant,
+}
+
+impl SyncedTime {
+    pub fn new(epoch_secs: i64, utc_offset_secs: i64) -> Self {
+        Self {
+            epoch_secs,
+            utc_offset_secs,
+            synced_at: Instant::now(),
+        }
+    }
+
+    /// Current UTC seconds since epoch
+    pub fn utc_secs(&self) -> i64 {
+        self.epoch_secs + self.synced_at.elapsed().as_secs() as i64
+    }
+
+    /// Local offset from UTC in seconds
+    pub fn utc_offset_secs(&self) -> i64 {
+        self.utc_offset_secs
+    }
+
+    /// Change the local offset without resyncing (takes effect on the next date check)
+    pub fn set_utc_offset_secs(&mut self, offset_secs: i64) {
+        self.utc_offset_secs = offset_secs;
+    }
+
+    /// Current local time as seconds since epoch (for dates and clock display)
+    pub fn local_secs(&self) -> i64 {
+        self.utc_secs() + self.utc_offset_secs
+    }
+}
+
+/// Shared time source, set by the phone or NTP; None until either has synced
+pub type SharedTime = Arc<Mutex<Option<SyncedTime>>>;
+
+/// Lock shared storage. A panic while another thread held it poisons the mutex; the data is
+/// still consistent between operations, so recover it instead of wedging every later request.
+pub fn lock_storage(storage: &SharedStorage) -> MutexGuard<'_, Storage> {
+    lock_recovering(storage, "storage")
+}
+
+/// Lock the shared time source, recovering from poisoning like `lock_storage`
+pub fn lock_time(time: &SharedTime) -> MutexGuard<'_, Option<SyncedTime>> {
+    lock_recovering(time, "time")
+}
+
+fn lock_recovering<'a, T>(mutex: &'a Mutex<T>, name: &str) -> MutexGuard<'a, T> {
+    mutex.lock().unwrap_or_else(|poisoned| {
+        log::error!("Recovered {} lock poisoned by a panicked thread", name);
+        mutex.clear_poison();
+        poisoned.into_inner()
+    })
+}
+
+/// SHA-256 of the PIN required by every /api endpoint when set (loaded from NVS when the
+/// server starts, changed via PUT /api/pin). None leaves the API open, as on a fresh device.
+static API_PIN_DIGEST: Mutex<Option<[u8; 32]>> = Mutex::new(None);
+
+/// Wrong PINs in a row, and when the lockout they triggered ends
+static PIN_FAILURES: Mutex<(u32, Option<Instant>)> = Mutex::new((0, None));
+
+/// Last task completed over the API and when, so a double-submitted form or a retried
+/// request can't complete it twice (the device keeps its own lock in the navigator)
+static LAST_API_COMPLETION: Mutex<Option<(u32, Instant)>> = Mutex::new(None);
+
+/// Event streams (GET /api/events) open right now, at most SSE_MAX_STREAMS
+static SSE_STREAMS: AtomicUsize = AtomicUsize::new(0);
+
+/// One of the SSE_MAX_STREAMS stream slots, given back on drop however the stream ends
+struct SseSlot;
+
+impl SseSlot {
+    fn acquire() -> Option<Self> {
+        SSE_STREAMS
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |open| {
+                (open < config::SSE_MAX_STREAMS).then_some(open + 1)
+            })
+            .ok()
+            .map(|_| Self)
+    }
+}
+
+impl Drop for SseSlot {
+    fn drop(&mut self) {
+        SSE_STREAMS.fetch_sub(1, Ordering::AcqRel);
+    }
+}
 
 /// Shared WiFi instance for scanning (AP mode only)
 pub type SharedWifi = Arc<Mutex<BlockingWifi<EspWifi<'static>>>>;
 
+/// Screen mirror shared with the main loop: latest snapshot out, debug render requests in
+#[derive(Default)]
+pub struct ScreenShare {
+    /// Published by the main loop after each render
+    pub snapshot: Mutex<serde_json::Value>,
+    /// Views queued by POST /api/debug/render, drained by the main loop
+    #[cfg(feature = "debug")]
+    pub render_requests: Mutex<alloc::collections::VecDeque<serde_json::Value>>,
+}
+
+pub type SharedScreen = Arc<ScreenShare>;
+
 /// Start the HTTP server
+#[allow(clippy::too_many_arguments)]
 pub fn start_server(
     storage: SharedStorage,
     time_source: SharedTime,
@@ -40,22 +151,21 @@ pub fn start_server(
     wifi_mode: WiFiMode,
     shared_wifi: Option<SharedWifi>,
     nvs_partition: Option<EspDefaultNvsPartition>,
+    screen: SharedScreen,
+    device: SharedDevice,
 ) -> Result<EspHttpServer<'static>, Box<dyn std::error::Error>> {
     let server_config = HttpConfig {
         http_port: config::HTTP_PORT,
+        max_uri_handlers: config::HTTP_MAX_URI_HANDLERS,
         ..Default::default()
     };
 
     let mut server = EspHttpServer::new(&server_config)?;
+    *lock_recovering(&API_PIN_DIGEST, "pin") = device::load_api_pin(&nvs_partition).map(|pin| sha256(pin.as_bytes()));
 
     // GET / -> serve index.html
     {
-        server.fn_handler("/", Method::Get, |req| -> Result<(), esp_idf_svc::io::EspIOError> {
-            let html = include_str!("../static/index.html");
-            req.into_ok_response()?
-                .write(html.as_bytes())?;
-            Ok(())
-        })?;
+        server.fn_handler("/", Method::Get, serve_index)?;
     }
 
     // Captive portal detection handlers
@@ -81,12 +191,7 @@ pub fn start_server(
 
         // iOS / macOS captive portal detection - serve HTML directly so captive portal sheet
         // shows our web UI without needing an extra redirect round trip
-        server.fn_handler("/hotspot-detect.html", Method::Get, |req| -> Result<(), esp_idf_svc::io::EspIOError> {
-            let html = include_str!("../static/index.html");
-            req.into_ok_response()?
-                .write(html.as_bytes())?;
-            Ok(())
-        })?;
+        server.fn_handler("/hotspot-detect.html", Method::Get, serve_index)?;
 
         // Windows connectivity check
         let redirect = url.clone();
@@ -113,14 +218,21 @@ pub fn start_server(
         })?;
     }
 
-    // GET /health
+    // GET /health - also reports record counts so a monitor can spot a blank device after a failed load
     {
+        let store = storage.clone();
         let time = time_source.clone();
         server.fn_handler("/health", Method::Get, move |req| -> Result<(), esp_idf_svc::io::EspIOError> {
-            let timestamp = time.lock().unwrap().unwrap_or(0);
+            let timestamp = lock_time(&time).map(|t| t.utc_secs()).unwrap_or(0);
+            let (tasks, history) = {
+                let s = lock_storage(&store);
+                (s.task_store.tasks.len(), s.history_store.records.len())
+            };
             let body = json!({
                 "status": "ok",
-                "timestamp": timestamp
+                "timestamp": timestamp,
+                "tasks": tasks,
+                "history": history,
             })
             .to_string();
             let mut resp = req.into_ok_response()?;
@@ -129,31 +241,263 @@ pub fn start_server(
         })?;
     }
 
-    // GET /api/tasks
+    // GET /api/screen - mirror of what the device display is showing
+    {
+        let screen = screen.clone();
+        api_handler(&mut server, "/api/screen", Method::Get, move |req| -> Result<(), esp_idf_svc::io::EspIOError> {
+            let body = screen.snapshot.lock().unwrap().to_string();
+            let mut resp = req.into_ok_response()?;
+            resp.write(body.as_bytes())?;
+            Ok(())
+        })?;
+    }
+
+    // GET /api/tasks?sort=due|name|urgency&offset=N&limit=N - X-Total-Count has the unpaged
+    // count; with an ETag, so a polling client gets 304 until something changes
     {
         let store = storage.clone();
         let time = time_source.clone();
-        server.fn_handler("/api/tasks", Method::Get, move |req| -> Result<(), esp_idf_svc::io::EspIOError> {
-            let s = store.lock().unwrap();
+        // The storage revision restarts at 0 on boot; the salt keeps an ETag from before a
+        // restart from matching different data after it
+        let etag_salt = unsafe { esp_idf_svc::sys::esp_random() };
+        api_handler(&mut server, "/api/tasks", Method::Get, move |req| -> Result<(), esp_idf_svc::io::EspIOError> {
+            let uri = req.uri();
+            let sort = query_param(uri, "sort").map(|v| v.parse::<TaskSort>().map_err(|_| "sort must be due, name or urgency"));
+            let offset = query_param(uri, "offset").map(|v| v.parse::<usize>().map_err(|_| "offset must be a whole number"));
+            let limit = query_param(uri, "limit").map(|v| v.parse::<usize>().map_err(|_| "limit must be a whole number"));
+            let (sort, offset, limit) = match (sort.transpose(), offset.transpose(), limit.transpose()) {
+                (Ok(sort), Ok(offset), Ok(limit)) => (sort.unwrap_or(TaskSort::Due), offset.unwrap_or(0), limit),
+                (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => {
+                    let err = json!({"error": e}).to_string();
+                    let mut resp = req.into_response(400, None, &[("Content-Type", "application/json")])?;
+                    resp.write(err.as_bytes())?;
+                    return Ok(());
+                }
+            };
+
+            let s = lock_storage(&store);
             let today = get_today(&time);
-            let tasks = s.get_all_tasks(true);
+            // Task JSON also depends on the date (days until due, urgency)
+            let etag = format!(
+                "\"{:08x}-{}-{}-{}\"",
+                etag_salt,
+                s.task_store.next_id,
+                s.revision(),
+                today.format("%Y%m%d")
+            );
+            if etag_matches(&req, &etag) {
+                let mut resp = req.into_response(304, None, &[("ETag", &etag)])?;
+                resp.write(&[])?;
+                return Ok(());
+            }
+
+            let tasks = s.get_tasks_sorted(sort, today, &s.settings.urgency);
+            let total = tasks.len().to_string();
             let json_tasks: Vec<serde_json::Value> = tasks
                 .iter()
-                .map(|t| {
-                    json!({
-                        "id": t.id,
-                        "name": t.name,
-                        "recurrenceType": t.recurrence_type.as_str(),
-                        "recurrenceValue": t.recurrence_value,
-                        "nextDueDate": t.next_due_date,
-                        "daysUntilDue": t.days_until_due(today),
-                        "urgency": t.urgency(today).as_str(),
-                        "createdAt": t.created_at,
-                        "updatedAt": t.updated_at,
-                    })
-                })
+                .skip(offset)
+                .take(limit.unwrap_or(usize::MAX))
+                .map(|t| task_json(t, today, &s.settings.urgency))
                 .collect();
             let body = serde_json::to_string(&json_tasks).unwrap_or_else(|_| "[]".into());
+            // no-cache: browsers may keep the body but must revalidate with If-None-Match
+            let mut resp = req.into_response(
+                200,
+                None,
+                &[
+                    ("Content-Type", "application/json"),
+                    ("ETag", &etag),
+                    ("Cache-Control", "no-cache"),
+                    ("X-Total-Count", &total),
+                ],
+            )?;
+            resp.write(body.as_bytes())?;
+            Ok(())
+        })?;
+    }
+
+    // GET /api/upcoming?days=N - tasks due in the next N days, grouped by due date (for widgets)
+    {
+        let store = storage.clone();
+        let time = time_source.clone();
+        api_handler(&mut server, "/api/upcoming", Method::Get, move |req| -> Result<(), esp_idf_svc::io::EspIOError> {
+            let days = query_param(req.uri(), "days")
+                .and_then(|v| v.parse::<i64>().ok())
+                .unwrap_or(config::UPCOMING_DEFAULT_DAYS)
+                .clamp(0, config::UPCOMING_MAX_DAYS);
+            let today = get_today(&time);
+            let s = lock_storage(&store);
+
+            let mut grouped = serde_json::Map::new();
+            for (date, tasks) in s.upcoming(today, days as i32) {
+                let json_tasks: Vec<serde_json::Value> =
+                    tasks.iter().map(|t| task_json(t, today, &s.settings.urgency)).collect();
+                grouped.insert(date.format("%Y-%m-%d").to_string(), serde_json::Value::from(json_tasks));
+            }
+
+            let body = serde_json::Value::Object(grouped).to_string();
+            let mut resp = req.into_ok_response()?;
+            resp.write(body.as_bytes())?;
+            Ok(())
+        })?;
+    }
+
+    // GET /api/events - Server-Sent Events: `data: {"rev":N}` whenever storage changes.
+    // The server can only work on one request at a time, so rather than holding the stream
+    // forever it stays open for SSE_HOLD_MS and then closes; the client reconnects after the
+    // `retry` delay and sends the last seen revision back as Last-Event-ID, so a change made
+    // while disconnected is reported on the next connection.
+    {
+        let store = storage.clone();
+        api_handler(&mut server, "/api/events", Method::Get, move |req| -> Result<(), esp_idf_svc::io::EspIOError> {
+            let Some(_slot) = SseSlot::acquire() else {
+                let retry_secs = (config::SSE_RETRY_MS / 1000).max(1).to_string();
+                let mut resp = req.into_response(503, None, &[("Retry-After", retry_secs.as_str())])?;
+                resp.write(b"Too many event streams")?;
+                return Ok(());
+            };
+            let mut last_rev = req.header("Last-Event-ID").and_then(|v| v.trim().parse::<u64>().ok());
+            let mut resp = req.into_response(
+                200,
+                None,
+                &[("Content-Type", "text/event-stream"), ("Cache-Control", "no-cache")],
+            )?;
+            resp.write(format!("retry: {}\n\n", config::SSE_RETRY_MS).as_bytes())?;
+
+            let opened = Instant::now();
+            let mut last_write = opened;
+            loop {
+                let rev = lock_storage(&store).revision();
+                if last_rev != Some(rev) {
+                    resp.write(format!("id: {}\ndata: {{\"rev\":{}}}\n\n", rev, rev).as_bytes())?;
+                    last_rev = Some(rev);
+                    last_write = Instant::now();
+                } else if last_write.elapsed() >= Duration::from_millis(config::SSE_KEEPALIVE_MS) {
+                    // Errors out (ending the stream) once the page has gone away
+                    resp.write(b": keepalive\n\n")?;
+                    last_write = Instant::now();
+                }
+                if opened.elapsed() >= Duration::from_millis(config::SSE_HOLD_MS) {
+                    return Ok(());
+                }
+                std::thread::sleep(Duration::from_millis(config::SSE_POLL_MS));
+            }
+        })?;
+    }
+
+    // GET /api/history?since=YYYY-MM-DD - completions across all tasks, newest first
+    {
+        let store = storage.clone();
+        api_handler(&mut server, "/api/history", Method::Get, move |req| -> Result<(), esp_idf_svc::io::EspIOError> {
+            let since = match query_param(req.uri(), "since") {
+                Some(v) => match NaiveDate::parse_from_str(v, "%Y-%m-%d") {
+                    Ok(date) => Some(date),
+                    Err(_) => {
+                        let err = json!({"error": "since must be YYYY-MM-DD"}).to_string();
+                        let mut resp = req.into_response(400, None, &[("Content-Type", "application/json")])?;
+                        resp.write(err.as_bytes())?;
+                        return Ok(());
+                    }
+                },
+                None => None,
+            };
+            let records = lock_storage(&store).get_history_since(since, config::HISTORY_API_MAX_RECORDS);
+
+            // Written record by record so the full log never sits in RAM as one string
+            let mut resp = req.into_ok_response()?;
+            resp.write(b"[")?;
+            for (i, h) in records.iter().enumerate() {
+                if i > 0 {
+                    resp.write(b",")?;
+                }
+                let record = json!({
+                    "id": h.id,
+                    "taskId": h.task_id,
+                    "completedAt": h.completed_at,
+                    "daysSinceLast": h.days_since_last,
+                    "note": h.note,
+                    "recurrenceLabel": h.recurrence_label_at_completion,
+                });
+                resp.write(record.to_string().as_bytes())?;
+            }
+            resp.write(b"]")?;
+            Ok(())
+        })?;
+    }
+
+    // GET /api/backup - all tasks and history as one document, restorable with POST /api/restore
+    {
+        let store = storage.clone();
+        api_handler(&mut server, "/api/backup", Method::Get, move |req| -> Result<(), esp_idf_svc::io::EspIOError> {
+            // Serialized under the lock so tasks and history match, then written without it
+            let body = lock_storage(&store).backup_json();
+            let body = match body {
+                Ok(body) => body,
+                Err(e) => {
+                    log::error!("Failed to serialize backup: {}", e);
+                    let err = json!({"error": "Failed to create backup"}).to_string();
+                    let mut resp = req.into_response(500, None, &[("Content-Type", "application/json")])?;
+                    resp.write(err.as_bytes())?;
+                    return Ok(());
+                }
+            };
+            let mut resp = req.into_response(
+                200,
+                None,
+                &[
+                    ("Content-Type", "application/json"),
+                    ("Content-Disposition", "attachment; filename=\"days-tracker-backup.json\""),
+                ],
+            )?;
+            resp.write(body.as_bytes())?;
+            Ok(())
+        })?;
+    }
+
+    // POST /api/restore - replace all tasks and history with a backup from GET /api/backup
+    {
+        let store = storage.clone();
+        api_handler(&mut server, "/api/restore", Method::Post, move |mut req| -> Result<(), esp_idf_svc::io::EspIOError> {
+            let mut body = Vec::new();
+            let mut chunk = [0u8; 1024];
+            loop {
+                let len = req.read(&mut chunk).unwrap_or(0);
+                if len == 0 {
+                    break;
+                }
+                body.extend_from_slice(&chunk[..len]);
+                if body.len() > config::RESTORE_BODY_MAX_BYTES {
+                    let err = json!({"error": "Backup too large"}).to_string();
+                    let mut resp = req.into_response(413, None, &[("Content-Type", "application/json")])?;
+                    resp.write(err.as_bytes())?;
+                    return Ok(());
+                }
+            }
+
+            let backup = match serde_json::from_slice::<Backup>(&body) {
+                Ok(backup) => backup,
+                Err(e) => {
+                    let err = json!({"error": format!("Invalid backup: {}", e)}).to_string();
+                    let mut resp = req.into_response(400, None, &[("Content-Type", "application/json")])?;
+                    resp.write(err.as_bytes())?;
+                    return Ok(());
+                }
+            };
+            drop(body);
+
+            let (tasks, history) = (backup.tasks.tasks.len(), backup.history.records.len());
+            let restored = lock_storage(&store).restore(backup);
+            if let Err(e) = restored {
+                let (status, err) = match e {
+                    RestoreError::Invalid(e) => (400, json!({"error": format!("Invalid backup: {}", e)})),
+                    RestoreError::SaveFailed => (500, json!({"error": "Backup loaded but could not be saved"})),
+                };
+                let mut resp = req.into_response(status, None, &[("Content-Type", "application/json")])?;
+                resp.write(err.to_string().as_bytes())?;
+                return Ok(());
+            }
+
+            let body = json!({"status": "ok", "tasks": tasks, "history": history}).to_string();
             let mut resp = req.into_ok_response()?;
             resp.write(body.as_bytes())?;
             Ok(())
@@ -164,7 +508,7 @@ pub fn start_server(
     {
         let store = storage.clone();
         let time = time_source.clone();
-        server.fn_handler("/api/tasks", Method::Post, move |mut req| -> Result<(), esp_idf_svc::io::EspIOError> {
+        api_handler(&mut server, "/api/tasks", Method::Post, move |mut req| -> Result<(), esp_idf_svc::io::EspIOError> {
             let mut buf = [0u8; 1024];
             let len = req.read(&mut buf).unwrap_or(0);
             let body_str = core::str::from_utf8(&buf[..len]).unwrap_or("");
@@ -181,24 +525,27 @@ pub fn start_server(
                         "weekly" => RecurrenceType::Weekly,
                         "monthly" => RecurrenceType::Monthly,
                         "yearly" => RecurrenceType::Yearly,
+                        "weekdays" => RecurrenceType::Weekdays,
+                        "businessdays" => RecurrenceType::Businessdays,
                         _ => RecurrenceType::Daily,
                     };
+                    let new = check_weekday_mask(recurrence_type, data["recurrenceValue"].as_u64().map(|v| v as u32))
+                        .and_then(|_| with_task_options(NewTask::new(name, recurrence_type, rec_value, next_due), &data));
+                    let new = match new {
+                        Ok(new) => new,
+                        Err(e) => {
+                            let err = json!({"error": e}).to_string();
+                            let mut resp = req.into_response(400, None, &[("Content-Type", "application/json")])?;
+                            resp.write(err.as_bytes())?;
+                            return Ok(());
+                        }
+                    };
 
                     let now_iso = get_now_iso(&time);
                     let today = get_today(&time);
-                    let mut s = store.lock().unwrap();
-                    let task = s.create_task(name, recurrence_type, rec_value, next_due, &now_iso);
-
-                    let resp_body = json!({
-                        "id": task.id,
-                        "name": task.name,
-                        "recurrenceType": task.recurrence_type.as_str(),
-                        "recurrenceValue": task.recurrence_value,
-                        "nextDueDate": task.next_due_date,
-                        "daysUntilDue": task.days_until_due(today),
-                        "urgency": task.urgency(today).as_str(),
-                    })
-                    .to_string();
+                    let mut s = lock_storage(&store);
+                    let thresholds = s.settings.urgency;
+                    let resp_body = task_json(s.create_task(new, &now_iso), today, &thresholds).to_string();
 
                     let mut resp = req.into_response(201, None, &[("Content-Type", "application/json")])?;
                     resp.write(resp_body.as_bytes())?;
@@ -213,17 +560,199 @@ pub fn start_server(
         })?;
     }
 
+    // POST /api/tasks/snooze-overdue - move every overdue task to tomorrow (no completion recorded)
+    {
+        let store = storage.clone();
+        let time = time_source.clone();
+        api_handler(&mut server, "/api/tasks/snooze-overdue", Method::Post, move |req| -> Result<(), esp_idf_svc::io::EspIOError> {
+            let now_iso = get_now_iso(&time);
+            let today = get_today(&time);
+            let snoozed = lock_storage(&store).snooze_all_overdue(today, &now_iso);
+            log::info!("Snoozed {} overdue task(s) via API", snoozed);
+
+            let body = json!({"snoozed": snoozed}).to_string();
+            let mut resp = req.into_ok_response()?;
+            resp.write(body.as_bytes())?;
+            Ok(())
+        })?;
+    }
+
+    // POST /api/tasks/batch - create many tasks at once (seeding a new device)
+    {
+        let store = storage.clone();
+        let time = time_source.clone();
+        api_handler(&mut server, "/api/tasks/batch", Method::Post, move |mut req| -> Result<(), esp_idf_svc::io::EspIOError> {
+            let mut body = Vec::new();
+            let mut chunk = [0u8; 512];
+            loop {
+                let len = req.read(&mut chunk).unwrap_or(0);
+                if len == 0 {
+                    break;
+                }
+                body.extend_from_slice(&chunk[..len]);
+                if body.len() > config::BATCH_BODY_MAX_BYTES {
+                    let err = json!({"error": "Batch too large"}).to_string();
+                    let mut resp = req.into_response(413, None, &[("Content-Type", "application/json")])?;
+                    resp.write(err.as_bytes())?;
+                    return Ok(());
+                }
+            }
+
+            let items = match serde_json::from_slice::<serde_json::Value>(&body) {
+                Ok(serde_json::Value::Array(items)) => items,
+                _ => {
+                    let err = json!({"error": "Expected a JSON array of tasks"}).to_string();
+                    let mut resp = req.into_response(400, None, &[("Content-Type", "application/json")])?;
+                    resp.write(err.as_bytes())?;
+                    return Ok(());
+                }
+            };
+
+            let now_iso = get_now_iso(&time);
+            let today = get_today(&time);
+            let mut created = Vec::new();
+            let mut errors = Vec::new();
+            {
+                // One lock and one flash write for the whole batch
+                let mut s = lock_storage(&store);
+                let thresholds = s.settings.urgency;
+                s.batch(|s| {
+                    for (index, item) in items.iter().enumerate() {
+                        match parse_new_task(item) {
+                            Ok(new) => created.push(task_json(s.create_task(new, &now_iso), today, &thresholds)),
+                            Err(e) => errors.push(json!({"index": index, "error": e})),
+                        }
+                    }
+                });
+            }
+            log::info!("Batch create: {} created, {} rejected", created.len(), errors.len());
+
+            let body = json!({"created": created, "errors": errors}).to_string();
+            let mut resp = req.into_response(201, None, &[("Content-Type", "application/json")])?;
+            resp.write(body.as_bytes())?;
+            Ok(())
+        })?;
+    }
+
+    // POST /api/tasks/complete - complete several tasks at once ({"ids": [1, 2, 3]}); each id
+    // gets its own result, so unknown or quota-limited ones don't stop the rest
+    {
+        let store = storage.clone();
+        let time = time_source.clone();
+        api_handler(&mut server, "/api/tasks/complete", Method::Post, move |mut req| -> Result<(), esp_idf_svc::io::EspIOError> {
+            let mut body = Vec::new();
+            let mut chunk = [0u8; 512];
+            loop {
+                let len = req.read(&mut chunk).unwrap_or(0);
+                if len == 0 {
+                    break;
+                }
+                body.extend_from_slice(&chunk[..len]);
+                if body.len() > config::BATCH_BODY_MAX_BYTES {
+                    let err = json!({"error": "Batch too large"}).to_string();
+                    let mut resp = req.into_response(413, None, &[("Content-Type", "application/json")])?;
+                    resp.write(err.as_bytes())?;
+                    return Ok(());
+                }
+            }
+
+            let ids: Option<Vec<u32>> = serde_json::from_slice::<serde_json::Value>(&body)
+                .ok()
+                .and_then(|data| {
+                    data["ids"]
+                        .as_array()?
+                        .iter()
+                        .map(|id| id.as_u64().and_then(|id| u32::try_from(id).ok()))
+                        .collect()
+                });
+            let Some(ids) = ids else {
+                let err = json!({"error": "Expected {\"ids\": [task ids]}"}).to_string();
+                let mut resp = req.into_response(400, None, &[("Content-Type", "application/json")])?;
+                resp.write(err.as_bytes())?;
+                return Ok(());
+            };
+
+            let now_iso = get_now_iso(&time);
+            let today = get_today(&time);
+            let mut results = Vec::new();
+            let mut completed = 0;
+            {
+                // One lock and one flash write for the whole batch
+                let mut s = lock_storage(&store);
+                let thresholds = s.settings.urgency;
+                s.batch(|s| {
+                    for (index, &task_id) in ids.iter().enumerate() {
+                        if ids[..index].contains(&task_id) {
+                            results.push(json!({"id": task_id, "ok": false, "error": "Duplicate id"}));
+                            continue;
+                        }
+                        if api_completion_locked(task_id) {
+                            results.push(json!({"id": task_id, "ok": false, "error": "Task was just completed"}));
+                            continue;
+                        }
+                        match s.complete_task(task_id, &now_iso, today, None) {
+                            CompleteResult::Completed => {
+                                record_api_completion(task_id);
+                                completed += 1;
+                                let task = s.get_task(task_id).map(|t| task_json(t, today, &thresholds));
+                                results.push(json!({"id": task_id, "ok": true, "task": task}));
+                            }
+                            CompleteResult::QuotaReached => {
+                                let message = s.get_task(task_id).and_then(|t| t.quota).map(|q| q.reached_message());
+                                results.push(json!({
+                                    "id": task_id,
+                                    "ok": false,
+                                    "error": "Completion quota reached",
+                                    "message": message,
+                                }));
+                            }
+                            CompleteResult::NotFound => {
+                                results.push(json!({"id": task_id, "ok": false, "error": "Task not found"}));
+                            }
+                        }
+                    }
+                });
+            }
+            log::info!("Bulk complete: {} of {} completed", completed, ids.len());
+
+            let body = json!({"completed": completed, "results": results}).to_string();
+            let mut resp = req.into_ok_response()?;
+            resp.write(body.as_bytes())?;
+            Ok(())
+        })?;
+    }
+
+    // GET /api/time - what the device thinks "now" is (date falls back to 2025-01-01 until synced)
+    {
+        let time = time_source.clone();
+        api_handler(&mut server, "/api/time", Method::Get, move |req| -> Result<(), esp_idf_svc::io::EspIOError> {
+            let epoch = lock_time(&time).map(|t| t.utc_secs());
+            let body = json!({
+                "synced": epoch.is_some(),
+                "epoch": epoch.unwrap_or(0),
+                "date": get_today(&time).format("%Y-%m-%d").to_string(),
+            })
+            .to_string();
+            let mut resp = req.into_ok_response()?;
+            resp.write(body.as_bytes())?;
+            Ok(())
+        })?;
+    }
+
     // POST /api/time - receive timestamp from phone JS for RTC sync
     {
         let time = time_source.clone();
-        server.fn_handler("/api/time", Method::Post, move |mut req| -> Result<(), esp_idf_svc::io::EspIOError> {
+        let nvs = nvs_partition.clone();
+        api_handler(&mut server, "/api/time", Method::Post, move |mut req| -> Result<(), esp_idf_svc::io::EspIOError> {
             let mut buf = [0u8; 256];
             let len = req.read(&mut buf).unwrap_or(0);
             let body_str = core::str::from_utf8(&buf[..len]).unwrap_or("");
 
             if let Ok(data) = serde_json::from_str::<serde_json::Value>(body_str) {
                 if let Some(ts) = data["timestamp"].as_i64() {
-                    *time.lock().unwrap() = Some(ts / 1000); // JS sends milliseconds
+                    // JS sends milliseconds (UTC); local dates use the saved offset
+                    let offset_mins = device::load_tz_offset(&nvs).unwrap_or(0);
+                    *lock_time(&time) = Some(SyncedTime::new(ts / 1000, offset_mins as i64 * 60));
                     log::info!("Time synced from phone: {}", ts / 1000);
                 }
             }
@@ -235,19 +764,197 @@ pub fn start_server(
         })?;
     }
 
+    // GET /api/settings
+    {
+        let store = storage.clone();
+        let nvs = nvs_partition.clone();
+        api_handler(&mut server, "/api/settings", Method::Get, move |req| -> Result<(), esp_idf_svc::io::EspIOError> {
+            let tz_offset = device::load_tz_offset(&nvs);
+            let body = settings_json(&lock_storage(&store).settings, tz_offset).to_string();
+            let mut resp = req.into_ok_response()?;
+            resp.write(body.as_bytes())?;
+            Ok(())
+        })?;
+    }
+
+    // PUT /api/settings - partial update, omitted fields keep their value
+    {
+        let store = storage.clone();
+        let time = time_source.clone();
+        let nvs = nvs_partition.clone();
+        let device = device.clone();
+        api_handler(&mut server, "/api/settings", Method::Put, move |mut req| -> Result<(), esp_idf_svc::io::EspIOError> {
+            let mut buf = [0u8; 512];
+            let len = req.read(&mut buf).unwrap_or(0);
+            let body_str = core::str::from_utf8(&buf[..len]).unwrap_or("");
+
+            let data: serde_json::Value = match serde_json::from_str(body_str) {
+                Ok(d) => d,
+                Err(_) => {
+                    let err = json!({"error": "Invalid JSON"}).to_string();
+                    let mut resp = req.into_response(400, None, &[("Content-Type", "application/json")])?;
+                    resp.write(err.as_bytes())?;
+                    return Ok(());
+                }
+            };
+
+            // Every field is validated before anything is applied, so a rejected request
+            // leaves both NVS and the running settings untouched. A failed save does too: the
+            // settings are saved first and put back if the UTC offset then can't be saved.
+            let new_tz_offset = if data["tzOffsetMinutes"].is_null() {
+                None
+            } else {
+                let minutes = data["tzOffsetMinutes"]
+                    .as_i64()
+                    .filter(|m| (config::TZ_OFFSET_MIN_MINUTES as i64..=config::TZ_OFFSET_MAX_MINUTES as i64).contains(m));
+                let Some(minutes) = minutes else {
+                    let err = json!({
+                        "error": format!(
+                            "tzOffsetMinutes must be a whole number from {} to {}",
+                            config::TZ_OFFSET_MIN_MINUTES,
+                            config::TZ_OFFSET_MAX_MINUTES
+                        )
+                    })
+                    .to_string();
+                    let mut resp = req.into_response(400, None, &[("Content-Type", "application/json")])?;
+                    resp.write(err.as_bytes())?;
+                    return Ok(());
+                };
+                Some(minutes as i32)
+            };
+
+            let mut s = lock_storage(&store);
+            let mut settings = s.settings.clone();
+
+            if let Some(cells) = data["dashboardCells"].as_array() {
+                let mut parsed = Vec::new();
+                for cell in cells {
+                    match cell.as_str().and_then(|c| c.parse::<DashboardMetric>().ok()) {
+                        Some(m) if !parsed.contains(&m) => parsed.push(m),
+                        Some(_) => {}
+                        None => {
+                            let err = json!({"error": "Unknown dashboard cell"}).to_string();
+                            let mut resp = req.into_response(400, None, &[("Content-Type", "application/json")])?;
+                            resp.write(err.as_bytes())?;
+                            return Ok(());
+                        }
+                    }
+                }
+                if parsed.is_empty() {
+                    let err = json!({"error": "At least one dashboard cell is required"}).to_string();
+                    let mut resp = req.into_response(400, None, &[("Content-Type", "application/json")])?;
+                    resp.write(err.as_bytes())?;
+                    return Ok(());
+                }
+                settings.dashboard_cells = parsed;
+            }
+            if let Some(wake) = data["wakeOnRotation"].as_bool() {
+                settings.wake_on_rotation = wake;
+            }
+            if let Some(show) = data["showClock"].as_bool() {
+                settings.show_clock = show;
+            }
+            if let Some(screensaver) = data["clockScreensaver"].as_bool() {
+                settings.clock_screensaver = screensaver;
+            }
+            if let Some(show) = data["showDoneToday"].as_bool() {
+                settings.show_done_today = show;
+            }
+            if let Some(compact) = data["compactTaskList"].as_bool() {
+                settings.compact_task_list = compact;
+            }
+            if let Some(start) = data["weekStart"].as_str() {
+                match start.parse::<WeekStart>() {
+                    Ok(week_start) => settings.week_start = week_start,
+                    Err(()) => {
+                        let err = json!({"error": "weekStart must be \"monday\" or \"sunday\""}).to_string();
+                        let mut resp = req.into_response(400, None, &[("Content-Type", "application/json")])?;
+                        resp.write(err.as_bytes())?;
+                        return Ok(());
+                    }
+                }
+            }
+            if let Some(dwell) = data["completionDwellMs"].as_u64() {
+                settings.completion_dwell_ms = dwell.min(config::COMPLETION_DWELL_MAX_MS as u64) as u32;
+            }
+            if let Some(hold) = data["longPressMs"].as_u64() {
+                settings.long_press_ms =
+                    hold.clamp(config::LONG_PRESS_MIN_MS as u64, config::LONG_PRESS_MAX_MS as u64) as u32;
+            }
+            if let Some(debounce) = data["buttonDebounceMs"].as_u64() {
+                settings.button_debounce_ms = debounce.min(config::BUTTON_DEBOUNCE_MAX_MS as u64) as u32;
+            }
+            if let Some(pct) = data["brightness"].as_u64() {
+                settings.brightness = pct.clamp(config::BRIGHTNESS_MIN_PCT as u64, 100) as u8;
+            }
+            if let Some(pct) = data["dimBrightness"].as_u64() {
+                settings.dim_brightness = pct.min(100) as u8;
+            }
+            if let Some(grace) = data["urgency"]["overdueGraceDays"].as_i64() {
+                settings.urgency.overdue_grace_days = grace.clamp(0, 30) as i32;
+            }
+            if let Some(week) = data["urgency"]["weekDays"].as_i64() {
+                settings.urgency.week_days = week.clamp(1, 60) as i32;
+            }
+
+            let settings_changed = settings != s.settings;
+            if settings_changed {
+                if let Some(ref nvs_part) = nvs {
+                    if let Err(e) = device::save_settings(nvs_part, &settings) {
+                        log::error!("Failed to save settings: {}", e);
+                        let err = json!({"error": "Failed to save settings"}).to_string();
+                        let mut resp = req.into_response(500, None, &[("Content-Type", "application/json")])?;
+                        resp.write(err.as_bytes())?;
+                        return Ok(());
+                    }
+                }
+            }
+            // Kept under its own NVS key, since the NTP time source reads it before storage mounts
+            if let Some(minutes) = new_tz_offset {
+                if let Err(e) = apply_tz_offset(&time, &nvs, minutes) {
+                    log::error!("Failed to save UTC offset: {}", e);
+                    if let (true, Some(nvs_part)) = (settings_changed, nvs.as_ref()) {
+                        if let Err(e) = device::save_settings(nvs_part, &s.settings) {
+                            log::error!("Failed to put back the previous settings: {}", e);
+                        }
+                    }
+                    let err = json!({"error": "Failed to save UTC offset"}).to_string();
+                    let mut resp = req.into_response(500, None, &[("Content-Type", "application/json")])?;
+                    resp.write(err.as_bytes())?;
+                    return Ok(());
+                }
+            }
+            if settings_changed {
+                s.set_settings(settings);
+                device.settings_changed.store(true, Ordering::Relaxed);
+            }
+
+            let tz_offset = device::load_tz_offset(&nvs);
+            let body = settings_json(&s.settings, tz_offset).to_string();
+            let mut resp = req.into_ok_response()?;
+            resp.write(body.as_bytes())?;
+            Ok(())
+        })?;
+    }
+
     // === WiFi management endpoints ===
+    // Status, scan and connect make up the setup page, so they stay open in AP mode
+    let ap_mode = !wifi_mode.is_station();
 
     // GET /api/wifi/status
     {
         let mode = wifi_mode.clone();
-        server.fn_handler("/api/wifi/status", Method::Get, move |req| -> Result<(), esp_idf_svc::io::EspIOError> {
+        let device = device.clone();
+        setup_handler(&mut server, "/api/wifi/status", Method::Get, ap_mode, move |req| -> Result<(), esp_idf_svc::io::EspIOError> {
             let ip = mode.ip();
             let body = json!({
                 "mode": mode.mode_str(),
                 "ssid": mode.ssid().unwrap_or(""),
                 "ip": format!("{}.{}.{}.{}", ip[0], ip[1], ip[2], ip[3]),
                 "connected": mode.is_station(),
-                "hostname": "daystracker",
+                "rssi": if mode.is_station() { wifi::rssi() } else { None },
+                "hostname": config::MDNS_HOSTNAME,
+                "deviceName": *device.name.lock().unwrap(),
             })
             .to_string();
             let mut resp = req.into_ok_response()?;
@@ -259,7 +966,7 @@ pub fn start_server(
     // GET /api/wifi/scan
     {
         let shared_w = shared_wifi.clone();
-        server.fn_handler("/api/wifi/scan", Method::Get, move |req| -> Result<(), esp_idf_svc::io::EspIOError> {
+        setup_handler(&mut server, "/api/wifi/scan", Method::Get, ap_mode, move |req| -> Result<(), esp_idf_svc::io::EspIOError> {
             if let Some(ref w) = shared_w {
                 let mut wifi_guard = w.lock().unwrap();
                 let networks = wifi::scan_networks(&mut wifi_guard);
@@ -285,73 +992,344 @@ pub fn start_server(
         })?;
     }
 
-    // POST /api/wifi/connect
+    // POST /api/wifi/connect
+    {
+        let nvs = nvs_partition.clone();
+        setup_handler(&mut server, "/api/wifi/connect", Method::Post, ap_mode, move |mut req| -> Result<(), esp_idf_svc::io::EspIOError> {
+            let mut buf = [0u8; 512];
+            let len = req.read(&mut buf).unwrap_or(0);
+            let body_str = core::str::from_utf8(&buf[..len]).unwrap_or("");
+
+            if let Ok(data) = serde_json::from_str::<serde_json::Value>(body_str) {
+                let ssid = data["ssid"].as_str().unwrap_or("");
+                let password = data["password"].as_str().unwrap_or("");
+
+                if ssid.is_empty() {
+                    let err = json!({"error": "SSID required"}).to_string();
+                    let mut resp = req.into_response(400, None, &[("Content-Type", "application/json")])?;
+                    resp.write(err.as_bytes())?;
+                    return Ok(());
+                }
+
+                if let Some(ref nvs_part) = nvs {
+                    if let Err(e) = wifi::save_wifi_creds(nvs_part, ssid, password) {
+                        log::error!("Failed to save WiFi creds: {}", e);
+                        let err = json!({"error": "Failed to save credentials"}).to_string();
+                        let mut resp = req.into_response(500, None, &[("Content-Type", "application/json")])?;
+                        resp.write(err.as_bytes())?;
+                        return Ok(());
+                    }
+                }
+
+                let body = json!({"status": "ok", "message": "Credentials saved. Restarting..."}).to_string();
+                let mut resp = req.into_ok_response()?;
+                resp.write(body.as_bytes())?;
+
+                // Schedule restart after response is sent
+                std::thread::spawn(|| {
+                    std::thread::sleep(std::time::Duration::from_secs(1));
+                    log::info!("Restarting to apply WiFi credentials...");
+                    unsafe { esp_idf_svc::sys::esp_restart(); }
+                });
+            } else {
+                let err = json!({"error": "Invalid JSON"}).to_string();
+                let mut resp = req.into_response(400, None, &[("Content-Type", "application/json")])?;
+                resp.write(err.as_bytes())?;
+            }
+            Ok(())
+        })?;
+    }
+
+    // DELETE /api/wifi/credentials
+    {
+        let nvs = nvs_partition.clone();
+        api_handler(&mut server, "/api/wifi/credentials", Method::Delete, move |req| -> Result<(), esp_idf_svc::io::EspIOError> {
+            if let Some(ref nvs_part) = nvs {
+                let _ = wifi::clear_wifi_creds(nvs_part);
+            }
+
+            let body = json!({"status": "ok", "message": "Credentials cleared. Restarting..."}).to_string();
+            let mut resp = req.into_ok_response()?;
+            resp.write(body.as_bytes())?;
+
+            // Schedule restart
+            std::thread::spawn(|| {
+                std::thread::sleep(std::time::Duration::from_secs(1));
+                log::info!("Restarting after WiFi credential reset...");
+                unsafe { esp_idf_svc::sys::esp_restart(); }
+            });
+
+            Ok(())
+        })?;
+    }
+
+    // === Device identity endpoints ===
+
+    // GET /api/device
+    {
+        let device = device.clone();
+        api_handler(&mut server, "/api/device", Method::Get, move |req| -> Result<(), esp_idf_svc::io::EspIOError> {
+            let body = device_json(&device).to_string();
+            let mut resp = req.into_ok_response()?;
+            resp.write(body.as_bytes())?;
+            Ok(())
+        })?;
+    }
+
+    // PUT /api/device - rename this device (shown on the settings screen) and/or invert the encoder
+    {
+        let device = device.clone();
+        let nvs = nvs_partition.clone();
+        api_handler(&mut server, "/api/device", Method::Put, move |mut req| -> Result<(), esp_idf_svc::io::EspIOError> {
+            let mut buf = [0u8; 256];
+            let len = req.read(&mut buf).unwrap_or(0);
+            let body_str = core::str::from_utf8(&buf[..len]).unwrap_or("");
+
+            let data = serde_json::from_str::<serde_json::Value>(body_str).unwrap_or_default();
+            let invert = data["invertEncoder"].as_bool();
+            let name = data["name"].as_str().map(|n| n.trim().to_string());
+            let bad_name = match name {
+                Some(ref name) => name.is_empty() || name.chars().count() > config::DEVICE_NAME_MAX_CHARS,
+                None => invert.is_none(),
+            };
+            if bad_name {
+                let err = json!({
+                    "error": format!("Name must be 1-{} characters", config::DEVICE_NAME_MAX_CHARS)
+                })
+                .to_string();
+                let mut resp = req.into_response(400, None, &[("Content-Type", "application/json")])?;
+                resp.write(err.as_bytes())?;
+                return Ok(());
+            }
+
+            if let Some(name) = name {
+                if let Some(ref nvs_part) = nvs {
+                    if let Err(e) = device::save_device_name(nvs_part, &name) {
+                        log::error!("Failed to save device name: {}", e);
+                        let err = json!({"error": "Failed to save device name"}).to_string();
+                        let mut resp = req.into_response(500, None, &[("Content-Type", "application/json")])?;
+                        resp.write(err.as_bytes())?;
+                        return Ok(());
+                    }
+                }
+                *device.name.lock().unwrap() = name;
+            }
+
+            if let Some(invert) = invert {
+                if let Some(ref nvs_part) = nvs {
+                    if let Err(e) = device::save_invert_encoder(nvs_part, invert) {
+                        log::error!("Failed to save encoder direction: {}", e);
+                        let err = json!({"error": "Failed to save encoder direction"}).to_string();
+                        let mut resp = req.into_response(500, None, &[("Content-Type", "application/json")])?;
+                        resp.write(err.as_bytes())?;
+                        return Ok(());
+                    }
+                }
+                device.invert_encoder.store(invert, Ordering::Relaxed);
+            }
+
+            let body = device_json(&device).to_string();
+            let mut resp = req.into_ok_response()?;
+            resp.write(body.as_bytes())?;
+            Ok(())
+        })?;
+    }
+
+    // PUT /api/pin - {"pin": "1234"} to require a PIN on every /api request, null to remove it
+    {
+        let nvs = nvs_partition.clone();
+        api_handler(&mut server, "/api/pin", Method::Put, move |mut req| -> Result<(), esp_idf_svc::io::EspIOError> {
+            let mut buf = [0u8; 128];
+            let len = req.read(&mut buf).unwrap_or(0);
+            let body_str = core::str::from_utf8(&buf[..len]).unwrap_or("");
+
+            let pin = match serde_json::from_str::<serde_json::Value>(body_str) {
+                Ok(data) if data["pin"].is_null() || data["pin"] == "" => None,
+                Ok(data) => match data["pin"].as_str() {
+                    Some(pin)
+                        if (config::API_PIN_MIN_LEN..=config::API_PIN_MAX_LEN).contains(&pin.len())
+                            && pin.bytes().all(|b| b.is_ascii_digit()) =>
+                    {
+                        Some(String::from(pin))
+                    }
+                    _ => {
+                        let err = json!({
+                            "error": format!(
+                                "pin must be {}-{} digits, or null to remove it",
+                                config::API_PIN_MIN_LEN,
+                                config::API_PIN_MAX_LEN
+                            )
+                        })
+                        .to_string();
+                        let mut resp = req.into_response(400, None, &[("Content-Type", "application/json")])?;
+                        resp.write(err.as_bytes())?;
+                        return Ok(());
+                    }
+                },
+                Err(_) => {
+                    let err = json!({"error": "Invalid JSON"}).to_string();
+                    let mut resp = req.into_response(400, None, &[("Content-Type", "application/json")])?;
+                    resp.write(err.as_bytes())?;
+                    return Ok(());
+                }
+            };
+
+            if let Some(ref nvs_part) = nvs {
+                if let Err(e) = device::save_api_pin(nvs_part, pin.as_deref()) {
+                    log::error!("Failed to save API PIN: {}", e);
+                    let err = json!({"error": "Failed to save PIN"}).to_string();
+                    let mut resp = req.into_response(500, None, &[("Content-Type", "application/json")])?;
+                    resp.write(err.as_bytes())?;
+                    return Ok(());
+                }
+            }
+            let pin_set = pin.is_some();
+            *lock_recovering(&API_PIN_DIGEST, "pin") = pin.map(|pin| sha256(pin.as_bytes()));
+
+            let body = json!({"pinSet": pin_set}).to_string();
+            let mut resp = req.into_ok_response()?;
+            resp.write(body.as_bytes())?;
+            Ok(())
+        })?;
+    }
+
+    // === Display mounting endpoints ===
+
+    // GET /api/display - saved rotation/offset (what the next boot will use)
     {
         let nvs = nvs_partition.clone();
-        server.fn_handler("/api/wifi/connect", Method::Post, move |mut req| -> Result<(), esp_idf_svc::io::EspIOError> {
-            let mut buf = [0u8; 512];
+        api_handler(&mut server, "/api/display", Method::Get, move |req| -> Result<(), esp_idf_svc::io::EspIOError> {
+            let display = nvs
+                .as_ref()
+                .map(display_config::load_display_config)
+                .unwrap_or_default();
+            let body = display_json(&display).to_string();
+            let mut resp = req.into_ok_response()?;
+            resp.write(body.as_bytes())?;
+            Ok(())
+        })?;
+    }
+
+    // PUT /api/display - partial update; rotation applies right away, the rest after restart
+    {
+        let device = device.clone();
+        let nvs = nvs_partition.clone();
+        api_handler(&mut server, "/api/display", Method::Put, move |mut req| -> Result<(), esp_idf_svc::io::EspIOError> {
+            let mut buf = [0u8; 256];
             let len = req.read(&mut buf).unwrap_or(0);
             let body_str = core::str::from_utf8(&buf[..len]).unwrap_or("");
 
-            if let Ok(data) = serde_json::from_str::<serde_json::Value>(body_str) {
-                let ssid = data["ssid"].as_str().unwrap_or("");
-                let password = data["password"].as_str().unwrap_or("");
-
-                if ssid.is_empty() {
-                    let err = json!({"error": "SSID required"}).to_string();
+            let (data, nvs_part) = match (serde_json::from_str::<serde_json::Value>(body_str), nvs.as_ref()) {
+                (Ok(d), Some(n)) => (d, n),
+                (Err(_), _) => {
+                    let err = json!({"error": "Invalid JSON"}).to_string();
                     let mut resp = req.into_response(400, None, &[("Content-Type", "application/json")])?;
                     resp.write(err.as_bytes())?;
                     return Ok(());
                 }
-
-                if let Some(ref nvs_part) = nvs {
-                    if let Err(e) = wifi::save_wifi_creds(nvs_part, ssid, password) {
-                        log::error!("Failed to save WiFi creds: {}", e);
-                        let err = json!({"error": "Failed to save credentials"}).to_string();
-                        let mut resp = req.into_response(500, None, &[("Content-Type", "application/json")])?;
-                        resp.write(err.as_bytes())?;
-                        return Ok(());
-                    }
+                (Ok(_), None) => {
+                    let err = json!({"error": "NVS unavailable"}).to_string();
+                    let mut resp = req.into_response(500, None, &[("Content-Type", "application/json")])?;
+                    resp.write(err.as_bytes())?;
+                    return Ok(());
                 }
+            };
 
-                let body = json!({"status": "ok", "message": "Credentials saved. Restarting..."}).to_string();
-                let mut resp = req.into_ok_response()?;
-                resp.write(body.as_bytes())?;
+            let mut display = display_config::load_display_config(nvs_part);
+            if let Some(deg) = data["rotation"].as_u64() {
+                if !DisplayConfig::is_valid_rotation(deg as u16) {
+                    let err = json!({
+                        "error": "Rotation must be 90 or 270 (UI is landscape; 0/180 would need width and height swapped)"
+                    })
+                    .to_string();
+                    let mut resp = req.into_response(400, None, &[("Content-Type", "application/json")])?;
+                    resp.write(err.as_bytes())?;
+                    return Ok(());
+                }
+                display.rotation_deg = deg as u16;
+            }
+            if let Some(x) = data["offsetX"].as_u64() {
+                display.offset_x = x.min(32) as u16;
+            }
+            if let Some(y) = data["offsetY"].as_u64() {
+                display.offset_y = y.min(32) as u16;
+            }
+            if let Some(hz) = data["spiFreqHz"].as_u64() {
+                if !DisplayConfig::is_valid_spi_freq(hz.min(u32::MAX as u64) as u32) {
+                    let err = json!({"error": "spiFreqHz out of range"}).to_string();
+                    let mut resp = req.into_response(400, None, &[("Content-Type", "application/json")])?;
+                    resp.write(err.as_bytes())?;
+                    return Ok(());
+                }
+                display.spi_freq_hz = hz as u32;
+            }
 
-                // Schedule restart after response is sent
-                std::thread::spawn(|| {
-                    std::thread::sleep(std::time::Duration::from_secs(1));
-                    log::info!("Restarting to apply WiFi credentials...");
-                    unsafe { esp_idf_svc::sys::esp_restart(); }
-                });
-            } else {
-                let err = json!({"error": "Invalid JSON"}).to_string();
-                let mut resp = req.into_response(400, None, &[("Content-Type", "application/json")])?;
+            if let Err(e) = display_config::save_display_config(nvs_part, &display) {
+                log::error!("Failed to save display config: {}", e);
+                let err = json!({"error": "Failed to save display config"}).to_string();
+                let mut resp = req.into_response(500, None, &[("Content-Type", "application/json")])?;
                 resp.write(err.as_bytes())?;
+                return Ok(());
             }
+            device.rotation_deg.store(display.rotation_deg, Ordering::Relaxed);
+
+            let body = display_json(&display).to_string();
+            let mut resp = req.into_ok_response()?;
+            resp.write(body.as_bytes())?;
             Ok(())
         })?;
     }
 
-    // DELETE /api/wifi/credentials
-    {
-        let nvs = nvs_partition.clone();
-        server.fn_handler("/api/wifi/credentials", Method::Delete, move |req| -> Result<(), esp_idf_svc::io::EspIOError> {
-            if let Some(ref nvs_part) = nvs {
-                let _ = wifi::clear_wifi_creds(nvs_part);
-            }
+    // === Debug endpoints ===
 
-            let body = json!({"status": "ok", "message": "Credentials cleared. Restarting..."}).to_string();
+    // GET /api/debug/logs?n=N - most recent log lines from the RAM ring buffer
+    #[cfg(feature = "debug")]
+    {
+        api_handler(&mut server, "/api/debug/logs", Method::Get, |req| -> Result<(), esp_idf_svc::io::EspIOError> {
+            let limit = query_param(req.uri(), "n")
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(config::LOG_BUFFER_LINES);
+            let entries = crate::log_buffer::recent(limit);
+            let json_entries: Vec<serde_json::Value> = entries
+                .iter()
+                .map(|e| {
+                    json!({
+                        "uptimeMs": e.uptime_ms,
+                        "level": e.level.as_str(),
+                        "message": e.message,
+                    })
+                })
+                .collect();
+            let body = serde_json::to_string(&json_entries).unwrap_or_else(|_| "[]".into());
             let mut resp = req.into_ok_response()?;
             resp.write(body.as_bytes())?;
+            Ok(())
+        })?;
+    }
 
-            // Schedule restart
-            std::thread::spawn(|| {
-                std::thread::sleep(std::time::Duration::from_secs(1));
-                log::info!("Restarting after WiFi credential reset...");
-                unsafe { esp_idf_svc::sys::esp_restart(); }
-            });
+    // POST /api/debug/render - show an arbitrary view on the device (body: {"view": "dashboard", ...})
+    // The result is visible via GET /api/screen; normal UI returns on input or after a timeout.
+    #[cfg(feature = "debug")]
+    {
+        let screen = screen.clone();
+        api_handler(&mut server, "/api/debug/render", Method::Post, move |mut req| -> Result<(), esp_idf_svc::io::EspIOError> {
+            let mut buf = [0u8; 512];
+            let len = req.read(&mut buf).unwrap_or(0);
+            let body_str = core::str::from_utf8(&buf[..len]).unwrap_or("");
 
+            match serde_json::from_str::<serde_json::Value>(body_str) {
+                Ok(data) if data["view"].as_str().is_some_and(|v| v.parse::<ViewState>().is_ok()) => {
+                    screen.render_requests.lock().unwrap().push_back(data);
+                    let body = json!({"status": "queued"}).to_string();
+                    let mut resp = req.into_response(202, None, &[("Content-Type", "application/json")])?;
+                    resp.write(body.as_bytes())?;
+                }
+                _ => {
+                    let err = json!({"error": "Invalid JSON or unknown view"}).to_string();
+                    let mut resp = req.into_response(400, None, &[("Content-Type", "application/json")])?;
+                    resp.write(err.as_bytes())?;
+                }
+            }
             Ok(())
         })?;
     }
@@ -364,7 +1342,48 @@ pub fn start_server(
     Ok(server)
 }
 
-/// Register routes for /api/tasks/* (GET, PUT, DELETE single task + complete + history)
+/// Web UI, gzipped at build time (build.rs) and sent compressed when the client accepts it
+/// (roughly a quarter of the size, which matters over a weak AP link)
+fn serve_index(req: Request<&mut EspHttpConnection>) -> Result<(), esp_idf_svc::io::EspIOError> {
+    const HTML: &str = include_str!("../static/index.html");
+    const HTML_GZ: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/index.html.gz"));
+
+    if req.header("Accept-Encoding").is_some_and(accepts_gzip) {
+        req.into_response(
+            200,
+            None,
+            &[("Content-Type", "text/html"), ("Content-Encoding", "gzip"), ("Vary", "Accept-Encoding")],
+        )?
+        .write(HTML_GZ)?;
+    } else {
+        req.into_ok_response()?.write(HTML.as_bytes())?;
+    }
+    Ok(())
+}
+
+/// Whether an Accept-Encoding value allows gzip: listed as `gzip` (or covered by `*` when not
+/// listed) with a non-zero q-value. `gzip;q=0` refuses it, and tokens like `gzipx` don't count.
+fn accepts_gzip(accept_encoding: &str) -> bool {
+    let mut gzip = None;
+    let mut any = None;
+    for entry in accept_encoding.split(',') {
+        let mut parts = entry.split(';');
+        let coding = parts.next().unwrap_or("").trim();
+        // A q-value that doesn't parse is taken as a refusal; plain HTML always works
+        let q = parts
+            .filter_map(|p| p.trim().split_once('='))
+            .find(|(k, _)| k.trim().eq_ignore_ascii_case("q"))
+            .map_or(1.0, |(_, v)| v.trim().parse::<f32>().unwrap_or(0.0));
+        if coding.eq_ignore_ascii_case("gzip") || coding.eq_ignore_ascii_case("x-gzip") {
+            gzip = Some(q);
+        } else if coding == "*" {
+            any = Some(q);
+        }
+    }
+    gzip.or(any).is_some_and(|q| q > 0.0)
+}
+
+/// Register routes for /api/tasks/* (GET, PUT, DELETE single task + complete/reset/undo + history)
 fn register_task_routes(
     server: &mut EspHttpServer<'static>,
     storage: SharedStorage,
@@ -374,7 +1393,7 @@ fn register_task_routes(
     {
         let store = storage.clone();
         let time = time_source.clone();
-        server.fn_handler("/api/tasks/*", Method::Get, move |req| -> Result<(), esp_idf_svc::io::EspIOError> {
+        api_handler(server, "/api/tasks/*", Method::Get, move |req| -> Result<(), esp_idf_svc::io::EspIOError> {
             let uri = req.uri();
             let parts: Vec<&str> = uri.trim_start_matches("/api/tasks/").split('/').collect();
 
@@ -395,7 +1414,7 @@ fn register_task_routes(
                 }
             };
 
-            let s = store.lock().unwrap();
+            let s = lock_storage(&store);
             let today = get_today(&time);
 
             if parts.len() >= 2 && parts[1] == "history" {
@@ -416,6 +1435,8 @@ fn register_task_routes(
                             "id": h.id,
                             "completedAt": h.completed_at,
                             "daysSinceLast": h.days_since_last,
+                            "note": h.note,
+                            "recurrenceLabel": h.recurrence_label_at_completion,
                         })
                     })
                     .collect();
@@ -427,18 +1448,7 @@ fn register_task_routes(
                 // GET /api/tasks/:id
                 match s.get_task(task_id) {
                     Some(task) => {
-                        let body = json!({
-                            "id": task.id,
-                            "name": task.name,
-                            "recurrenceType": task.recurrence_type.as_str(),
-                            "recurrenceValue": task.recurrence_value,
-                            "nextDueDate": task.next_due_date,
-                            "daysUntilDue": task.days_until_due(today),
-                            "urgency": task.urgency(today).as_str(),
-                            "createdAt": task.created_at,
-                            "updatedAt": task.updated_at,
-                        })
-                        .to_string();
+                        let body = task_json(task, today, &s.settings.urgency).to_string();
                         let mut resp = req.into_ok_response()?;
                         resp.write(body.as_bytes())?;
                     }
@@ -457,7 +1467,7 @@ fn register_task_routes(
     {
         let store = storage.clone();
         let time = time_source.clone();
-        server.fn_handler("/api/tasks/*", Method::Put, move |mut req| -> Result<(), esp_idf_svc::io::EspIOError> {
+        api_handler(server, "/api/tasks/*", Method::Put, move |mut req| -> Result<(), esp_idf_svc::io::EspIOError> {
             let uri = req.uri().to_string();
             let task_id_str = uri.trim_start_matches("/api/tasks/").split('/').next().unwrap_or("");
             let task_id: u32 = match task_id_str.parse() {
@@ -480,27 +1490,56 @@ fn register_task_routes(
                     "weekly" => RecurrenceType::Weekly,
                     "monthly" => RecurrenceType::Monthly,
                     "yearly" => RecurrenceType::Yearly,
+                    "weekdays" => RecurrenceType::Weekdays,
+                    "businessdays" => RecurrenceType::Businessdays,
                     _ => RecurrenceType::Daily,
                 });
                 let rec_value = data["recurrenceValue"].as_u64().map(|v| v as u32);
                 let next_due = data["nextDueDate"].as_str().map(String::from);
+                let catch_up = data["catchUp"].as_bool();
+                let paused = data["paused"].as_bool();
+                let recur_from_completion = data["recurFromCompletion"].as_bool();
+                let options = match parse_task_options(&data) {
+                    Ok(options) => options,
+                    Err(e) => {
+                        let err = json!({"error": e}).to_string();
+                        let mut resp = req.into_response(400, None, &[("Content-Type", "application/json")])?;
+                        resp.write(err.as_bytes())?;
+                        return Ok(());
+                    }
+                };
+
+                let update = TaskUpdate {
+                    name,
+                    recurrence_type: rec_type,
+                    recurrence_value: rec_value,
+                    next_due_date: next_due,
+                    catch_up,
+                    paused,
+                    recur_from_completion,
+                    ..options
+                };
 
                 let now_iso = get_now_iso(&time);
                 let today = get_today(&time);
-                let mut s = store.lock().unwrap();
+                let mut s = lock_storage(&store);
+                let thresholds = s.settings.urgency;
+
+                // Checked against the stored task under the lock, so a partial update can't
+                // pair a day mask with an interval type (or the other way round)
+                let checked = s
+                    .get_task(task_id)
+                    .map_or(Ok(()), |task| check_recurrence_update(task, update.recurrence_type, update.recurrence_value));
+                if let Err(e) = checked {
+                    let err = json!({"error": e}).to_string();
+                    let mut resp = req.into_response(400, None, &[("Content-Type", "application/json")])?;
+                    resp.write(err.as_bytes())?;
+                    return Ok(());
+                }
 
-                match s.update_task(task_id, name, rec_type, rec_value, next_due, &now_iso) {
+                match s.update_task(task_id, update, &now_iso) {
                     Some(task) => {
-                        let body = json!({
-                            "id": task.id,
-                            "name": task.name,
-                            "recurrenceType": task.recurrence_type.as_str(),
-                            "recurrenceValue": task.recurrence_value,
-                            "nextDueDate": task.next_due_date,
-                            "daysUntilDue": task.days_until_due(today),
-                            "urgency": task.urgency(today).as_str(),
-                        })
-                        .to_string();
+                        let body = task_json(task, today, &thresholds).to_string();
                         let mut resp = req.into_ok_response()?;
                         resp.write(body.as_bytes())?;
                     }
@@ -522,7 +1561,7 @@ fn register_task_routes(
     // DELETE /api/tasks/*
     {
         let store = storage.clone();
-        server.fn_handler("/api/tasks/*", Method::Delete, move |req| -> Result<(), esp_idf_svc::io::EspIOError> {
+        api_handler(server, "/api/tasks/*", Method::Delete, move |req| -> Result<(), esp_idf_svc::io::EspIOError> {
             let uri = req.uri().to_string();
             let task_id_str = uri.trim_start_matches("/api/tasks/").split('/').next().unwrap_or("");
             let task_id: u32 = match task_id_str.parse() {
@@ -535,7 +1574,7 @@ fn register_task_routes(
                 }
             };
 
-            let mut s = store.lock().unwrap();
+            let mut s = lock_storage(&store);
             if s.delete_task(task_id) {
                 let mut resp = req.into_response(204, None, &[])?;
                 resp.write(&[])?;
@@ -548,18 +1587,16 @@ fn register_task_routes(
         })?;
     }
 
-    // POST /api/tasks/*/complete
+    // POST /api/tasks/*/complete, /api/tasks/*/reset and /api/tasks/*/undo
+    // (ESP-IDF wildcards only match a trailing '*', so one handler dispatches on the suffix)
     {
         let store = storage.clone();
         let time = time_source.clone();
-        server.fn_handler("/api/tasks/*/complete", Method::Post, move |req| -> Result<(), esp_idf_svc::io::EspIOError> {
+        api_handler(server, "/api/tasks/*", Method::Post, move |mut req| -> Result<(), esp_idf_svc::io::EspIOError> {
             let uri = req.uri().to_string();
-            let task_id_str = uri
-                .trim_start_matches("/api/tasks/")
-                .trim_end_matches("/complete")
-                .split('/')
-                .next()
-                .unwrap_or("");
+            let mut parts = uri.trim_start_matches("/api/tasks/").split('/');
+            let task_id_str = parts.next().unwrap_or("");
+            let action = parts.next().unwrap_or("");
             let task_id: u32 = match task_id_str.parse() {
                 Ok(id) => id,
                 Err(_) => {
@@ -570,18 +1607,101 @@ fn register_task_routes(
                 }
             };
 
+            // Optional body: {"note": "..."} for complete, {"nextDueDate": "YYYY-MM-DD"} for reset
+            let mut buf = [0u8; 512];
+            let len = req.read(&mut buf).unwrap_or(0);
+            let body_str = core::str::from_utf8(&buf[..len]).unwrap_or("");
+            let data = serde_json::from_str::<serde_json::Value>(body_str).unwrap_or_default();
+
             let now_iso = get_now_iso(&time);
             let today = get_today(&time);
-            let mut s = store.lock().unwrap();
+            let mut s = lock_storage(&store);
+
+            if action == "reset" {
+                let new_due = match data["nextDueDate"].as_str() {
+                    None => Some(today),
+                    Some(d) => NaiveDate::parse_from_str(d, "%Y-%m-%d").ok(),
+                };
+                let Some(new_due) = new_due else {
+                    let err = json!({"error": "nextDueDate must be YYYY-MM-DD"}).to_string();
+                    let mut resp = req.into_response(400, None, &[("Content-Type", "application/json")])?;
+                    resp.write(err.as_bytes())?;
+                    return Ok(());
+                };
+                if s.reset_task(task_id, new_due, &now_iso) {
+                    let thresholds = s.settings.urgency;
+                    let body = s
+                        .get_task(task_id)
+                        .map(|t| task_json(t, today, &thresholds))
+                        .unwrap_or_default()
+                        .to_string();
+                    let mut resp = req.into_ok_response()?;
+                    resp.write(body.as_bytes())?;
+                } else {
+                    let err = json!({"error": "Task not found"}).to_string();
+                    let mut resp = req.into_response(404, None, &[("Content-Type", "application/json")])?;
+                    resp.write(err.as_bytes())?;
+                }
+                return Ok(());
+            }
+            if action == "undo" {
+                if s.get_task(task_id).is_none() {
+                    let err = json!({"error": "Task not found"}).to_string();
+                    let mut resp = req.into_response(404, None, &[("Content-Type", "application/json")])?;
+                    resp.write(err.as_bytes())?;
+                } else if s.undo_last_completion(task_id, &now_iso) {
+                    let thresholds = s.settings.urgency;
+                    let body = s
+                        .get_task(task_id)
+                        .map(|t| task_json(t, today, &thresholds))
+                        .unwrap_or_default()
+                        .to_string();
+                    let mut resp = req.into_ok_response()?;
+                    resp.write(body.as_bytes())?;
+                } else {
+                    let err = json!({"error": "Nothing to undo"}).to_string();
+                    let mut resp = req.into_response(409, None, &[("Content-Type", "application/json")])?;
+                    resp.write(err.as_bytes())?;
+                }
+                return Ok(());
+            }
+            if action != "complete" {
+                let err = json!({"error": "Not found"}).to_string();
+                let mut resp = req.into_response(404, None, &[("Content-Type", "application/json")])?;
+                resp.write(err.as_bytes())?;
+                return Ok(());
+            }
+
+            let note = data["note"]
+                .as_str()
+                .map(|n| n.trim().to_string())
+                .filter(|n| !n.is_empty())
+                .map(|n| n.chars().take(config::NOTE_MAX_CHARS).collect::<String>());
 
-            if s.complete_task(task_id, &now_iso, today) {
+            if api_completion_locked(task_id) {
+                let err = json!({"error": "Task was just completed"}).to_string();
+                let mut resp = req.into_response(409, None, &[("Content-Type", "application/json")])?;
+                resp.write(err.as_bytes())?;
+                return Ok(());
+            }
+
+            let result = s.complete_task(task_id, &now_iso, today, note);
+            if result == CompleteResult::Completed {
+                record_api_completion(task_id);
+            }
+            if result == CompleteResult::QuotaReached {
+                let message = s.get_task(task_id).and_then(|t| t.quota).map(|q| q.reached_message());
+                let err = json!({"error": "Completion quota reached", "message": message}).to_string();
+                let mut resp = req.into_response(409, None, &[("Content-Type", "application/json")])?;
+                resp.write(err.as_bytes())?;
+            } else if result == CompleteResult::Completed {
                 if let Some(task) = s.get_task(task_id) {
                     let body = json!({
                         "id": task.id,
                         "name": task.name,
                         "nextDueDate": task.next_due_date,
                         "daysUntilDue": task.days_until_due(today),
-                        "urgency": task.urgency(today).as_str(),
+                        "urgency": task.urgency(today, &s.settings.urgency).as_str(),
                     })
                     .to_string();
                     let mut resp = req.into_ok_response()?;
@@ -603,22 +1723,381 @@ fn register_task_routes(
     Ok(())
 }
 
-/// Get today's date from the shared time source
-pub fn get_today(time: &SharedTime) -> NaiveDate {
-    let secs = time.lock().unwrap().unwrap_or(0);
-    if secs > 0 {
-        chrono::DateTime::from_timestamp(secs, 0)
-            .map(|dt| dt.date_naive())
-            .unwrap_or_else(|| NaiveDate::from_ymd_opt(2025, 1, 1).unwrap())
+/// Serialize a task for API responses (camelCase, with computed due fields)
+fn task_json(task: &Task, today: NaiveDate, thresholds: &UrgencyThresholds) -> serde_json::Value {
+    json!({
+        "id": task.id,
+        "name": task.name,
+        "recurrenceType": task.recurrence_type.as_str(),
+        "recurrenceValue": task.recurrence_value,
+        "recurrenceLabel": task.recurrence_label(),
+        "nextDueDate": task.next_due_date,
+        "daysUntilDue": task.days_until_due(today),
+        "urgency": task.urgency(today, thresholds).as_str(),
+        "catchUp": task.catch_up,
+        "paused": task.paused,
+        "recurFromCompletion": task.recur_from_completion,
+        "dueMessage": task.due_message,
+        "category": task.category,
+        "color": task.color,
+        "pinned": task.pinned,
+        "quota": task.quota.map(|q| json!({
+            "maxPerPeriod": q.max_per_period,
+            "period": q.period.as_str(),
+        })),
+        "createdAt": task.created_at,
+        "updatedAt": task.updated_at,
+    })
+}
+
+/// Validate one batch item (stricter than the single-task POST, which fills in defaults)
+fn parse_new_task(data: &serde_json::Value) -> Result<NewTask, &'static str> {
+    let name = data["name"].as_str().map(str::trim).unwrap_or("");
+    if name.is_empty() {
+        return Err("Name is required");
+    }
+
+    let recurrence_type = match data["recurrenceType"].as_str() {
+        Some(s) => s.parse::<RecurrenceType>().map_err(|_| "Invalid recurrenceType")?,
+        None => RecurrenceType::Daily,
+    };
+
+    let recurrence_value = data["recurrenceValue"].as_u64().unwrap_or(1);
+    if recurrence_value == 0 || recurrence_value > u32::MAX as u64 {
+        return Err("recurrenceValue must be a positive integer");
+    }
+    check_weekday_mask(recurrence_type, data["recurrenceValue"].as_u64().map(|v| v as u32))?;
+
+    let next_due_date = data["nextDueDate"].as_str().unwrap_or("");
+    if NaiveDate::parse_from_str(next_due_date, "%Y-%m-%d").is_err() {
+        return Err("nextDueDate must be YYYY-MM-DD");
+    }
+
+    let new = NewTask::new(String::from(name), recurrence_type, recurrence_value as u32, String::from(next_due_date));
+    with_task_options(new, data)
+}
+
+/// Fill in a new task's optional fields from the request (absent and null both mean unset)
+fn with_task_options(new: NewTask, data: &serde_json::Value) -> Result<NewTask, &'static str> {
+    let options = parse_task_options(data)?;
+    Ok(NewTask {
+        catch_up: data["catchUp"].as_bool().unwrap_or(false),
+        recur_from_completion: data["recurFromCompletion"].as_bool().unwrap_or(false),
+        due_message: options.due_message.flatten(),
+        quota: options.quota.flatten(),
+        category: options.category.flatten(),
+        color: options.color.flatten(),
+        pinned: options.pinned.unwrap_or(false),
+        ..new
+    })
+}
+
+/// Validate the optional fields shared by create, batch create and update.
+/// Only those fields are set; the caller fills in the rest.
+fn parse_task_options(data: &serde_json::Value) -> Result<TaskUpdate, &'static str> {
+    Ok(TaskUpdate {
+        due_message: parse_due_message(data)?,
+        quota: parse_quota(data)?,
+        category: parse_category(data)?,
+        color: parse_tag_color(data)?,
+        pinned: data["pinned"].as_bool(),
+        ..Default::default()
+    })
+}
+
+/// Read an optional "quota": {"maxPerPeriod": n, "period": "daily|weekly|monthly|yearly"}.
+/// Absent = leave unchanged, null = remove the limit.
+fn parse_quota(data: &serde_json::Value) -> Result<Option<Option<CompletionQuota>>, &'static str> {
+    let value = match data.get("quota") {
+        None => return Ok(None),
+        Some(serde_json::Value::Null) => return Ok(Some(None)),
+        Some(v) => v,
+    };
+    let max_per_period = value["maxPerPeriod"].as_u64().unwrap_or(0);
+    if max_per_period == 0 || max_per_period > u32::MAX as u64 {
+        return Err("quota.maxPerPeriod must be a positive integer");
+    }
+    let period = value["period"]
+        .as_str()
+        .and_then(|p| p.parse::<RecurrenceType>().ok())
+        .filter(|p| !matches!(p, RecurrenceType::Weekdays | RecurrenceType::Businessdays))
+        .ok_or("quota.period must be daily, weekly, monthly or yearly")?;
+    Ok(Some(Some(CompletionQuota {
+        max_per_period: max_per_period as u32,
+        period,
+    })))
+}
+
+/// Read an optional "dueMessage": absent = leave unchanged, null or blank = clear
+fn parse_due_message(data: &serde_json::Value) -> Result<Option<Option<String>>, &'static str> {
+    let value = match data.get("dueMessage") {
+        None => return Ok(None),
+        Some(serde_json::Value::Null) => return Ok(Some(None)),
+        Some(v) => v.as_str().ok_or("dueMessage must be a string")?.trim(),
+    };
+    if value.is_empty() {
+        return Ok(Some(None));
+    }
+    if value.chars().count() > config::DUE_MESSAGE_MAX_CHARS {
+        return Err("dueMessage is too long");
+    }
+    Ok(Some(Some(String::from(value))))
+}
+
+/// Read an optional "category" label: absent = leave unchanged, null or blank = clear
+fn parse_category(data: &serde_json::Value) -> Result<Option<Option<String>>, &'static str> {
+    let value = match data.get("category") {
+        None => return Ok(None),
+        Some(serde_json::Value::Null) => return Ok(Some(None)),
+        Some(v) => v.as_str().ok_or("category must be a string")?.trim(),
+    };
+    if value.is_empty() {
+        return Ok(Some(None));
+    }
+    if value.chars().count() > config::CATEGORY_MAX_CHARS {
+        return Err("category is too long");
+    }
+    Ok(Some(Some(String::from(value))))
+}
+
+/// Read an optional "color" ("#RRGGBB"), stored uppercase: absent = leave unchanged, null or blank = clear
+fn parse_tag_color(data: &serde_json::Value) -> Result<Option<Option<String>>, &'static str> {
+    let value = match data.get("color") {
+        None => return Ok(None),
+        Some(serde_json::Value::Null) => return Ok(Some(None)),
+        Some(v) => v.as_str().ok_or("color must be a string")?.trim(),
+    };
+    if value.is_empty() {
+        return Ok(Some(None));
+    }
+    if theme::hex_color(value).is_none() {
+        return Err("color must be #RRGGBB");
+    }
+    Ok(Some(Some(value.to_ascii_uppercase())))
+}
+
+/// Serialize display config for API responses
+fn display_json(display: &DisplayConfig) -> serde_json::Value {
+    json!({
+        "rotation": display.rotation_deg,
+        "offsetX": display.offset_x,
+        "offsetY": display.offset_y,
+        "spiFreqHz": display.spi_freq_hz,
+        "appliesOnRestart": true,
+    })
+}
+
+/// Serialize the device identity for API responses
+fn device_json(device: &DeviceSettings) -> serde_json::Value {
+    json!({
+        "name": *device.name.lock().unwrap(),
+        "invertEncoder": device.invert_encoder.load(Ordering::Relaxed),
+    })
+}
+
+/// Serialize settings for API responses. `tzOffsetSet` is false until an offset is saved
+/// (UTC is used meanwhile), so the web UI knows to send the phone's.
+fn settings_json(settings: &Settings, tz_offset_minutes: Option<i32>) -> serde_json::Value {
+    json!({
+        "tzOffsetMinutes": tz_offset_minutes.unwrap_or(0),
+        "tzOffsetSet": tz_offset_minutes.is_some(),
+        "dashboardCells": settings.dashboard_cells.iter().map(|c| c.as_str()).collect::<Vec<_>>(),
+        "wakeOnRotation": settings.wake_on_rotation,
+        "showClock": settings.show_clock,
+        "clockScreensaver": settings.clock_screensaver,
+        "showDoneToday": settings.show_done_today,
+        "compactTaskList": settings.compact_task_list,
+        "completionDwellMs": settings.completion_dwell_ms,
+        "longPressMs": settings.long_press_ms,
+        "buttonDebounceMs": settings.button_debounce_ms,
+        "brightness": settings.brightness,
+        "dimBrightness": settings.dim_brightness,
+        "weekStart": settings.week_start.as_str(),
+        "urgency": {
+            "overdueGraceDays": settings.urgency.overdue_grace_days,
+            "weekDays": settings.urgency.week_days,
+        },
+    })
+}
+
+/// Get a query string parameter value from a request URI (no percent-decoding)
+fn query_param<'a>(uri: &'a str, key: &str) -> Option<&'a str> {
+    let query = uri.split_once('?')?.1;
+    query.split('&').find_map(|pair| {
+        let (k, v) = pair.split_once('=').unwrap_or((pair, ""));
+        if k == key {
+            Some(v)
+        } else {
+            None
+        }
+    })
+}
+
+/// Register an /api route behind the PIN check: when a PIN is set, requests without it get
+/// 401 and never reach `handler`
+fn api_handler<F>(
+    server: &mut EspHttpServer<'static>,
+    uri: &str,
+    method: Method,
+    handler: F,
+) -> Result<(), esp_idf_svc::sys::EspError>
+where
+    F: for<'r> Fn(Request<&mut EspHttpConnection<'r>>) -> Result<(), esp_idf_svc::io::EspIOError> + Send + 'static,
+{
+    server.fn_handler(uri, method, move |req| {
+        if let Some(wait) = pin_lockout_remaining() {
+            return locked_out(req, wait);
+        }
+        if !check_auth(&req) {
+            return unauthorized(req);
+        }
+        handler(req)
+    })?;
+    Ok(())
+}
+
+/// Register a WiFi setup route: open in AP mode, where a phone on the setup network may not
+/// know the PIN (set before the device lost its network), and behind the PIN otherwise
+fn setup_handler<F>(
+    server: &mut EspHttpServer<'static>,
+    uri: &str,
+    method: Method,
+    ap_mode: bool,
+    handler: F,
+) -> Result<(), esp_idf_svc::sys::EspError>
+where
+    F: for<'r> Fn(Request<&mut EspHttpConnection<'r>>) -> Result<(), esp_idf_svc::io::EspIOError> + Send + 'static,
+{
+    if ap_mode {
+        server.fn_handler(uri, method, handler)?;
+        Ok(())
     } else {
-        // Fallback if time not yet synced
-        NaiveDate::from_ymd_opt(2025, 1, 1).unwrap()
+        api_handler(server, uri, method, handler)
+    }
+}
+
+/// Whether the request may use the API: always when no PIN is set, otherwise it must carry
+/// the PIN as `X-PIN: <pin>` or `Authorization: Bearer <pin>`
+fn check_auth(req: &Request<&mut EspHttpConnection>) -> bool {
+    let Some(expected) = *lock_recovering(&API_PIN_DIGEST, "pin") else {
+        return true;
+    };
+    let given = req
+        .header("X-PIN")
+        .or_else(|| req.header("Authorization").and_then(|v| v.strip_prefix("Bearer ")));
+    let Some(given) = given else {
+        return false;
+    };
+    // Digests are always 32 bytes and every byte is compared, so the response time reveals
+    // neither the PIN's length nor how much of it matched
+    let ok = sha256(given.as_bytes()).iter().zip(expected.iter()).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0;
+    record_pin_attempt(ok);
+    ok
+}
+
+/// Count wrong PINs in a row, locking the API once there are API_PIN_MAX_FAILURES of them.
+/// A right PIN resets the count.
+fn record_pin_attempt(ok: bool) {
+    let mut failures = lock_recovering(&PIN_FAILURES, "pin failures");
+    if ok {
+        *failures = (0, None);
+        return;
+    }
+    failures.0 += 1;
+    if failures.0 >= config::API_PIN_MAX_FAILURES {
+        log::warn!(
+            "{} wrong API PINs in a row, locking the API for {}s",
+            failures.0,
+            config::API_PIN_LOCKOUT_SECS
+        );
+        failures.1 = Some(Instant::now() + Duration::from_secs(config::API_PIN_LOCKOUT_SECS));
+    }
+}
+
+/// Time left in a lockout after too many wrong PINs, if one is running
+fn pin_lockout_remaining() -> Option<Duration> {
+    let (_, until) = *lock_recovering(&PIN_FAILURES, "pin failures");
+    until.and_then(|until| until.checked_duration_since(Instant::now()))
+}
+
+/// SHA-256 via the mbedTLS build in ESP-IDF
+fn sha256(data: &[u8]) -> [u8; 32] {
+    let mut digest = [0u8; 32];
+    // SAFETY: both buffers are valid for the given lengths; 0 selects SHA-256 (not SHA-224)
+    unsafe {
+        esp_idf_svc::sys::mbedtls_sha256(data.as_ptr(), data.len(), digest.as_mut_ptr(), 0);
+    }
+    digest
+}
+
+/// Whether the request's If-None-Match lists `etag` (or is `*`), so a 304 can be sent instead
+fn etag_matches(req: &Request<&mut EspHttpConnection>, etag: &str) -> bool {
+    req.header("If-None-Match").is_some_and(|v| {
+        v.split(',').map(str::trim).any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
+    })
+}
+
+/// True if `task_id` was completed over the API within the last COMPLETE_LOCK_MS
+fn api_completion_locked(task_id: u32) -> bool {
+    lock_recovering(&LAST_API_COMPLETION, "completion")
+        .is_some_and(|(id, at)| id == task_id && at.elapsed() < Duration::from_millis(config::COMPLETE_LOCK_MS))
+}
+
+fn record_api_completion(task_id: u32) {
+    *lock_recovering(&LAST_API_COMPLETION, "completion") = Some((task_id, Instant::now()));
+}
+
+/// 401 response for requests rejected by `check_auth`
+fn unauthorized(req: Request<&mut EspHttpConnection>) -> Result<(), esp_idf_svc::io::EspIOError> {
+    let err = json!({"error": "PIN required"}).to_string();
+    let mut resp = req.into_response(401, None, &[("Content-Type", "application/json")])?;
+    resp.write(err.as_bytes())?;
+    Ok(())
+}
+
+/// 429 while a lockout after too many wrong PINs is running
+fn locked_out(req: Request<&mut EspHttpConnection>, wait: Duration) -> Result<(), esp_idf_svc::io::EspIOError> {
+    let retry_secs = wait.as_secs().max(1).to_string();
+    let err = json!({"error": "Too many wrong PINs, try again later"}).to_string();
+    let mut resp = req.into_response(
+        429,
+        None,
+        &[("Content-Type", "application/json"), ("Retry-After", retry_secs.as_str())],
+    )?;
+    resp.write(err.as_bytes())?;
+    Ok(())
+}
+
+/// Save the local UTC offset for later boots, then set it on the running clock (left alone
+/// if the save fails). The NVS write is skipped when unchanged.
+fn apply_tz_offset(
+    time: &SharedTime,
+    nvs: &Option<EspDefaultNvsPartition>,
+    minutes: i32,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(nvs_part) = nvs {
+        if device::load_tz_offset(nvs) != Some(minutes) {
+            device::save_tz_offset(nvs_part, minutes)?;
+        }
     }
+    if let Some(synced) = lock_time(time).as_mut() {
+        synced.set_utc_offset_secs(minutes as i64 * 60);
+    }
+    Ok(())
+}
+
+/// Get today's date (local, per the UTC offset) from the shared time source
+pub fn get_today(time: &SharedTime) -> NaiveDate {
+    lock_time(time)
+        .filter(|t| t.local_secs() > 0)
+        .and_then(|t| local_date(t.utc_secs(), t.utc_offset_secs()))
+        // Fallback if time not yet synced
+        .unwrap_or_else(|| NaiveDate::from_ymd_opt(2025, 1, 1).unwrap())
 }
 
 /// Get current datetime as ISO string
 pub fn get_now_iso(time: &SharedTime) -> String {
-    let secs = time.lock().unwrap().unwrap_or(0);
+    let secs = lock_time(time).map(|t| t.local_secs()).unwrap_or(0);
     if secs > 0 {
         chrono::DateTime::from_timestamp(secs, 0)
             .map(|dt| dt.format("%Y-%m-%dT%H:%M:%S").to_string())
@@ -627,3 +2106,9 @@ pub fn get_now_iso(time: &SharedTime) -> String {
         String::from("2025-01-01T00:00:00")
     }
 }
+
+/// Current local time as "HH:MM", or None until the phone has synced the clock
+pub fn get_clock_text(time: &SharedTime) -> Option<String> {
+    let secs = lock_time(time).map(|t| t.local_secs())?;
+    chrono::DateTime::from_timestamp(secs, 0).map(|dt| dt.format("%H:%M").to_string())
+}
@@ -0,0 +1,96 @@
+/// In-memory ring buffer log sink
+///
+/// Tees every `log` record to the ESP-IDF serial logger and keeps the most
+/// recent lines in RAM so they can be fetched over HTTP without a USB cable.
+/// Only compiled with the `debug` feature.
+extern crate alloc;
+
+use alloc::collections::VecDeque;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use std::sync::Mutex;
+
+use esp_idf_svc::log::EspLogger;
+use log::{Level, Log, Metadata, Record};
+
+use crate::config;
+
+/// A single captured log line
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    pub uptime_ms: i64,
+    pub level: Level,
+    pub message: String,
+}
+
+/// Logger that forwards to `EspLogger` and records into the ring buffer
+struct RingLogger {
+    serial: EspLogger,
+    lines: Mutex<VecDeque<LogEntry>>,
+}
+
+static LOGGER: RingLogger = RingLogger {
+    serial: EspLogger::new(),
+    lines: Mutex::new(VecDeque::new()),
+};
+
+/// Install the ring buffer logger (replaces `EspLogger::initialize_default`)
+pub fn init() {
+    log::set_logger(&LOGGER)
+        .map(|()| LOGGER.serial.initialize())
+        .unwrap();
+}
+
+/// Get up to `limit` of the most recent log lines, oldest first
+pub fn recent(limit: usize) -> Vec<LogEntry> {
+    match LOGGER.lines.lock() {
+        Ok(lines) => {
+            let skip = lines.len().saturating_sub(limit);
+            lines.iter().skip(skip).cloned().collect()
+        }
+        Err(_) => Vec::new(),
+    }
+}
+
+impl Log for RingLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.serial.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        self.serial.log(record);
+
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let mut message = format!("{}", record.args());
+        if message.len() > config::LOG_LINE_MAX_LEN {
+            // Truncate on a char boundary
+            let mut end = config::LOG_LINE_MAX_LEN;
+            while !message.is_char_boundary(end) {
+                end -= 1;
+            }
+            message.truncate(end);
+        }
+
+        let entry = LogEntry {
+            uptime_ms: unsafe { esp_idf_svc::sys::esp_timer_get_time() } / 1000,
+            level: record.level(),
+            message,
+        };
+
+        if let Ok(mut lines) = self.lines.lock() {
+            if lines.len() >= config::LOG_BUFFER_LINES {
+                lines.pop_front();
+            }
+            lines.push_back(entry);
+        }
+    }
+
+    fn flush(&self) {
+        self.serial.flush();
+    }
+}
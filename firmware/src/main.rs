@@ -10,49 +10,60 @@ extern crate alloc;
 use alloc::format;
 use alloc::string::String;
 
-use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
 use esp_idf_hal::delay::FreeRtos;
-use esp_idf_hal::gpio::PinDriver;
+use esp_idf_hal::gpio::{InputPin, OutputPin, PinDriver};
+use esp_idf_hal::ledc::{config::TimerConfig, LedcDriver, LedcTimerDriver};
 use esp_idf_hal::peripherals::Peripherals;
 use esp_idf_hal::spi::{config::Config as SpiConfig, SpiDeviceDriver, SpiDriverConfig};
 use esp_idf_hal::units::FromValueType;
 use esp_idf_svc::eventloop::EspSystemEventLoop;
+#[cfg(not(feature = "debug"))]
 use esp_idf_svc::log::EspLogger;
 use esp_idf_svc::nvs::EspDefaultNvsPartition;
 
 use display_interface_spi::SPIInterface;
 use mipidsi::models::ST7735s;
-use mipidsi::options::{ColorInversion, Orientation, Rotation};
+use mipidsi::options::{ColorInversion, Orientation};
 use mipidsi::Builder;
 
 mod config;
+mod device;
 mod display;
+mod display_config;
 mod dns;
 mod encoder;
 mod fonts;
 mod http_server;
+#[cfg(feature = "debug")]
+mod log_buffer;
 mod models;
+mod quadrature;
 mod renderer;
 mod storage;
 mod theme;
 mod views;
 mod wifi;
 
+use device::{DeviceSettings, SharedDevice};
 use display::FrameBuffer;
 use encoder::{Encoder, EncoderEvent};
-use http_server::{get_now_iso, get_today, SharedStorage, SharedTime, SharedWifi};
-use models::{HistoryDisplayEntry, TaskDisplayData};
+use http_server::{get_clock_text, get_now_iso, get_today, lock_storage, ScreenShare, SharedScreen, SharedStorage, SharedTime, SharedWifi};
+use models::{HistoryDisplayEntry, Task, TaskDisplayData, TaskRowData};
 use renderer::Renderer;
-use storage::Storage;
+use storage::{CompleteResult, Storage, TaskUpdate};
 use views::{RenderCommand, ViewNavigator, ViewState};
 use wifi::WiFiMode;
 
 fn main() {
     // Initialize ESP-IDF
     esp_idf_svc::sys::link_patches();
+    #[cfg(feature = "debug")]
+    log_buffer::init();
+    #[cfg(not(feature = "debug"))]
     EspLogger::initialize_default();
 
     log::info!("Days Tracker Kiosk Starting...");
@@ -64,6 +75,11 @@ fn main() {
     // === Initialize SPI Display ===
     log::info!("Initializing display...");
 
+    let display_cfg = nvs
+        .as_ref()
+        .map(display_config::load_display_config)
+        .unwrap_or_default();
+
     let spi = peripherals.spi2;
     let sclk = peripherals.pins.gpio19;  // D8 - SPI clock
     let mosi = peripherals.pins.gpio18;  // D10 - SPI MOSI
@@ -78,7 +94,7 @@ fn main() {
         Option::<esp_idf_hal::gpio::AnyIOPin>::None,
         Some(cs),
         &SpiDriverConfig::default(),
-        &SpiConfig::new().baudrate(config::SPI_FREQ_HZ.Hz()),
+        &SpiConfig::new().baudrate(display_cfg.spi_freq_hz.Hz()),
     )
     .unwrap();
 
@@ -87,9 +103,9 @@ fn main() {
     let mut hw_display = Builder::new(ST7735s, spi_iface)
         .reset_pin(rst)
         .invert_colors(ColorInversion::Inverted)
-        .orientation(Orientation::new().rotate(Rotation::Deg90))
+        .orientation(Orientation::new().rotate(display_cfg.rotation()))
         .display_size(128, 160)
-        .display_offset(2, 1)
+        .display_offset(display_cfg.offset_x, display_cfg.offset_y)
         .init(&mut FreeRtos)
         .unwrap();
 
@@ -98,62 +114,96 @@ fn main() {
     // === Initialize Encoder + Backlight ===
     log::info!("Initializing encoder...");
     let backlight_on = Arc::new(AtomicBool::new(true));
+    let invert_encoder = device::load_invert_encoder(&nvs);
+    let backlight_timer = LedcTimerDriver::new(
+        peripherals.ledc.timer0,
+        &TimerConfig::new().frequency(config::BACKLIGHT_PWM_HZ.Hz()),
+    )
+    .unwrap();
+    let backlight = LedcDriver::new(
+        peripherals.ledc.channel0,
+        backlight_timer,
+        peripherals.pins.gpio20,  // D9 - Backlight (PWM)
+    )
+    .unwrap();
     let mut enc = Encoder::new(
         peripherals.pins.gpio0,   // D0 - Encoder CLK (A)
         peripherals.pins.gpio1,   // D1 - Encoder DT (B)
         peripherals.pins.gpio2,   // D2 - Encoder switch
-        peripherals.pins.gpio20,  // D9 - Backlight
+        backlight,
         backlight_on.clone(),
+        invert_encoder,
     )
     .unwrap();
     log::info!("Encoder initialized");
 
     // === Create framebuffer ===
     let mut fb = FrameBuffer::new();
+    Renderer::render_boot_splash(&mut fb);
+    flush_to_display(&mut hw_display, &mut fb);
+
+    // Extract modem before branching (consumed by whichever WiFi mode initializes)
+    let mut modem = peripherals.modem;
+
+    // === Self-test: hold the encoder button during boot ===
+    if boot_button_held(&enc) {
+        log::info!("Button held at boot, entering self-test");
+        run_self_test(&mut hw_display, &mut fb, &mut enc, &mut modem, sysloop.clone(), nvs.clone());
+        log::info!("Self-test finished, continuing boot");
+    }
 
     // === Determine WiFi mode: Station (saved creds) or AP (provisioning) ===
     Renderer::render_connecting(&mut fb, "Starting WiFi...");
-    flush_to_display(&mut hw_display, &fb);
+    flush_to_display(&mut hw_display, &mut fb);
 
     // Clone NVS partition for credential access (separate from WiFi driver)
     let nvs_for_creds = nvs.clone();
 
-    // Extract modem before branching (consumed by whichever WiFi mode initializes)
-    let modem = peripherals.modem;
-
     // Check for saved WiFi credentials
     let saved_creds = nvs_for_creds
         .as_ref()
         .and_then(|nvs_part| wifi::load_wifi_creds(nvs_part));
 
+    // Creds that worked before but failed last boot: stay in AP, retry station later
+    let sta_failed = nvs_for_creds
+        .as_ref()
+        .is_some_and(|nvs_part| wifi::get_wifi_flag(nvs_part, config::NVS_KEY_STA_FAILED));
+    if sta_failed && saved_creds.is_some() {
+        log::warn!("Last station attempt failed, starting in AP mode (will retry station)");
+    }
+
     let (wifi_mode, mut sta_wifi, shared_wifi, _dns_handle): (
         WiFiMode,
         Option<wifi::BlockingWifiHandle>,
         Option<SharedWifi>,
         bool,
-    ) = if let Some(ref creds) = saved_creds {
+    ) = if let Some(ref creds) = saved_creds.as_ref().filter(|_| !sta_failed) {
         // === Station Mode: Connect to saved WiFi ===
         log::info!("Found saved WiFi credentials, trying Station mode...");
         Renderer::render_connecting(&mut fb, &format!("Connecting to {}...", creds.ssid));
-        flush_to_display(&mut hw_display, &fb);
+        flush_to_display(&mut hw_display, &mut fb);
 
-        // Try connecting (single attempt — on failure, clear creds and restart into AP)
+        // Try connecting (a few attempts with backoff — if all fail, restart into AP)
         log::info!("Connecting to '{}'...", creds.ssid);
 
-        let result = wifi::init_station(
-            modem,
-            sysloop.clone(),
-            nvs.clone(),
-            creds,
-        );
+        let result = wifi::init_station(modem, sysloop.clone(), nvs.clone(), creds, |attempt| {
+            if attempt > 1 {
+                let message = format!("Retrying ({}/{})...", attempt, config::STA_CONNECT_ATTEMPTS);
+                Renderer::render_connecting(&mut fb, &message);
+                flush_to_display(&mut hw_display, &mut fb);
+            }
+        });
 
         if let Ok((wifi_inst, ip)) = result {
+            if let Some(ref nvs_part) = nvs_for_creds {
+                wifi::set_wifi_flag(nvs_part, config::NVS_KEY_STA_VERIFIED, true);
+            }
             let ssid = creds.ssid.clone();
             let mode = WiFiMode::Station { ssid: ssid.clone(), ip };
 
             let url = wifi::web_url_from_ip(ip);
             Renderer::render_connected(&mut fb, &ssid, &url);
-            flush_to_display(&mut hw_display, &fb);
+            flush_to_display(&mut hw_display, &mut fb);
             FreeRtos::delay_ms(2000);
 
             log::info!("WiFi Station mode ready: {}", url);
@@ -162,13 +212,19 @@ fn main() {
             // No DNS captive portal needed in STA mode
             (mode, Some(wifi_inst), None::<SharedWifi>, false)
         } else {
-            // Connection failed — clear bad credentials and restart into AP mode
-            log::error!("Station connection failed, clearing credentials and restarting...");
+            // Connection failed — restart into AP mode. Creds that have worked before are kept
+            // (home WiFi may just be down) and retried later; never-working creds are cleared.
             Renderer::render_wifi_failed(&mut fb, &creds.ssid);
-            flush_to_display(&mut hw_display, &fb);
+            flush_to_display(&mut hw_display, &mut fb);
 
             if let Some(ref nvs_part) = nvs_for_creds {
-                let _ = wifi::clear_wifi_creds(nvs_part);
+                if config::AP_STA_RETRY_MINS > 0 && wifi::get_wifi_flag(nvs_part, config::NVS_KEY_STA_VERIFIED) {
+                    log::error!("Station connection failed, keeping credentials and restarting into AP...");
+                    wifi::set_wifi_flag(nvs_part, config::NVS_KEY_STA_FAILED, true);
+                } else {
+                    log::error!("Station connection failed, clearing credentials and restarting...");
+                    let _ = wifi::clear_wifi_creds(nvs_part);
+                }
             }
 
             FreeRtos::delay_ms(3000);
@@ -181,7 +237,7 @@ fn main() {
         // === AP Mode: Provisioning ===
         log::info!("No saved WiFi credentials, starting SoftAP provisioning...");
         Renderer::render_connecting(&mut fb, "Starting setup...");
-        flush_to_display(&mut hw_display, &fb);
+        flush_to_display(&mut hw_display, &mut fb);
 
         let wifi_inst = wifi::init_softap(modem, sysloop, nvs.clone()).unwrap();
         log::info!("WiFi SoftAP ready");
@@ -204,13 +260,66 @@ fn main() {
     log::info!("Mounting storage...");
     let _spiffs = unsafe { esp_idf_svc::fs::spiffs::Spiffs::new(config::STORAGE_PARTITION) };
 
+    // Settings live in NVS; older firmware kept them in a file, which is moved over once
+    let settings = device::load_settings(&nvs_for_creds).unwrap_or_else(|| {
+        let legacy = Storage::load_legacy_settings(config::LEGACY_SETTINGS_FILE);
+        if let (Some(settings), Some(nvs_part)) = (&legacy, &nvs_for_creds) {
+            match device::save_settings(nvs_part, settings) {
+                Ok(()) => Storage::remove_legacy_settings(config::LEGACY_SETTINGS_FILE),
+                Err(e) => log::warn!("Failed to migrate settings to NVS: {}", e),
+            }
+        }
+        legacy.unwrap_or_default()
+    });
+
     let storage = Arc::new(Mutex::new(Storage::new(
         config::TASKS_FILE,
         config::HISTORY_FILE,
+        settings,
     )));
 
-    // === Shared time source (synced from phone) ===
+    // === Shared time source (synced from phone, or NTP in Station mode) ===
     let time_source: SharedTime = Arc::new(Mutex::new(None));
+    let tz_offset = device::load_tz_offset(&nvs_for_creds).unwrap_or(0);
+    log::info!("UTC offset: {:+} min", tz_offset);
+    let _sntp = if wifi_mode.is_station() {
+        match wifi::start_sntp(time_source.clone(), nvs_for_creds.clone()) {
+            Ok(sntp) => Some(sntp),
+            Err(e) => {
+                log::warn!("NTP unavailable, waiting for phone time sync: {:?}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    // === mDNS (Station mode only; the captive portal's DNS already answers in AP mode) ===
+    let mdns = if wifi_mode.is_station() {
+        match wifi::start_mdns() {
+            Ok(mdns) => {
+                log::info!("mDNS ready: {}.local", config::MDNS_HOSTNAME);
+                Some(mdns)
+            }
+            Err(e) => {
+                log::warn!("mDNS unavailable, using IP address: {:?}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    // === Shared screen snapshot (for the web mirror) ===
+    let screen: SharedScreen = Arc::new(ScreenShare::default());
+
+    // === Device configuration changed from the web UI ===
+    let device_settings: SharedDevice = Arc::new(DeviceSettings::default());
+    let device_name = device::load_device_name(&nvs_for_creds);
+    log::info!("Device name: {}", device_name);
+    *device_settings.name.lock().unwrap() = device_name.clone();
+    device_settings.invert_encoder.store(invert_encoder, Ordering::Relaxed);
+    device_settings.rotation_deg.store(display_cfg.rotation_deg, Ordering::Relaxed);
 
     // === Start HTTP Server ===
     log::info!("Starting HTTP server...");
@@ -223,6 +332,8 @@ fn main() {
         wifi_mode.clone(),
         shared_wifi,
         nvs_for_creds.clone(),
+        screen.clone(),
+        device_settings.clone(),
     )
     .unwrap());
     log::info!("HTTP server ready on port {}", config::HTTP_PORT);
@@ -230,22 +341,26 @@ fn main() {
     // === Initialize View Navigator ===
     let mut nav = ViewNavigator::new();
     nav.ctx.wifi_mode = wifi_mode.clone();
+    nav.ctx.device_name = device_name;
 
     // Set the URL based on WiFi mode
     nav.ctx.ap_url = match &wifi_mode {
-        WiFiMode::Station { ip, .. } => wifi::web_url_from_ip(*ip),
+        WiFiMode::Station { ip, .. } => wifi::station_url(*ip, mdns.is_some()),
         WiFiMode::AccessPoint { ip } => format!("http://{}.{}.{}.{}", ip[0], ip[1], ip[2], ip[3]),
     };
 
     // Load initial data
-    {
-        let s = storage.lock().unwrap();
+    let mut settings = {
+        let s = lock_storage(&storage);
         let today = get_today(&time_source);
         let counts = s.get_task_counts(today);
         nav.set_task_counts(counts);
+        nav.apply_settings(&s.settings);
+        enc.apply_settings(&s.settings);
         let tasks = s.get_all_tasks(true);
         nav.set_tasks(tasks);
-    }
+        s.settings.clone()
+    };
 
     // AP mode (no WiFi provisioned): show WiFi QR code as the entry point
     // STA mode (connected to WiFi): always show Dashboard
@@ -260,62 +375,154 @@ fn main() {
     let nvs_for_reset = nvs_for_creds;
 
     let mut last_idle_check = Instant::now();
+    let mut last_render_date = get_today(&time_source);
+    let mut last_clock_text: Option<String> = None;
+    let mut last_signal_bars: Option<u8> = None;
     let mut needs_render = true;
     let mut wifi_reconnect_at: Option<Instant> = None;
+    let mut wifi_reconnect_failures: u32 = 0;
+    let mut last_flush = Instant::now();
+    let mut flush_errors: u32 = 0;
+    let mut name_scroll_at = Instant::now();
+    let sta_retry_interval = Duration::from_secs(config::AP_STA_RETRY_MINS * 60);
+    let mut sta_retry_at = if sta_failed && saved_creds.is_some() && config::AP_STA_RETRY_MINS > 0 {
+        Some(Instant::now() + sta_retry_interval)
+    } else {
+        None
+    };
+    #[cfg(feature = "debug")]
+    let mut debug_render_until: Option<Instant> = None;
 
     loop {
         // Poll encoder
         if let Some(event) = enc.poll() {
             let action = match event {
-                EncoderEvent::Clockwise => {
-                    nav.handle_clockwise();
+                // Hold indicator is drawn over the current frame; the view itself is unchanged
+                EncoderEvent::PressProgress(progress) => {
+                    if progress > 0.0 {
+                        Renderer::draw_hold_progress(&mut fb, progress);
+                        flush_to_display(&mut hw_display, &mut fb);
+                    } else {
+                        needs_render = true;
+                    }
+                    continue;
+                }
+                EncoderEvent::Clockwise(steps) => {
+                    nav.handle_clockwise(steps);
                     None
                 }
-                EncoderEvent::CounterClockwise => {
-                    nav.handle_counter_clockwise();
+                EncoderEvent::CounterClockwise(steps) => {
+                    nav.handle_counter_clockwise(steps);
                     None
                 }
                 EncoderEvent::ShortPress => nav.handle_press(),
                 EncoderEvent::LongPress => nav.handle_long_press(),
+                EncoderEvent::DoublePress => nav.handle_double_press(),
             };
 
             // Handle actions
             if let Some(action) = action {
-                handle_action(action, &mut nav, &storage, &time_source, &nvs_for_reset);
+                // Lets blocking actions (the completion animation) draw intermediate frames
+                let mut draw = |nav: &ViewNavigator| {
+                    render_current_view(&mut fb, nav, &storage, &time_source);
+                    flush_to_display(&mut hw_display, &mut fb);
+                };
+                handle_action(action, &mut nav, &storage, &time_source, &nvs_for_reset, &mut draw);
+            }
+
+            #[cfg(feature = "debug")]
+            {
+                debug_render_until = None;
+            }
+            nav.ctx.name_scroll = 0;
+            name_scroll_at = Instant::now() + Duration::from_millis(config::NAME_SCROLL_PAUSE_MS);
+            needs_render = true;
+        }
+
+        // Marquee a card name that doesn't fit. Only while the card is lit; this redraw is not
+        // user activity, so the idle timeout still applies.
+        if enc.is_backlight_on() && nav.ctx.state == ViewState::TaskList && Instant::now() >= name_scroll_at {
+            let period = nav.ctx.current_task().and_then(|t| Renderer::name_scroll_period(&fb, &t.name));
+            if let Some(period) = period {
+                let next = nav.ctx.name_scroll + config::NAME_SCROLL_STEP_PX;
+                let delay = if next >= period { config::NAME_SCROLL_PAUSE_MS } else { config::NAME_SCROLL_INTERVAL_MS };
+                nav.ctx.name_scroll = if next >= period { 0 } else { next };
+                name_scroll_at = Instant::now() + Duration::from_millis(delay);
+                needs_render = true;
             }
+        }
+
+        // Remote debug render: show the requested view until input or timeout
+        #[cfg(feature = "debug")]
+        {
+            let request = screen.render_requests.lock().unwrap().pop_front();
+            if let Some(request) = request {
+                let preview = debug_preview_nav(&nav, &request);
+                render_current_view(&mut fb, &preview, &storage, &time_source);
+                flush_to_display(&mut hw_display, &mut fb);
+                *screen.snapshot.lock().unwrap() = preview.screen_state(get_today(&time_source));
+                debug_render_until = Some(Instant::now() + Duration::from_secs(config::DEBUG_RENDER_HOLD_SECS));
+                needs_render = false;
+                log::info!("Debug render: {}", preview.ctx.state.as_str());
+            } else if debug_render_until.is_some_and(|until| Instant::now() >= until) {
+                debug_render_until = None;
+                needs_render = true;
+            }
+        }
 
+        if nav.clear_expired_toast() || nav.check_completing_timeout() {
             needs_render = true;
         }
 
         // Render if state changed
         if needs_render {
             render_current_view(&mut fb, &nav, &storage, &time_source);
-            flush_to_display(&mut hw_display, &fb);
+            if flush_to_display(&mut hw_display, &mut fb) {
+                flush_errors = 0;
+            } else {
+                flush_errors += 1;
+            }
+            last_flush = Instant::now();
+            *screen.snapshot.lock().unwrap() = nav.screen_state(get_today(&time_source));
             needs_render = false;
         }
 
-        // Deferred WiFi reconnect — wait 3 seconds after wake so the UI
-        // is fully responsive while the user navigates, then reconnect.
+        // Deferred WiFi reconnect — starts a few seconds after wake so the UI is fully
+        // responsive while the user navigates. Failed attempts are retried with the same
+        // backoff as at boot, scheduled here rather than slept through.
         #[allow(unused_assignments)]
-        if let Some(wake_time) = wifi_reconnect_at {
-            if wake_time.elapsed() > Duration::from_secs(3) {
-                wifi_reconnect_at = None;
-                log::info!("Reconnecting WiFi...");
-
-                let server_ip = if let Some(ref mut w) = sta_wifi {
-                    match wifi::restart_wifi(w) {
-                        Ok(new_ip) => {
-                            nav.ctx.ap_url = wifi::web_url_from_ip(new_ip);
-                            new_ip
-                        }
-                        Err(e) => {
-                            log::error!("WiFi reconnect failed: {}", e);
-                            wifi_mode.ip()
+        if wifi_reconnect_at.is_some_and(|at| Instant::now() >= at) {
+            wifi_reconnect_at = None;
+            log::info!("Reconnecting WiFi...");
+
+            let reconnected = match sta_wifi {
+                Some(ref mut w) => match wifi::restart_wifi(w) {
+                    Ok(new_ip) => {
+                        nav.ctx.ap_url = wifi::station_url(new_ip, mdns.is_some());
+                        Some(new_ip)
+                    }
+                    Err(e) => {
+                        wifi_reconnect_failures += 1;
+                        log::error!(
+                            "WiFi reconnect attempt {}/{} failed: {}",
+                            wifi_reconnect_failures,
+                            config::STA_CONNECT_ATTEMPTS,
+                            e
+                        );
+                        if wifi_reconnect_failures < config::STA_CONNECT_ATTEMPTS {
+                            let delay = wifi::backoff_delay(wifi_reconnect_failures);
+                            wifi_reconnect_at = Some(Instant::now() + delay);
                         }
+                        None
                     }
-                } else {
-                    wifi_mode.ip()
-                };
+                },
+                None => Some(wifi_mode.ip()),
+            };
+
+            // Serve again once connected, or after the last attempt (on the old address)
+            if reconnected.is_some() || wifi_reconnect_at.is_none() {
+                wifi_reconnect_failures = 0;
+                let server_ip = reconnected.unwrap_or_else(|| wifi_mode.ip());
 
                 match http_server::start_server(
                     storage.clone(),
@@ -324,6 +531,8 @@ fn main() {
                     wifi_mode.clone(),
                     None,
                     nvs_for_reset.clone(),
+                    screen.clone(),
+                    device_settings.clone(),
                 ) {
                     Ok(s) => {
                         server = Some(s);
@@ -339,6 +548,111 @@ fn main() {
         // Check idle timeout periodically
         let now = Instant::now();
         if now.duration_since(last_idle_check) > Duration::from_secs(1) {
+            // Date changed (midnight, or a time sync moved the clock either way): refresh due counts
+            let today = get_today(&time_source);
+            if today != last_render_date {
+                log::info!("Date changed {} -> {}, refreshing", last_render_date, today);
+                last_render_date = today;
+                reload_data(&mut nav, &storage, &time_source);
+                needs_render = true;
+            }
+
+            // Settings changed from the web UI: push them into the encoder and navigator
+            if device_settings.settings_changed.swap(false, Ordering::Relaxed) {
+                settings = lock_storage(&storage).settings.clone();
+                enc.apply_settings(&settings);
+                nav.apply_settings(&settings);
+                needs_render = true;
+            }
+
+            // Encoder direction flipped from the web UI
+            enc.set_invert_direction(device_settings.invert_encoder.load(Ordering::Relaxed));
+
+            // Rotation changed from the web UI: the panel keeps its boot rotation until the
+            // next restart, so turn the image around in the flush instead
+            let flipped = device_settings.rotation_deg.load(Ordering::Relaxed) != display_cfg.rotation_deg;
+            if flipped != fb.is_flipped() {
+                fb.set_flipped(flipped);
+                needs_render = true;
+            }
+
+            // Device renamed from the web UI
+            {
+                let device_name = device_settings.name.lock().unwrap();
+                if *device_name != nav.ctx.device_name {
+                    nav.ctx.device_name = device_name.clone();
+                    needs_render = true;
+                }
+            }
+
+            // Redraw when the displayed minute changes (or the clock is toggled)
+            let clock_text = if settings.show_clock || nav.ctx.state == ViewState::Clock {
+                get_clock_text(&time_source)
+            } else {
+                None
+            };
+            if clock_text != last_clock_text {
+                last_clock_text = clock_text;
+                needs_render = true;
+            }
+
+            // Dashboard signal indicator follows the station RSSI
+            if wifi_mode.is_station() && nav.ctx.state == ViewState::Dashboard {
+                let bars = wifi::rssi().map(wifi::signal_bars);
+                if bars != last_signal_bars {
+                    last_signal_bars = bars;
+                    needs_render = true;
+                }
+            }
+
+            // Self-heal: repaint periodically in case an SPI glitch garbled the panel,
+            // and slow the bus (persisted, then restart) if flushes keep failing
+            if config::DISPLAY_SELF_HEAL {
+                if enc.is_backlight_on() && last_flush.elapsed() >= Duration::from_secs(config::DISPLAY_REFRESH_SECS) {
+                    log::info!("Display recovery flush");
+                    fb.mark_all_dirty();
+                    if flush_to_display(&mut hw_display, &mut fb) {
+                        flush_errors = 0;
+                    } else {
+                        flush_errors += 1;
+                    }
+                    last_flush = Instant::now();
+                }
+
+                if flush_errors >= config::DISPLAY_ERROR_LIMIT && display_cfg.spi_freq_hz > config::SPI_FALLBACK_FREQ_HZ {
+                    log::warn!(
+                        "{} failed display flushes, lowering SPI to {} Hz and restarting",
+                        flush_errors,
+                        config::SPI_FALLBACK_FREQ_HZ
+                    );
+                    flush_errors = 0;
+                    if let Some(ref nvs_part) = nvs_for_reset {
+                        // Start from what's saved, so a rotation set since boot isn't lost
+                        let slower = display_config::DisplayConfig {
+                            spi_freq_hz: config::SPI_FALLBACK_FREQ_HZ,
+                            ..display_config::load_display_config(nvs_part)
+                        };
+                        if display_config::save_display_config(nvs_part, &slower).is_ok() {
+                            unsafe { esp_idf_svc::sys::esp_restart(); }
+                        }
+                    }
+                }
+            }
+
+            // AP mode with known-good creds: periodically go back and try the home network
+            if sta_retry_at.is_some_and(|at| now >= at) {
+                if wifi::ap_client_count() > 0 {
+                    log::info!("Station retry postponed: a phone is connected to the setup hotspot");
+                    sta_retry_at = Some(now + sta_retry_interval);
+                } else {
+                    log::info!("Retrying station mode, restarting...");
+                    if let Some(ref nvs_part) = nvs_for_reset {
+                        wifi::set_wifi_flag(nvs_part, config::NVS_KEY_STA_FAILED, false);
+                    }
+                    unsafe { esp_idf_svc::sys::esp_restart(); }
+                }
+            }
+
             // QR code screen gets longer timeout so user can scan and use web UI
             let timeout_secs = if nav.ctx.state == ViewState::QrCode {
                 config::QR_IDLE_TIMEOUT_SECS
@@ -346,7 +660,24 @@ fn main() {
                 config::IDLE_TIMEOUT_SECS // TODO: increase for normal use after testing
             };
 
+            let idle_halfway = !nav.ctx.showing_pinned_task()
+                && enc.seconds_since_activity() > timeout_secs as f64 / 2.0
+                && enc.is_backlight_on();
+
+            // Screensaver: swap to the big clock (any input goes back to the view it replaced)
+            if idle_halfway && settings.clock_screensaver && nav.show_clock() {
+                log::info!("Clock screensaver");
+                needs_render = true;
+            }
+
+            // Halfway to the timeout, dim first so the screen doesn't go dark without warning
+            if idle_halfway && nav.ctx.screen_timeout_enabled && settings.dim_brightness > 0 && !enc.is_dimmed() {
+                enc.dim(settings.dim_brightness);
+                log::info!("Screen dimmed (idle)");
+            }
+
             if nav.ctx.screen_timeout_enabled
+                && !nav.ctx.showing_pinned_task()
                 && enc.seconds_since_activity() > timeout_secs as f64
                 && enc.is_backlight_on()
             {
@@ -380,10 +711,11 @@ fn main() {
                     let _ = hw_display.wake(&mut FreeRtos);
                     enc.set_backlight(true);
                     enc.reset_activity();
-                    flush_to_display(&mut hw_display, &fb);
+                    fb.mark_all_dirty();
+                    flush_to_display(&mut hw_display, &mut fb);
 
-                    // Defer WiFi reconnect 3s so encoder is responsive immediately
-                    wifi_reconnect_at = Some(Instant::now());
+                    // Defer WiFi reconnect so encoder is responsive immediately
+                    wifi_reconnect_at = Some(Instant::now() + Duration::from_secs(config::STA_RECONNECT_DELAY_SECS));
                     needs_render = true;
                 }
             }
@@ -402,15 +734,14 @@ fn handle_action(
     storage: &SharedStorage,
     time_source: &SharedTime,
     nvs_partition: &Option<EspDefaultNvsPartition>,
+    draw: &mut dyn FnMut(&ViewNavigator),
 ) {
     let today = get_today(time_source);
 
     match action {
         "complete" => {
-            if let Some(task) = nav.ctx.current_task() {
-                let task_id = task.id;
-                let now_iso = get_now_iso(time_source);
-
+            // Snapshot the task up front: the web API may edit or delete it mid-animation
+            if let Some(task) = nav.ctx.current_task().cloned() {
                 // Run completion animation
                 let start = Instant::now();
                 let duration_ms = config::COMPLETING_DURATION_MS;
@@ -418,34 +749,103 @@ fn handle_action(
                 while start.elapsed().as_millis() < duration_ms as u128 {
                     let progress = start.elapsed().as_millis() as f32 / duration_ms as f32;
                     nav.ctx.completing_progress = progress.min(1.0);
+                    draw(nav);
                     FreeRtos::delay_ms(16); // ~60fps
                 }
 
-                // Actually complete in storage
-                {
-                    let mut s = storage.lock().unwrap();
-                    s.complete_task(task_id, &now_iso, today);
+                // Hold the "Done!" frame long enough to be seen
+                let dwell_ms = lock_storage(storage).settings.completion_dwell_ms;
+                if dwell_ms > 0 {
+                    nav.ctx.completing_progress = 1.0;
+                    draw(nav);
+                    FreeRtos::delay_ms(dwell_ms);
                 }
 
-                // Reload tasks and counts
+                complete_task_now(&task, nav, storage, time_source);
+            }
+        }
+        "pause" | "resume" => {
+            if let Some(task) = nav.ctx.current_task() {
+                let task_id = task.id;
+                let now_iso = get_now_iso(time_source);
+                {
+                    let mut s = lock_storage(storage);
+                    let update = TaskUpdate {
+                        paused: Some(action == "pause"),
+                        ..Default::default()
+                    };
+                    s.update_task(task_id, update, &now_iso);
+                }
+                reload_data(nav, storage, time_source);
+            }
+        }
+        "pin" | "unpin" => {
+            if let Some(task) = nav.ctx.current_task() {
+                let task_id = task.id;
+                let now_iso = get_now_iso(time_source);
+                let update = TaskUpdate {
+                    pinned: Some(action == "pin"),
+                    ..Default::default()
+                };
+                lock_storage(storage).update_task(task_id, update, &now_iso);
                 reload_data(nav, storage, time_source);
-                nav.complete_animation_done();
+                nav.show_toast(if action == "pin" { "Screen stays on" } else { "Screen can sleep" });
             }
         }
         "delete" => {
             if let Some(task) = nav.ctx.current_task() {
                 let task_id = task.id;
                 {
-                    let mut s = storage.lock().unwrap();
+                    let mut s = lock_storage(storage);
                     s.delete_task(task_id);
                 }
                 reload_data(nav, storage, time_source);
             }
         }
+        "reset_task" => {
+            if let Some(task) = nav.ctx.current_task() {
+                let task_id = task.id;
+                let now_iso = get_now_iso(time_source);
+                lock_storage(storage).reset_task(task_id, today, &now_iso);
+                reload_data(nav, storage, time_source);
+                nav.show_toast("Task reset");
+            }
+        }
+        "reschedule" => {
+            let target = nav.ctx.current_task().map(|t| t.id).zip(nav.ctx.reschedule_date.take());
+            if let Some((task_id, date)) = target {
+                let now_iso = get_now_iso(time_source);
+                let update = TaskUpdate {
+                    next_due_date: Some(date.format("%Y-%m-%d").to_string()),
+                    ..Default::default()
+                };
+                let updated = lock_storage(storage).update_task(task_id, update, &now_iso).is_some();
+                reload_data(nav, storage, time_source);
+                if updated {
+                    nav.show_toast(&format!("Due {}", date.format("%b %d")));
+                }
+            }
+        }
+        "undo_complete" => {
+            if let Some(task_id) = nav.ctx.undo_candidate() {
+                nav.ctx.last_completed = None;
+                let now_iso = get_now_iso(time_source);
+                let undone = lock_storage(storage).undo_last_completion(task_id, &now_iso);
+                reload_data(nav, storage, time_source);
+                nav.show_toast(if undone { "Completion undone" } else { "Nothing to undo" });
+            }
+        }
+        "snooze_overdue" => {
+            let now_iso = get_now_iso(time_source);
+            let snoozed = lock_storage(storage).snooze_all_overdue(today, &now_iso);
+            log::info!("Snoozed {} overdue task(s) to tomorrow", snoozed);
+            reload_data(nav, storage, time_source);
+            nav.show_toast(&format!("Snoozed {}", snoozed));
+        }
         "load_history" => {
             if let Some(task) = nav.ctx.current_task() {
                 let task_id = task.id;
-                let s = storage.lock().unwrap();
+                let s = lock_storage(storage);
                 let history = s.get_task_history(task_id);
                 nav.set_history(history);
             }
@@ -464,25 +864,43 @@ fn handle_action(
             log::info!("Resetting WiFi credentials and restarting...");
             if let Some(ref nvs_part) = nvs_partition {
                 let _ = wifi::clear_wifi_creds(nvs_part);
+                // Also the way back in for someone who forgot the API PIN
+                let _ = device::save_api_pin(nvs_part, None);
             }
             FreeRtos::delay_ms(500);
             unsafe { esp_idf_svc::sys::esp_restart(); }
         }
         "filter_tasks" => {
             let urgency = nav.ctx.filtered_urgency.clone().unwrap_or_default();
-            let s = storage.lock().unwrap();
+            let s = lock_storage(storage);
             let tasks = s.get_tasks_by_urgency(&urgency, today);
             nav.set_tasks(tasks);
         }
         "show_all_tasks" => {
-            let s = storage.lock().unwrap();
+            let s = lock_storage(storage);
             let tasks = s.get_all_tasks(true);
             nav.set_tasks(tasks);
         }
         "go_dashboard" => {
-            let s = storage.lock().unwrap();
+            let s = lock_storage(storage);
             let counts = s.get_task_counts(today);
             nav.set_task_counts(counts);
+            nav.apply_settings(&s.settings);
+        }
+        "toggle_list_style" => {
+            let compact = nav.ctx.compact_list;
+            {
+                let mut s = lock_storage(storage);
+                let mut settings = s.settings.clone();
+                settings.compact_task_list = compact;
+                if let Some(ref nvs_part) = nvs_partition {
+                    if let Err(e) = device::save_settings(nvs_part, &settings) {
+                        log::warn!("Failed to save list style: {}", e);
+                    }
+                }
+                s.set_settings(settings);
+            }
+            nav.show_toast(if compact { "List view" } else { "Card view" });
         }
         "show_settings" | "show_qr" => {
             // View transition handled by navigator
@@ -491,13 +909,45 @@ fn handle_action(
     }
 }
 
+/// Complete a task in storage and return the navigator to the task list, once the
+/// completion animation has played.
+fn complete_task_now(task: &Task, nav: &mut ViewNavigator, storage: &SharedStorage, time_source: &SharedTime) -> bool {
+    let now_iso = get_now_iso(time_source);
+    let today = get_today(time_source);
+
+    // No-op if the task is gone or its quota is used up
+    let result = lock_storage(storage).complete_task(task.id, &now_iso, today, None);
+    if result == CompleteResult::Completed {
+        nav.record_completion(task.id);
+    }
+
+    // Reload tasks and counts
+    reload_data(nav, storage, time_source);
+    nav.complete_animation_done();
+
+    match result {
+        CompleteResult::Completed => nav.show_undo_toast(),
+        CompleteResult::NotFound => {
+            log::warn!("Task {} ('{}') changed during completion, skipped", task.id, task.name);
+            nav.show_toast("Task changed");
+        }
+        CompleteResult::QuotaReached => {
+            log::info!("Task {} ('{}') quota reached, completion not recorded", task.id, task.name);
+            let message = task.quota.map(|q| q.reached_message()).unwrap_or_default();
+            nav.show_toast(&message);
+        }
+    }
+    result == CompleteResult::Completed
+}
+
 /// Reload tasks and counts after mutations
 fn reload_data(nav: &mut ViewNavigator, storage: &SharedStorage, time_source: &SharedTime) {
     let today = get_today(time_source);
-    let s = storage.lock().unwrap();
+    let s = lock_storage(storage);
 
     let counts = s.get_task_counts(today);
     nav.set_task_counts(counts);
+    nav.apply_settings(&s.settings);
 
     let tasks = match &nav.ctx.filtered_urgency {
         Some(urgency) => s.get_tasks_by_urgency(urgency, today),
@@ -510,14 +960,20 @@ fn reload_data(nav: &mut ViewNavigator, storage: &SharedStorage, time_source: &S
 fn render_current_view(
     fb: &mut FrameBuffer,
     nav: &ViewNavigator,
-    _storage: &SharedStorage,
+    storage: &SharedStorage,
     time_source: &SharedTime,
 ) {
     let today = get_today(time_source);
+    let (show_clock, show_done_today, week_days) = {
+        let s = lock_storage(storage);
+        (s.settings.show_clock, s.settings.show_done_today, s.settings.urgency.week_days)
+    };
+    let clock = if show_clock { get_clock_text(time_source) } else { None };
 
     match nav.get_render_command() {
-        RenderCommand::Dashboard { counts, selected } => {
-            Renderer::render_dashboard(fb, counts.overdue, counts.today, counts.week, counts.total, selected);
+        RenderCommand::Dashboard { counts, cells, selected } => {
+            let signal = if nav.ctx.wifi_mode.is_station() { wifi::rssi().map(wifi::signal_bars) } else { None };
+            Renderer::render_dashboard(fb, &counts, &cells, selected, week_days, clock.as_deref(), signal);
         }
         RenderCommand::TaskCard {
             task_index,
@@ -528,15 +984,45 @@ fn render_current_view(
                 let display_data = TaskDisplayData {
                     name: task.name.clone(),
                     days_until_due: task.days_until_due(today),
-                    urgency: String::from(task.urgency(today).as_str()),
+                    urgency: String::from(if task.paused { "paused" } else { task.urgency(today, &nav.ctx.urgency_thresholds).as_str() }),
                     next_due_date: task.formatted_due_date(),
+                    recurrence: Some(task.recurrence_label()),
+                    done_today: show_done_today
+                        && lock_storage(storage)
+                            .get_last_completion(task.id)
+                            .and_then(|r| r.completed_date())
+                            == Some(today),
+                    due_message: task
+                        .due_message
+                        .clone()
+                        .filter(|_| !task.paused && task.days_until_due(today) <= 0),
+                    category: task.category.clone(),
+                    color: task.color.clone(),
                 };
-                Renderer::render_task_card(fb, &display_data, task_index, total);
+                Renderer::render_task_card(fb, &display_data, task_index, total, nav.ctx.name_scroll);
             }
         }
         RenderCommand::BackCard { total } => {
             Renderer::render_back_card(fb, total);
         }
+        RenderCommand::TaskListCompact { selected, filtered } => {
+            let title = match filtered.as_deref() {
+                None | Some("total") => "All Tasks",
+                Some(filter) => theme::urgency_label(filter),
+            };
+            let rows: alloc::vec::Vec<TaskRowData> = nav
+                .ctx
+                .tasks
+                .iter()
+                .map(|task| TaskRowData {
+                    name: task.name.clone(),
+                    days_until_due: task.days_until_due(today),
+                    urgency: String::from(if task.paused { "paused" } else { task.urgency(today, &nav.ctx.urgency_thresholds).as_str() }),
+                })
+                .collect();
+            let selected = usize::try_from(selected).ok();
+            Renderer::render_task_list_compact(fb, title, &rows, selected);
+        }
         RenderCommand::EmptyFiltered { filter_name } => {
             Renderer::render_empty_filtered(fb, &filter_name);
         }
@@ -558,6 +1044,12 @@ fn render_current_view(
             let msg = format!("Delete '{}'?", task_name);
             Renderer::render_confirm_dialog(fb, &msg, confirm_selected);
         }
+        RenderCommand::ResetTaskConfirm { task_name, confirmed } => {
+            Renderer::render_reset_task_confirm(fb, &task_name, confirmed);
+        }
+        RenderCommand::Reschedule { task_name, date, original } => {
+            Renderer::render_reschedule(fb, &task_name, date, today, original != Some(date));
+        }
         RenderCommand::Completing {
             task_name,
             progress,
@@ -568,6 +1060,8 @@ fn render_current_view(
             task_name,
             selected,
         } => {
+            // Only call out the schedule on entries completed under a different one
+            let current_schedule = nav.ctx.current_task().map(|t| t.recurrence_label());
             let entries: alloc::vec::Vec<HistoryDisplayEntry> = nav
                 .ctx
                 .history
@@ -575,15 +1069,29 @@ fn render_current_view(
                 .map(|h| HistoryDisplayEntry {
                     completed_at: h.formatted_date(),
                     days_since_last: h.days_since_last,
+                    note: h.note.clone(),
+                    schedule: h
+                        .recurrence_label_at_completion
+                        .clone()
+                        .filter(|label| Some(label) != current_schedule.as_ref()),
                 })
                 .collect();
-            Renderer::render_history(fb, &task_name, &entries, selected);
+            let (streak, average) = nav
+                .ctx
+                .current_task()
+                .map(|t| {
+                    let s = lock_storage(storage);
+                    (s.current_streak(t.id, today), s.average_interval(t.id))
+                })
+                .unwrap_or((0, None));
+            Renderer::render_history(fb, &task_name, &entries, selected, streak, average);
         }
         RenderCommand::Settings {
             selected,
             screen_timeout_enabled,
+            device_name,
         } => {
-            Renderer::render_settings(fb, selected, screen_timeout_enabled);
+            Renderer::render_settings(fb, selected, screen_timeout_enabled, &device_name);
         }
         RenderCommand::QrCode { ref wifi_mode, ref url } => {
             Renderer::render_qr_code(fb, wifi_mode, url);
@@ -591,6 +1099,164 @@ fn render_current_view(
         RenderCommand::ResetWifiConfirm { confirmed } => {
             Renderer::render_reset_wifi_confirm(fb, confirmed);
         }
+        RenderCommand::SnoozeConfirm { count, confirmed } => {
+            Renderer::render_snooze_confirm(fb, count, confirmed);
+        }
+        RenderCommand::Clock => {
+            let time = get_clock_text(time_source);
+            Renderer::render_clock(fb, time.as_deref().unwrap_or("--:--"));
+        }
+    }
+
+    // Dashboard and the clock view show the time themselves; everything else gets the footer corner
+    if let Some(ref clock) = clock {
+        if !matches!(nav.ctx.state, ViewState::Dashboard | ViewState::Clock) {
+            Renderer::draw_footer_clock(fb, clock);
+        }
+    }
+
+    if let Some((ref message, _)) = nav.ctx.toast {
+        Renderer::render_toast(fb, message);
+    }
+}
+
+/// Build a throwaway navigator showing the view described by a debug render request.
+/// Starts from the live data so unspecified fields look realistic.
+#[cfg(feature = "debug")]
+fn debug_preview_nav(nav: &ViewNavigator, request: &serde_json::Value) -> ViewNavigator {
+    let mut preview = ViewNavigator::new();
+    let ctx = &mut preview.ctx;
+
+    ctx.state = request["view"].as_str().and_then(|v| v.parse().ok()).unwrap_or(ViewState::Dashboard);
+    ctx.tasks = nav.ctx.tasks.clone();
+    ctx.history = nav.ctx.history.clone();
+    ctx.task_counts = nav.ctx.task_counts.clone();
+    ctx.dashboard_cells = nav.ctx.dashboard_cells.clone();
+    ctx.urgency_thresholds = nav.ctx.urgency_thresholds;
+    ctx.screen_timeout_enabled = nav.ctx.screen_timeout_enabled;
+    ctx.ap_url = nav.ctx.ap_url.clone();
+    ctx.device_name = nav.ctx.device_name.clone();
+    ctx.wifi_mode = nav.ctx.wifi_mode.clone();
+
+    let count = |key: &str, current: u32| request[key].as_u64().map(|v| v as u32).unwrap_or(current);
+    ctx.task_counts.overdue = count("overdue", ctx.task_counts.overdue);
+    ctx.task_counts.today = count("today", ctx.task_counts.today);
+    ctx.task_counts.tomorrow = count("tomorrow", ctx.task_counts.tomorrow);
+    ctx.task_counts.week = count("week", ctx.task_counts.week);
+    ctx.task_counts.total = count("total", ctx.task_counts.total);
+
+    if let Some(selected) = request["selected"].as_u64() {
+        let selected = selected as usize;
+        match ctx.state {
+            ViewState::Dashboard => ctx.dashboard_index = selected.min(ctx.dashboard_items().len() - 1),
+            ViewState::TaskList | ViewState::TaskListCompact => ctx.task_index = selected as i32,
+            ViewState::TaskActions => ctx.action_index = selected.min(ctx.action_items().len() - 1),
+            ViewState::TaskHistory => ctx.history_index = selected,
+            ViewState::Settings => ctx.setting_index = selected,
+            _ => {}
+        }
+    }
+    if let Some(progress) = request["progress"].as_f64() {
+        ctx.completing_progress = (progress as f32).clamp(0.0, 1.0);
+    }
+    if let Some(confirmed) = request["confirmed"].as_bool() {
+        ctx.delete_choice = if confirmed { views::DeleteChoice::Delete } else { views::DeleteChoice::Cancel };
+        ctx.reset_wifi_confirmed = confirmed;
+        ctx.snooze_confirmed = confirmed;
+        ctx.reset_task_confirmed = confirmed;
+    }
+    if let Some(message) = request["toast"].as_str() {
+        preview.show_toast(message);
+    }
+
+    preview
+}
+
+/// Check for the self-test boot gesture: button held continuously for SELF_TEST_HOLD_MS
+fn boot_button_held<CLK, DT, SW>(enc: &Encoder<'_, CLK, DT, SW>) -> bool
+where
+    CLK: InputPin + OutputPin,
+    DT: InputPin + OutputPin,
+    SW: InputPin + OutputPin,
+{
+    let start = Instant::now();
+    while start.elapsed() < Duration::from_millis(config::SELF_TEST_HOLD_MS) {
+        if !enc.is_button_held() {
+            return false;
+        }
+        FreeRtos::delay_ms(10);
+    }
+    true
+}
+
+/// Hardware self-test loop (display, encoder, WiFi). Runs before normal startup.
+/// Long press advances a page; long press on the last page continues booting.
+fn run_self_test<CLK, DT, SW>(
+    display: &mut impl embedded_graphics_core::draw_target::DrawTarget<Color = embedded_graphics_core::pixelcolor::Rgb565>,
+    fb: &mut FrameBuffer,
+    enc: &mut Encoder<'_, CLK, DT, SW>,
+    modem: &mut esp_idf_hal::modem::Modem,
+    sysloop: EspSystemEventLoop,
+    nvs: Option<EspDefaultNvsPartition>,
+) where
+    CLK: InputPin + OutputPin,
+    DT: InputPin + OutputPin,
+    SW: InputPin + OutputPin,
+{
+    // Wait for the boot-gesture hold to end so it isn't read as a long press
+    Renderer::render_connecting(fb, "Release for self-test");
+    flush_to_display(display, fb);
+    while enc.is_button_held() {
+        FreeRtos::delay_ms(10);
+    }
+
+    let mut page = 0;
+    let (mut cw, mut ccw, mut presses) = (0u32, 0u32, 0u32);
+    let mut scan_result: Option<Result<usize, ()>> = None;
+    let mut needs_render = true;
+
+    loop {
+        if needs_render {
+            match page {
+                0 => Renderer::render_selftest_colors(fb),
+                1 => Renderer::render_selftest_glyphs(fb),
+                2 => Renderer::render_selftest_encoder(fb, cw, ccw, presses),
+                _ => Renderer::render_selftest_wifi(fb, scan_result),
+            }
+            flush_to_display(display, fb);
+            needs_render = false;
+
+            // Scan after the "Scanning..." frame is on screen (blocks for a few seconds)
+            if page == 3 && scan_result.is_none() {
+                scan_result = Some(
+                    wifi::self_test_scan(modem, sysloop.clone(), nvs.clone()).map_err(|e| {
+                        log::error!("Self-test scan failed: {}", e);
+                    }),
+                );
+                needs_render = true;
+            }
+        }
+
+        if let Some(event) = enc.poll().filter(|e| !matches!(e, EncoderEvent::PressProgress(_))) {
+            match (page, event) {
+                (2, EncoderEvent::Clockwise(_)) => cw += 1,
+                (2, EncoderEvent::CounterClockwise(_)) => ccw += 1,
+                (2, EncoderEvent::ShortPress) => presses += 1,
+                (2, EncoderEvent::DoublePress) => presses += 2,
+                (3, EncoderEvent::ShortPress) => scan_result = None,
+                (3, EncoderEvent::LongPress) => return,
+                (0 | 1, EncoderEvent::ShortPress) | (_, EncoderEvent::LongPress) => {
+                    page += 1;
+                    if page == 3 {
+                        scan_result = None;
+                    }
+                }
+                _ => {}
+            }
+            needs_render = true;
+        }
+
+        FreeRtos::delay_ms(config::POLL_INTERVAL_MS as u32);
     }
 }
 
@@ -674,21 +1340,43 @@ fn wait_for_button_press() {
     }
 }
 
-/// Flush framebuffer to the hardware display
+/// Flush framebuffer to the hardware display. Returns false if the SPI write failed.
 fn flush_to_display(
     display: &mut impl embedded_graphics_core::draw_target::DrawTarget<Color = embedded_graphics_core::pixelcolor::Rgb565>,
-    fb: &FrameBuffer,
-) {
-    use embedded_graphics_core::geometry::Point;
+    fb: &mut FrameBuffer,
+) -> bool {
+    use embedded_graphics_core::geometry::{Point, Size};
     use embedded_graphics_core::pixelcolor::Rgb565;
-    use embedded_graphics_core::Pixel;
+    use embedded_graphics_core::primitives::Rectangle;
 
-    // Write all pixels from framebuffer to display
-    let pixels = fb.as_raw().iter().enumerate().map(|(i, &raw)| {
-        let x = (i as u32) % config::DISPLAY_WIDTH;
-        let y = (i as u32) / config::DISPLAY_WIDTH;
-        Pixel(Point::new(x as i32, y as i32), Rgb565::from(embedded_graphics_core::pixelcolor::raw::RawU16::new(raw)))
-    });
+    // Push only the region that changed; the driver sets the panel's address window to it
+    let Some((x, y, w, h)) = fb.take_dirty_rect() else {
+        return true;
+    };
+    let flipped = fb.is_flipped();
+    let raw = fb.as_raw();
+    let row_span = |row: u32| {
+        let start = (row * config::DISPLAY_WIDTH + x) as usize;
+        &raw[start..start + w as usize]
+    };
+    let to_color = |&px: &u16| Rgb565::from(embedded_graphics_core::pixelcolor::raw::RawU16::new(px));
 
-    let _ = display.draw_iter(pixels);
+    let ok = if flipped {
+        // Mounted the other way up: the mirrored region, with rows and pixels in reverse
+        let origin = Point::new(
+            (config::DISPLAY_WIDTH - x - w) as i32,
+            (config::DISPLAY_HEIGHT - y - h) as i32,
+        );
+        let pixels = (y..y + h).rev().flat_map(|row| row_span(row).iter().rev().map(to_color));
+        display.fill_contiguous(&Rectangle::new(origin, Size::new(w, h)), pixels)
+    } else {
+        let pixels = (y..y + h).flat_map(|row| row_span(row).iter().map(to_color));
+        display.fill_contiguous(&Rectangle::new(Point::new(x as i32, y as i32), Size::new(w, h)), pixels)
+    }
+    .is_ok();
+    if !ok {
+        // Unknown what reached the panel, so repaint it all next time
+        fb.mark_all_dirty();
+    }
+    ok
 }
@@ -1,8 +1,11 @@
 /// Data models for Days Tracker
 extern crate alloc;
 
+use alloc::format;
 use alloc::string::String;
-use chrono::NaiveDate;
+use alloc::vec::Vec;
+use core::str::FromStr;
+use chrono::{Datelike, Months, NaiveDate};
 use serde::{Deserialize, Serialize};
 
 /// Task recurrence patterns
@@ -13,6 +16,43 @@ pub enum RecurrenceType {
     Weekly,
     Monthly,
     Yearly,
+    Weekdays, // recurrence_value is a mask of WEEKDAY_BITS (bit 0 = Monday .. bit 6 = Sunday)
+    Businessdays, // recurrence_value counts Mon-Fri only
+}
+
+/// All seven days in a `RecurrenceType::Weekdays` mask
+pub const WEEKDAY_BITS: u32 = 0x7F;
+
+/// Mask bit for a day in a `RecurrenceType::Weekdays` mask
+pub fn weekday_bit(day: chrono::Weekday) -> u32 {
+    1 << day.num_days_from_monday()
+}
+
+/// A weekdays recurrence needs a day mask from 1 to WEEKDAY_BITS (None = no value given)
+pub fn check_weekday_mask(recurrence_type: RecurrenceType, value: Option<u32>) -> Result<(), &'static str> {
+    if recurrence_type != RecurrenceType::Weekdays {
+        return Ok(());
+    }
+    match value {
+        Some(mask) if (1..=WEEKDAY_BITS).contains(&mask) => Ok(()),
+        _ => Err("weekdays recurrenceValue must be a day mask from 1 to 127 (1 = Mon, 2 = Tue, 4 = Wed .. 64 = Sun)"),
+    }
+}
+
+/// Check a partial recurrence change against the task it applies to: the mask rule holds for
+/// the type and value the task ends up with, and switching to or from weekdays needs a new
+/// value, since an interval and a day mask mean different things
+pub fn check_recurrence_update(
+    task: &Task,
+    recurrence_type: Option<RecurrenceType>,
+    value: Option<u32>,
+) -> Result<(), &'static str> {
+    let new_type = recurrence_type.unwrap_or(task.recurrence_type);
+    let was_weekdays = task.recurrence_type == RecurrenceType::Weekdays;
+    if (new_type == RecurrenceType::Weekdays) != was_weekdays && value.is_none() {
+        return Err("recurrenceValue is required when switching to or from weekdays");
+    }
+    check_weekday_mask(new_type, Some(value.unwrap_or(task.recurrence_value)))
 }
 
 impl RecurrenceType {
@@ -22,12 +62,205 @@ impl RecurrenceType {
             Self::Weekly => "weekly",
             Self::Monthly => "monthly",
             Self::Yearly => "yearly",
+            Self::Weekdays => "weekdays",
+            Self::Businessdays => "businessdays",
+        }
+    }
+
+    /// Singular unit name used in human-readable labels
+    pub fn unit(&self) -> &'static str {
+        match self {
+            Self::Daily => "day",
+            Self::Weekly => "week",
+            Self::Monthly => "month",
+            Self::Yearly => "year",
+            Self::Weekdays => "week",
+            Self::Businessdays => "business day",
+        }
+    }
+}
+
+impl FromStr for RecurrenceType {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        match s {
+            "daily" => Ok(Self::Daily),
+            "weekly" => Ok(Self::Weekly),
+            "monthly" => Ok(Self::Monthly),
+            "yearly" => Ok(Self::Yearly),
+            "weekdays" => Ok(Self::Weekdays),
+            "businessdays" => Ok(Self::Businessdays),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Local calendar date of a UTC timestamp, for a UTC offset in seconds (negative west of
+/// Greenwich). The date rolls over at local midnight, not at 00:00 UTC.
+pub fn local_date(utc_secs: i64, utc_offset_secs: i64) -> Option<NaiveDate> {
+    chrono::DateTime::from_timestamp(utc_secs.checked_add(utc_offset_secs)?, 0).map(|dt| dt.date_naive())
+}
+
+/// Calculate next due date based on recurrence (pure date math, no storage access).
+/// Months and years are calendar steps; a day past the end of the target month is clamped
+/// to its last day (Jan 31 + 1 month = Feb 28/29, Feb 29 + 1 year = Feb 28). Weekdays is the
+/// first selected day after `from_date` (Fri with Mon/Wed/Fri = the following Mon); an empty
+/// mask falls back to a week later. Businessdays counts only Mon-Fri (Fri + 1 = the following Mon).
+pub fn calculate_next_due(from_date: NaiveDate, recurrence_type: RecurrenceType, value: u32) -> NaiveDate {
+    calculate_next_due_anchored(from_date, recurrence_type, value, from_date.day())
+}
+
+/// `calculate_next_due` for a schedule pinned to `anchor_day` of the month: monthly and
+/// yearly steps land on that day, clamped to the target month's length, so a clamp doesn't
+/// carry over (anchor 31: Jan 31 -> Feb 28 -> Mar 31). Other recurrence types ignore it.
+pub fn calculate_next_due_anchored(
+    from_date: NaiveDate,
+    recurrence_type: RecurrenceType,
+    value: u32,
+    anchor_day: u32,
+) -> NaiveDate {
+    match recurrence_type {
+        RecurrenceType::Daily => from_date + chrono::Duration::days(value as i64),
+        RecurrenceType::Weekly => from_date + chrono::Duration::weeks(value as i64),
+        RecurrenceType::Monthly => add_months_anchored(from_date, value, anchor_day),
+        RecurrenceType::Yearly => add_months_anchored(from_date, value.saturating_mul(12), anchor_day),
+        RecurrenceType::Weekdays => (1..=7)
+            .map(|n| from_date + chrono::Duration::days(n))
+            .find(|d| value & weekday_bit(d.weekday()) != 0)
+            .unwrap_or(from_date + chrono::Duration::weeks(1)),
+        RecurrenceType::Businessdays => add_business_days(from_date, value),
+    }
+}
+
+/// `months` calendar months after `from_date`, on `anchor_day` or the target month's last day
+fn add_months_anchored(from_date: NaiveDate, months: u32, anchor_day: u32) -> NaiveDate {
+    let Some(first) = from_date.with_day(1).and_then(|d| d.checked_add_months(Months::new(months))) else {
+        return NaiveDate::MAX;
+    };
+    let month_len = first
+        .checked_add_months(Months::new(1))
+        .map_or(31, |next| (next - first).num_days() as u32);
+    first.with_day(anchor_day.clamp(1, month_len)).unwrap_or(first)
+}
+
+/// Step forward day by day, counting only Mon-Fri. Any 7 consecutive days hold exactly 5
+/// business days, so whole weeks are skipped first and at most 5 steps remain.
+fn add_business_days(from_date: NaiveDate, days: u32) -> NaiveDate {
+    if days == 0 {
+        return from_date;
+    }
+    let weeks = (days - 1) / 5;
+    let Some(mut date) = from_date.checked_add_signed(chrono::Duration::weeks(weeks as i64)) else {
+        return NaiveDate::MAX;
+    };
+    let mut remaining = days - weeks * 5;
+    while remaining > 0 {
+        date = match date.succ_opt() {
+            Some(next) => next,
+            None => return NaiveDate::MAX,
+        };
+        if !matches!(date.weekday(), chrono::Weekday::Sat | chrono::Weekday::Sun) {
+            remaining -= 1;
+        }
+    }
+    date
+}
+
+/// Label for a weekday mask: "every Mon/Wed/Fri" for up to three days (the card has room
+/// for little more), named patterns or a count otherwise
+fn weekdays_label(mask: u32) -> String {
+    const NAMES: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+    let mask = mask & WEEKDAY_BITS;
+    match mask {
+        0 => String::from("every week"), // calculate_next_due's fallback
+        WEEKDAY_BITS => String::from("every day"),
+        0x1F => String::from("every weekday"),
+        0x60 => String::from("every weekend"),
+        _ if mask.count_ones() <= 3 => {
+            let days: Vec<&str> = (0..7).filter(|i| mask & (1 << i) != 0).map(|i| NAMES[i]).collect();
+            format!("every {}", days.join("/"))
+        }
+        _ => format!("{} days a week", mask.count_ones()),
+    }
+}
+
+/// First day of the calendar week, used wherever week boundaries matter (weekly quotas)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WeekStart {
+    #[default]
+    Monday,
+    Sunday,
+}
+
+impl WeekStart {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Monday => "monday",
+            Self::Sunday => "sunday",
+        }
+    }
+
+    /// Days since the start of the week containing `date` (0 on the first day)
+    pub fn days_into_week(&self, date: NaiveDate) -> u32 {
+        match self {
+            Self::Monday => date.weekday().num_days_from_monday(),
+            Self::Sunday => date.weekday().num_days_from_sunday(),
         }
     }
 }
 
-/// Task urgency levels based on days until due
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+impl FromStr for WeekStart {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        match s {
+            "monday" => Ok(Self::Monday),
+            "sunday" => Ok(Self::Sunday),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Cap on completions per calendar period ("once a day", "3 times a week")
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompletionQuota {
+    pub max_per_period: u32,
+    pub period: RecurrenceType, // Calendar day/week/month/year containing today
+}
+
+impl CompletionQuota {
+    /// First day of the period containing `today`
+    pub fn period_start(&self, today: NaiveDate, week_start: WeekStart) -> NaiveDate {
+        match self.period {
+            RecurrenceType::Daily | RecurrenceType::Businessdays => today,
+            RecurrenceType::Weekly | RecurrenceType::Weekdays => {
+                today - chrono::Duration::days(week_start.days_into_week(today) as i64)
+            }
+            RecurrenceType::Monthly => today.with_day(1).unwrap_or(today),
+            RecurrenceType::Yearly => today.with_ordinal(1).unwrap_or(today),
+        }
+    }
+
+    /// Short message for when the quota is used up (e.g. "Already done today")
+    pub fn reached_message(&self) -> String {
+        let period = match self.period {
+            RecurrenceType::Daily | RecurrenceType::Businessdays => "today",
+            RecurrenceType::Weekly | RecurrenceType::Weekdays => "this week",
+            RecurrenceType::Monthly => "this month",
+            RecurrenceType::Yearly => "this year",
+        };
+        if self.max_per_period == 1 {
+            format!("Already done {}", period)
+        } else {
+            format!("Done {}x {}", self.max_per_period, period)
+        }
+    }
+}
+
+/// Task urgency levels based on days until due (ordered most urgent first)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Urgency {
     Overdue,
     Today,
@@ -36,16 +269,33 @@ pub enum Urgency {
     Upcoming,
 }
 
+/// User-adjustable day boundaries between urgency levels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UrgencyThresholds {
+    pub overdue_grace_days: i32,  // Days past due that still count as "today" before going overdue
+    pub week_days: i32,           // Due within this many days = "week" (after tomorrow)
+}
+
+impl Default for UrgencyThresholds {
+    fn default() -> Self {
+        Self {
+            overdue_grace_days: 0,
+            week_days: 7,
+        }
+    }
+}
+
 impl Urgency {
     /// Determine urgency from days until due
-    pub fn from_days(days: i32) -> Self {
-        if days < 0 {
+    pub fn from_days(days: i32, thresholds: &UrgencyThresholds) -> Self {
+        if days < -thresholds.overdue_grace_days {
             Self::Overdue
-        } else if days == 0 {
+        } else if days <= 0 {
             Self::Today
         } else if days == 1 {
             Self::Tomorrow
-        } else if days <= 7 {
+        } else if days <= thresholds.week_days {
             Self::Week
         } else {
             Self::Upcoming
@@ -63,6 +313,88 @@ impl Urgency {
     }
 }
 
+/// Metric cells that can be shown on the dashboard grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DashboardMetric {
+    Overdue,
+    Today,
+    Tomorrow,
+    Week,
+    Total,
+}
+
+impl DashboardMetric {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Overdue => "overdue",
+            Self::Today => "today",
+            Self::Tomorrow => "tomorrow",
+            Self::Week => "week",
+            Self::Total => "total",
+        }
+    }
+}
+
+impl FromStr for DashboardMetric {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        match s {
+            "overdue" => Ok(Self::Overdue),
+            "today" => Ok(Self::Today),
+            "tomorrow" => Ok(Self::Tomorrow),
+            "week" => Ok(Self::Week),
+            "total" => Ok(Self::Total),
+            _ => Err(()),
+        }
+    }
+}
+
+/// User preferences persisted alongside tasks
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub dashboard_cells: Vec<DashboardMetric>,  // Shown in this order; hidden cells collapse
+    pub wake_on_rotation: bool,                 // false = only a button press wakes the screen
+    pub show_clock: bool,                       // HH:MM in a corner of every view (once time is synced)
+    pub clock_screensaver: bool,                // Big clock view after half the idle timeout
+    pub show_done_today: bool,                  // Check badge on cards completed today
+    pub compact_task_list: bool,                // Task list as rows instead of one card per task
+    pub completion_dwell_ms: u32,               // How long "Done!" stays up after the animation (0 = skip)
+    pub long_press_ms: u32,                     // Hold time for a long press (longer for unsteady hands)
+    pub button_debounce_ms: u32,                // Presses closer together than this are ignored
+    pub brightness: u8,                         // Backlight percent while the screen is on
+    pub dim_brightness: u8,                     // Backlight percent halfway to the idle timeout (0 = don't dim)
+    pub week_start: WeekStart,                  // Week boundary for weekly quotas
+    pub urgency: UrgencyThresholds,             // Day boundaries for card colors, counts and filters
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            dashboard_cells: alloc::vec![
+                DashboardMetric::Overdue,
+                DashboardMetric::Today,
+                DashboardMetric::Week,
+                DashboardMetric::Total,
+            ],
+            wake_on_rotation: true,
+            show_clock: false,
+            clock_screensaver: false,
+            show_done_today: true,
+            compact_task_list: false,
+            completion_dwell_ms: 400,
+            long_press_ms: 500,
+            button_debounce_ms: 200,
+            brightness: 100,
+            dim_brightness: 20,
+            week_start: WeekStart::Monday,
+            urgency: UrgencyThresholds::default(),
+        }
+    }
+}
+
 /// A recurring task
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Task {
@@ -73,6 +405,24 @@ pub struct Task {
     pub next_due_date: String,     // ISO format "YYYY-MM-DD"
     pub created_at: String,        // ISO format datetime
     pub updated_at: String,        // ISO format datetime
+    #[serde(default)]
+    pub catch_up: bool,            // Completing late skips missed occurrences
+    #[serde(default)]
+    pub paused: bool,              // Paused tasks are kept but never count as due
+    #[serde(default)]
+    pub recur_from_completion: bool, // Next due date counts from the day it was done, not the old due date
+    #[serde(default)]
+    pub due_message: Option<String>, // Shown on the card instead of the day count when due
+    #[serde(default)]
+    pub quota: Option<CompletionQuota>, // Extra completions in the period are rejected
+    #[serde(default)]
+    pub category: Option<String>,  // Tag shown in the card corner, e.g. "kitchen"
+    #[serde(default)]
+    pub color: Option<String>,     // "#RRGGBB" for the tag dot (urgency color if unset)
+    #[serde(default)]
+    pub pinned: bool,              // Screen stays on while this task's card is shown
+    #[serde(default)]
+    pub anchor_day: Option<u32>,   // Day of month monthly/yearly steps aim for (None = the due date's day)
 }
 
 impl Task {
@@ -81,6 +431,12 @@ impl Task {
         NaiveDate::parse_from_str(&self.next_due_date, "%Y-%m-%d").ok()
     }
 
+    /// Day of month the monthly/yearly schedule is pinned to: the anchor kept from an earlier
+    /// clamped step (due Feb 28 for the 31st), else the due date's own day
+    pub fn schedule_day(&self) -> Option<u32> {
+        self.anchor_day.or_else(|| self.due_date().map(|d| d.day()))
+    }
+
     /// Calculate days until task is due
     pub fn days_until_due(&self, today: NaiveDate) -> i32 {
         match self.due_date() {
@@ -90,8 +446,25 @@ impl Task {
     }
 
     /// Get urgency level
-    pub fn urgency(&self, today: NaiveDate) -> Urgency {
-        Urgency::from_days(self.days_until_due(today))
+    pub fn urgency(&self, today: NaiveDate, thresholds: &UrgencyThresholds) -> Urgency {
+        Urgency::from_days(self.days_until_due(today), thresholds)
+    }
+
+    /// Human-readable recurrence (e.g., "every week", "every 2 weeks", "every Mon/Wed/Fri")
+    pub fn recurrence_label(&self) -> String {
+        let unit = self.recurrence_type.unit();
+        let label = if self.recurrence_type == RecurrenceType::Weekdays {
+            weekdays_label(self.recurrence_value)
+        } else if self.recurrence_value <= 1 {
+            format!("every {}", unit)
+        } else {
+            format!("every {} {}s", self.recurrence_value, unit)
+        };
+        if self.recur_from_completion {
+            format!("{} after done", label)
+        } else {
+            label
+        }
     }
 
     /// Format due date for display (e.g., "Jan 15, 2026")
@@ -110,6 +483,14 @@ pub struct CompletionRecord {
     pub task_id: u32,
     pub completed_at: String,     // ISO format datetime
     pub days_since_last: Option<i32>,
+    #[serde(default)]
+    pub note: Option<String>,     // Free-text context entered at completion
+    #[serde(default)]
+    pub recurrence_label_at_completion: Option<String>, // Schedule in effect when completed (None on old records)
+    #[serde(default)]
+    pub previous_due_date: Option<String>, // Task's due date before this completion, for undo (None on old records)
+    #[serde(default)]
+    pub previous_anchor_day: Option<u32>, // Task's anchor day before this completion, restored by undo
 }
 
 impl CompletionRecord {
@@ -127,6 +508,11 @@ impl CompletionRecord {
             self.completed_at.clone()
         }
     }
+
+    /// Local date of the completion
+    pub fn completed_date(&self) -> Option<NaiveDate> {
+        NaiveDate::parse_from_str(self.completed_at.split('T').next()?, "%Y-%m-%d").ok()
+    }
 }
 
 /// Task data for rendering (lightweight view struct)
@@ -135,10 +521,295 @@ pub struct TaskDisplayData {
     pub days_until_due: i32,
     pub urgency: String,
     pub next_due_date: String,
+    pub recurrence: Option<String>,  // e.g. "every 2 weeks"; omitted if it won't fit
+    pub done_today: bool,            // Completed on today's date (badge clears at rollover)
+    pub due_message: Option<String>, // Only set while the task is due or overdue
+    pub category: Option<String>,
+    pub color: Option<String>,       // "#RRGGBB"; the tag dot falls back to the urgency color
+}
+
+/// Compact task list row for rendering
+pub struct TaskRowData {
+    pub name: String,
+    pub days_until_due: i32,
+    pub urgency: String,
 }
 
 /// History entry for rendering
 pub struct HistoryDisplayEntry {
     pub completed_at: String,
     pub days_since_last: Option<i32>,
+    pub note: Option<String>,
+    pub schedule: Option<String>, // Only set when it differs from the task's current schedule
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn next(from: &str, recurrence_type: RecurrenceType, value: u32) -> NaiveDate {
+        calculate_next_due(date(from), recurrence_type, value)
+    }
+
+    #[test]
+    fn daily_and_weekly_add_whole_days() {
+        assert_eq!(next("2026-03-01", RecurrenceType::Daily, 3), date("2026-03-04"));
+        assert_eq!(next("2026-12-30", RecurrenceType::Daily, 5), date("2027-01-04"));
+        assert_eq!(next("2026-03-01", RecurrenceType::Weekly, 2), date("2026-03-15"));
+    }
+
+    #[test]
+    fn monthly_keeps_the_day_of_month() {
+        assert_eq!(next("2026-01-15", RecurrenceType::Monthly, 1), date("2026-02-15"));
+        assert_eq!(next("2026-11-15", RecurrenceType::Monthly, 3), date("2027-02-15"));
+    }
+
+    #[test]
+    fn monthly_clamps_to_the_end_of_a_shorter_month() {
+        assert_eq!(next("2026-01-31", RecurrenceType::Monthly, 1), date("2026-02-28"));
+        assert_eq!(next("2026-03-31", RecurrenceType::Monthly, 1), date("2026-04-30"));
+        assert_eq!(next("2028-01-31", RecurrenceType::Monthly, 1), date("2028-02-29"));
+    }
+
+    #[test]
+    fn yearly_adds_whole_years() {
+        assert_eq!(next("2026-06-10", RecurrenceType::Yearly, 1), date("2027-06-10"));
+        assert_eq!(next("2026-06-10", RecurrenceType::Yearly, 2), date("2028-06-10"));
+    }
+
+    #[test]
+    fn yearly_from_leap_day_falls_back_to_feb_28() {
+        assert_eq!(next("2028-02-29", RecurrenceType::Yearly, 1), date("2029-02-28"));
+        assert_eq!(next("2028-02-29", RecurrenceType::Yearly, 4), date("2032-02-29"));
+    }
+
+    #[test]
+    fn default_urgency_thresholds() {
+        let t = UrgencyThresholds::default();
+        assert_eq!(Urgency::from_days(-1, &t), Urgency::Overdue);
+        assert_eq!(Urgency::from_days(0, &t), Urgency::Today);
+        assert_eq!(Urgency::from_days(1, &t), Urgency::Tomorrow);
+        assert_eq!(Urgency::from_days(7, &t), Urgency::Week);
+        assert_eq!(Urgency::from_days(8, &t), Urgency::Upcoming);
+    }
+
+    #[test]
+    fn custom_urgency_thresholds_move_the_boundaries() {
+        let t = UrgencyThresholds {
+            overdue_grace_days: 2,
+            week_days: 3,
+        };
+        assert_eq!(Urgency::from_days(-2, &t), Urgency::Today);
+        assert_eq!(Urgency::from_days(-3, &t), Urgency::Overdue);
+        assert_eq!(Urgency::from_days(3, &t), Urgency::Week);
+        assert_eq!(Urgency::from_days(4, &t), Urgency::Upcoming);
+    }
+
+    #[test]
+    fn weekly_quota_period_follows_the_week_start() {
+        let quota = CompletionQuota {
+            max_per_period: 1,
+            period: RecurrenceType::Weekly,
+        };
+        // 2026-03-04 is a Wednesday, 2026-03-08 a Sunday
+        assert_eq!(
+            quota.period_start(date("2026-03-04"), WeekStart::Monday),
+            date("2026-03-02")
+        );
+        assert_eq!(
+            quota.period_start(date("2026-03-04"), WeekStart::Sunday),
+            date("2026-03-01")
+        );
+        assert_eq!(
+            quota.period_start(date("2026-03-08"), WeekStart::Monday),
+            date("2026-03-02")
+        );
+        assert_eq!(
+            quota.period_start(date("2026-03-08"), WeekStart::Sunday),
+            date("2026-03-08")
+        );
+    }
+
+    #[test]
+    fn week_start_parses_from_settings_strings() {
+        assert_eq!("sunday".parse(), Ok(WeekStart::Sunday));
+        assert_eq!("monday".parse(), Ok(WeekStart::Monday));
+        assert_eq!("friday".parse::<WeekStart>(), Err(()));
+    }
+
+    #[test]
+    fn anchored_monthly_returns_to_the_31st_after_february() {
+        let step =
+            |from: &str| calculate_next_due_anchored(date(from), RecurrenceType::Monthly, 1, 31);
+        assert_eq!(step("2026-01-31"), date("2026-02-28"));
+        assert_eq!(step("2026-02-28"), date("2026-03-31"));
+        assert_eq!(step("2026-03-31"), date("2026-04-30"));
+        assert_eq!(step("2026-04-30"), date("2026-05-31"));
+    }
+
+    #[test]
+    fn anchored_monthly_crosses_the_year_end() {
+        let step = |from: &str, day| {
+            calculate_next_due_anchored(date(from), RecurrenceType::Monthly, 1, day)
+        };
+        assert_eq!(step("2026-12-31", 31), date("2027-01-31"));
+        assert_eq!(step("2026-11-30", 31), date("2026-12-31"));
+        assert_eq!(step("2026-12-15", 15), date("2027-01-15"));
+    }
+
+    #[test]
+    fn anchored_yearly_keeps_the_leap_day() {
+        let step =
+            |from: &str| calculate_next_due_anchored(date(from), RecurrenceType::Yearly, 1, 29);
+        assert_eq!(step("2028-02-29"), date("2029-02-28"));
+        assert_eq!(step("2029-02-28"), date("2030-02-28"));
+        assert_eq!(step("2031-02-28"), date("2032-02-29"));
+    }
+
+    #[test]
+    fn local_date_follows_negative_and_positive_offsets() {
+        // 2026-03-02T03:00:00Z
+        let utc = date("2026-03-02")
+            .and_hms_opt(3, 0, 0)
+            .unwrap()
+            .and_utc()
+            .timestamp();
+        assert_eq!(local_date(utc, 0), Some(date("2026-03-02")));
+        assert_eq!(local_date(utc, -5 * 3600), Some(date("2026-03-01")));
+        assert_eq!(local_date(utc, -3 * 3600), Some(date("2026-03-02")));
+        assert_eq!(local_date(utc, 330 * 60), Some(date("2026-03-02")));
+    }
+
+    #[test]
+    fn local_date_rolls_over_at_local_midnight() {
+        // 2026-12-31T23:30:00Z is already New Year's Day east of UTC, still New Year's Eve west
+        let utc = date("2026-12-31")
+            .and_hms_opt(23, 30, 0)
+            .unwrap()
+            .and_utc()
+            .timestamp();
+        assert_eq!(local_date(utc, 60 * 60), Some(date("2027-01-01")));
+        assert_eq!(local_date(utc, 14 * 3600), Some(date("2027-01-01")));
+        assert_eq!(local_date(utc, -12 * 3600), Some(date("2026-12-31")));
+        assert_eq!(local_date(utc + 29 * 60, 0), Some(date("2026-12-31")));
+        assert_eq!(local_date(utc + 30 * 60, 0), Some(date("2027-01-01")));
+    }
+
+    fn weekdays_task(mask: u32) -> Task {
+        Task {
+            id: 1,
+            name: String::from("Gym"),
+            recurrence_type: RecurrenceType::Weekdays,
+            recurrence_value: mask,
+            next_due_date: String::from("2026-03-02"),
+            created_at: String::from("2026-01-01T00:00:00"),
+            updated_at: String::from("2026-01-01T00:00:00"),
+            catch_up: false,
+            paused: false,
+            recur_from_completion: false,
+            due_message: None,
+            quota: None,
+            category: None,
+            color: None,
+            pinned: false,
+            anchor_day: None,
+        }
+    }
+
+    #[test]
+    fn weekdays_steps_to_the_next_selected_day() {
+        let mon_wed_fri = 0b001_0101;
+        // 2026-03-06 is a Friday
+        assert_eq!(
+            next("2026-03-06", RecurrenceType::Weekdays, mon_wed_fri),
+            date("2026-03-09")
+        );
+        assert_eq!(
+            next("2026-03-09", RecurrenceType::Weekdays, mon_wed_fri),
+            date("2026-03-11")
+        );
+    }
+
+    #[test]
+    fn single_day_mask_repeats_weekly() {
+        let wednesday = weekday_bit(chrono::Weekday::Wed);
+        assert_eq!(
+            next("2026-03-04", RecurrenceType::Weekdays, wednesday),
+            date("2026-03-11")
+        );
+        assert_eq!(
+            next("2026-03-06", RecurrenceType::Weekdays, wednesday),
+            date("2026-03-11")
+        );
+        assert_eq!(
+            check_weekday_mask(RecurrenceType::Weekdays, Some(wednesday)),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn value_only_update_is_checked_against_the_stored_type() {
+        let task = weekdays_task(0b001_0101);
+        assert!(check_recurrence_update(&task, None, Some(0)).is_err());
+        assert!(check_recurrence_update(&task, None, Some(500)).is_err());
+        assert_eq!(
+            check_recurrence_update(&task, None, Some(0b100_0000)),
+            Ok(())
+        );
+        assert_eq!(check_recurrence_update(&task, None, None), Ok(()));
+    }
+
+    #[test]
+    fn switching_to_or_from_weekdays_needs_a_new_value() {
+        let task = weekdays_task(0b001_0101);
+        assert!(check_recurrence_update(&task, Some(RecurrenceType::Weekly), None).is_err());
+        assert_eq!(
+            check_recurrence_update(&task, Some(RecurrenceType::Weekly), Some(2)),
+            Ok(())
+        );
+
+        let weekly = Task {
+            recurrence_type: RecurrenceType::Weekly,
+            recurrence_value: 2,
+            ..weekdays_task(0)
+        };
+        assert!(check_recurrence_update(&weekly, Some(RecurrenceType::Weekdays), None).is_err());
+        assert!(
+            check_recurrence_update(&weekly, Some(RecurrenceType::Weekdays), Some(200)).is_err()
+        );
+        assert_eq!(
+            check_recurrence_update(&weekly, Some(RecurrenceType::Weekdays), Some(1)),
+            Ok(())
+        );
+        assert_eq!(check_recurrence_update(&weekly, None, Some(500)), Ok(()));
+    }
+
+    #[test]
+    fn business_days_skip_the_weekend() {
+        // 2026-03-06 is a Friday
+        assert_eq!(
+            next("2026-03-06", RecurrenceType::Businessdays, 1),
+            date("2026-03-09")
+        );
+        assert_eq!(
+            next("2026-03-07", RecurrenceType::Businessdays, 1),
+            date("2026-03-09")
+        );
+        assert_eq!(
+            next("2026-03-04", RecurrenceType::Businessdays, 1),
+            date("2026-03-05")
+        );
+        assert_eq!(
+            next("2026-03-06", RecurrenceType::Businessdays, 5),
+            date("2026-03-13")
+        );
+        assert_eq!(
+            next("2026-03-06", RecurrenceType::Businessdays, 6),
+            date("2026-03-16")
+        );
+    }
 }
@@ -0,0 +1,148 @@
+/// Quadrature decoding for the rotary encoder
+///
+/// Turns (CLK, DT) readings into one step per physical detent. Kept free of GPIO so the
+/// decoding can be tested off the device.
+
+/// Quadrature state (CLK << 1 | DT) where the KY-040 rests between cycles (both pulled up)
+const QUAD_REST: u8 = 0b11;
+
+/// Direction of a quadrature transition, indexed by (previous state << 2 | new state).
+/// Clockwise runs 11 -> 01 -> 00 -> 10 -> 11; no change or a skipped state counts 0.
+const QUAD_TRANSITIONS: [i8; 16] = [
+    0, -1, 1, 0, //  00 -> 00, 01, 10, 11
+    1, 0, 0, -1, //  01 -> 00, 01, 10, 11
+    -1, 0, 0, 1, //  10 -> 00, 01, 10, 11
+    0, 1, -1, 0, //  11 -> 00, 01, 10, 11
+];
+
+/// (CLK, DT) state machine that reports a step each time the encoder settles in a detent
+pub struct QuadratureDecoder {
+    transitions_per_detent: u8, // 1, 2 or 4
+    state: u8,                  // Last (CLK, DT) reading
+    steps: i8,                  // Net transitions since the last detent
+}
+
+impl QuadratureDecoder {
+    /// Decoder for an encoder with `transitions_per_detent` (1, 2 or 4) transitions per click
+    pub fn new(transitions_per_detent: u8) -> Self {
+        Self {
+            transitions_per_detent,
+            state: QUAD_REST, // Pull-ups, so both lines idle high
+            steps: 0,
+        }
+    }
+
+    /// Feed one reading. Returns +1 (clockwise) or -1 once the encoder reaches the next
+    /// detent; bounces and half turns cancel out.
+    pub fn update(&mut self, clk_high: bool, dt_high: bool) -> Option<i32> {
+        let state = ((clk_high as u8) << 1) | dt_high as u8;
+        if state == self.state {
+            return None;
+        }
+
+        let transition = QUAD_TRANSITIONS[((self.state << 2) | state) as usize];
+        self.state = state;
+        self.steps = self.steps.saturating_add(transition);
+
+        if !self.at_detent(state) {
+            return None;
+        }
+        // A skipped state counts 0, so a cycle with one missed poll still nets half its transitions
+        let needed = (self.transitions_per_detent / 2).max(1) as i8;
+        let steps = core::mem::take(&mut self.steps);
+        if steps >= needed {
+            Some(1)
+        } else if steps <= -needed {
+            Some(-1)
+        } else {
+            None
+        }
+    }
+
+    /// Whether the encoder can rest in `state`: every state at 1 transition per detent,
+    /// both lines equal at 2, and only the high rest state at 4
+    fn at_detent(&self, state: u8) -> bool {
+        match self.transitions_per_detent {
+            1 => true,
+            2 => state == 0b00 || state == QUAD_REST,
+            _ => state == QUAD_REST,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// One clockwise quadrature cycle from rest, as (CLK, DT) readings
+    const CLOCKWISE: [(bool, bool); 4] =
+        [(false, true), (false, false), (true, false), (true, true)];
+
+    fn feed(
+        decoder: &mut QuadratureDecoder,
+        readings: impl IntoIterator<Item = (bool, bool)>,
+    ) -> Vec<i32> {
+        readings
+            .into_iter()
+            .filter_map(|(clk, dt)| decoder.update(clk, dt))
+            .collect()
+    }
+
+    fn counter_clockwise() -> impl Iterator<Item = (bool, bool)> {
+        [(true, false), (false, false), (false, true), (true, true)].into_iter()
+    }
+
+    #[test]
+    fn four_transitions_make_one_detent() {
+        let mut decoder = QuadratureDecoder::new(4);
+        assert_eq!(feed(&mut decoder, CLOCKWISE), [1]);
+        assert_eq!(feed(&mut decoder, counter_clockwise()), [-1]);
+    }
+
+    #[test]
+    fn two_transitions_make_one_detent() {
+        let mut decoder = QuadratureDecoder::new(2);
+        assert_eq!(feed(&mut decoder, CLOCKWISE), [1, 1]);
+        assert_eq!(feed(&mut decoder, counter_clockwise()), [-1, -1]);
+    }
+
+    #[test]
+    fn every_transition_is_a_detent() {
+        let mut decoder = QuadratureDecoder::new(1);
+        assert_eq!(feed(&mut decoder, CLOCKWISE), [1, 1, 1, 1]);
+        assert_eq!(feed(&mut decoder, counter_clockwise()), [-1, -1, -1, -1]);
+    }
+
+    #[test]
+    fn contact_bounce_cancels_out() {
+        for transitions in [1, 2, 4] {
+            let mut decoder = QuadratureDecoder::new(transitions);
+            let bounce = [(false, true), (true, true), (false, true), (true, true)];
+            let steps = feed(&mut decoder, bounce);
+            assert_eq!(
+                steps.iter().sum::<i32>(),
+                0,
+                "{} transitions per detent",
+                transitions
+            );
+        }
+    }
+
+    #[test]
+    fn a_full_cycle_survives_one_missed_poll() {
+        let mut decoder = QuadratureDecoder::new(4);
+        // 01 was never seen: 11 -> 00 is a skip and counts for nothing
+        assert_eq!(
+            feed(&mut decoder, [(false, false), (true, false), (true, true)]),
+            [1]
+        );
+        // A half turn that springs back is still no step
+        assert_eq!(
+            feed(
+                &mut decoder,
+                [(false, true), (false, false), (false, true), (true, true)]
+            ),
+            [] as [i32; 0]
+        );
+    }
+}
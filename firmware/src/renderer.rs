@@ -8,14 +8,22 @@ use alloc::format;
 use alloc::string::String;
 use alloc::vec::Vec;
 
+use chrono::NaiveDate;
 use embedded_graphics::pixelcolor::Rgb565;
 
 use crate::display::FrameBuffer;
-use crate::fonts::{self, BIG_NUM_HEIGHT, BIG_NUM_WIDTH, FONT_WIDTH};
-use crate::models::{HistoryDisplayEntry, TaskDisplayData};
+use crate::fonts::{self, BIG_NUM_HEIGHT, BIG_NUM_WIDTH, FONT_HEIGHT, FONT_WIDTH, LOGO, LOGO_HEIGHT, LOGO_WIDTH};
+use crate::models::{DashboardMetric, HistoryDisplayEntry, TaskDisplayData, TaskRowData};
 use crate::theme;
+use crate::views::TaskCounts;
 use crate::wifi::WiFiMode;
 
+/// Blank space between the end of a scrolling task name and its repeat
+const NAME_SCROLL_GAP: u32 = 24;
+
+/// Width of the dashboard's three-bar signal glyph
+const SIGNAL_GLYPH_WIDTH: u32 = 8;
+
 /// Renderer handles all UI drawing operations
 pub struct Renderer;
 
@@ -38,20 +46,43 @@ impl Renderer {
 
     /// Draw a single character
     fn draw_char(fb: &mut FrameBuffer, x: u32, y: u32, ch: char, color: Rgb565, scale: u32) {
-        let bitmap = fonts::get_char_bitmap(ch);
+        // Glyphs are pre-split into horizontal runs, so each run is one clipped slice fill
+        let runs = fonts::get_char_runs(ch);
+
+        for row in 0..FONT_HEIGHT as usize {
+            for &(start, len) in runs.row(row) {
+                fb.fill_rect(
+                    x + start as u32 * scale,
+                    y + row as u32 * scale,
+                    len as u32 * scale,
+                    scale,
+                    color,
+                );
+            }
+        }
+    }
 
-        for (row, &bits) in bitmap.iter().enumerate() {
-            for col in 0..FONT_WIDTH {
-                if (bits >> (FONT_WIDTH - 1 - col)) & 1 == 1 {
-                    for sy in 0..scale {
-                        for sx in 0..scale {
-                            fb.set_pixel(
-                                x + col * scale + sx,
-                                y + row as u32 * scale + sy,
-                                color,
-                            );
-                        }
+    /// Draw a monochrome bitmap (1bpp, row-major, MSB-first; each row padded to a whole byte).
+    /// Set bits are drawn in `color`, clear bits are left untouched.
+    pub fn draw_bitmap(fb: &mut FrameBuffer, x: u32, y: u32, width: u32, height: u32, bits: &[u8], color: Rgb565) {
+        let stride = width.div_ceil(8) as usize;
+
+        for (row, row_bits) in bits.chunks(stride).take(height as usize).enumerate() {
+            let bit = |col: u32| (row_bits[(col / 8) as usize] >> (7 - col % 8)) & 1 == 1;
+            // A short final chunk holds fewer columns than `width`
+            let limit = width.min(row_bits.len() as u32 * 8);
+
+            // Fill horizontal runs of set bits, like the font glyphs
+            let mut col = 0;
+            while col < limit {
+                if bit(col) {
+                    let start = col;
+                    while col < limit && bit(col) {
+                        col += 1;
                     }
+                    fb.fill_rect(x + start, y + row as u32, col - start, 1, color);
+                } else {
+                    col += 1;
                 }
             }
         }
@@ -108,6 +139,44 @@ impl Renderer {
         Self::draw_text(fb, x, y, text, color, scale);
     }
 
+    /// Draw text with no gap between glyphs (fits ~20% more per line)
+    fn draw_text_condensed(fb: &mut FrameBuffer, x: u32, y: u32, text: &str, color: Rgb565) {
+        let mut cursor_x = x;
+        for ch in text.chars() {
+            Self::draw_char(fb, cursor_x, y, ch, color, 1);
+            cursor_x += FONT_WIDTH;
+        }
+    }
+
+    /// Draw text starting at a possibly off-screen x, clipped to columns left..right
+    fn draw_text_clipped(fb: &mut FrameBuffer, x: i32, y: u32, text: &str, color: Rgb565, left: u32, right: u32) {
+        let mut cursor_x = x;
+        for ch in text.chars() {
+            if cursor_x >= right as i32 {
+                break;
+            }
+            if cursor_x + FONT_WIDTH as i32 > left as i32 {
+                let runs = fonts::get_char_runs(ch);
+                for row in 0..FONT_HEIGHT as usize {
+                    for &(start, len) in runs.row(row) {
+                        let x0 = (cursor_x + start as i32).max(left as i32);
+                        let x1 = (cursor_x + start as i32 + len as i32).min(right as i32);
+                        if x1 > x0 {
+                            fb.fill_rect(x0 as u32, y + row as u32, (x1 - x0) as u32, 1, color);
+                        }
+                    }
+                }
+            }
+            cursor_x += (FONT_WIDTH + 1) as i32;
+        }
+    }
+
+    /// Draw centered condensed text
+    fn draw_text_condensed_centered(fb: &mut FrameBuffer, y: u32, text: &str, color: Rgb565) {
+        let w = text.len() as u32 * FONT_WIDTH;
+        Self::draw_text_condensed(fb, (fb.width().saturating_sub(w)) / 2, y, text, color);
+    }
+
     /// Draw a pill-shaped badge (rounded rectangle with text)
     fn draw_pill(fb: &mut FrameBuffer, y: u32, text: &str, text_color: Rgb565, bg_color: Rgb565, scale: u32) {
         let text_w = Self::text_width(text, scale);
@@ -183,51 +252,153 @@ impl Renderer {
         Self::draw_text(fb, text_x, text_y, text, text_color, 1);
     }
 
-    /// Render a task card (main view)
-    pub fn render_task_card(fb: &mut FrameBuffer, task: &TaskDisplayData, index: usize, total: usize) {
+    /// Small 7x6 check mark
+    fn draw_check(fb: &mut FrameBuffer, x: u32, y: u32, color: Rgb565) {
+        for i in 0..3 {
+            fb.fill_rect(x + i, y + 3 + i, 1, 2, color);
+        }
+        for i in 0..4 {
+            fb.fill_rect(x + 3 + i, y + 4 - i, 1, 2, color);
+        }
+    }
+
+    /// Marquee cycle length in pixels for a card name too long to show in full even when
+    /// condensed to three lines; None if the name fits
+    pub fn name_scroll_period(fb: &FrameBuffer, name: &str) -> Option<u32> {
+        let condensed_chars = ((fb.width() - 8) / FONT_WIDTH) as usize;
+        (wrap_text(name, condensed_chars).len() > 3).then(|| Self::text_width(name, 1) + NAME_SCROLL_GAP)
+    }
+
+    /// Render a task card (main view). `name_scroll` is the marquee offset in pixels,
+    /// used only when the name doesn't fit (see `name_scroll_period`).
+    pub fn render_task_card(fb: &mut FrameBuffer, task: &TaskDisplayData, index: usize, total: usize, name_scroll: u32) {
         Self::clear(fb);
 
         let h = fb.height();
         let w = fb.width();
         let max_chars_per_line = ((w - 8) / (FONT_WIDTH + 1)) as usize;
 
-        // Urgency label at top with pill background
-        let urgency_color = theme::urgency_color(&task.urgency);
+        // Urgency label at top with pill background (overdue deepens in color as it ages)
+        let urgency_color = if task.urgency == "overdue" {
+            theme::overdue_color(task.days_until_due.unsigned_abs())
+        } else {
+            theme::urgency_color(&task.urgency)
+        };
         let urgency_label = theme::urgency_label(&task.urgency);
         Self::draw_pill(fb, 3, urgency_label, theme::TEXT_PRIMARY, urgency_color, 1);
 
-        // Task name - wrap to multiple lines if needed
-        let name_lines = wrap_text(&task.name, max_chars_per_line.min(25));
-        let name_start_y = 16;
-        for (i, line) in name_lines.iter().take(2).enumerate() {
-            Self::draw_text_centered(fb, name_start_y + (i as u32 * 9), line, theme::TEXT_PRIMARY, 1);
+        // Category tag in the top-left corner: a dot in the task's color, then as much label as fits
+        if task.category.is_some() || task.color.is_some() {
+            let dot_color = task.color.as_deref().and_then(theme::hex_color).unwrap_or(urgency_color);
+            fb.fill_rect(4, 7, 5, 3, dot_color);
+            fb.fill_rect(5, 6, 3, 5, dot_color);
+
+            if let Some(ref category) = task.category {
+                let pill_x = w.saturating_sub(Self::text_width(urgency_label, 1) + 10) / 2;
+                let max_chars = (pill_x.saturating_sub(14) / FONT_WIDTH) as usize;
+                let label: String = category.chars().take(max_chars).collect();
+                Self::draw_text_condensed(fb, 11, 5, &label, theme::TEXT_MUTED);
+            }
         }
 
-        // Large day count
-        let days_text = format!("{}", task.days_until_due.unsigned_abs());
+        // Done-today badge in the free top-right corner beside the pill
+        if task.done_today {
+            let text_w = Self::text_width("done", 1);
+            let x = w - text_w - 12;
+            Self::draw_check(fb, x, 4, theme::SUCCESS);
+            Self::draw_text(fb, x + 9, 5, "done", theme::SUCCESS, 1);
+        }
+
+        // Task name - two normal lines, else three condensed lines, else ellipsis
+        let name_start_y = 16;
+        let mut name_lines = wrap_text(&task.name, max_chars_per_line.min(25));
+        let condensed = name_lines.len() > 2;
+        if let Some(period) = Self::name_scroll_period(fb, &task.name) {
+            // Too long even condensed: one line scrolling sideways, wrapping around after a gap
+            let x = 4 - (name_scroll % period) as i32;
+            Self::draw_text_clipped(fb, x, name_start_y, &task.name, theme::TEXT_PRIMARY, 4, w - 4);
+            Self::draw_text_clipped(fb, x + period as i32, name_start_y, &task.name, theme::TEXT_PRIMARY, 4, w - 4);
+            name_lines.truncate(1);
+        } else if condensed {
+            let condensed_chars = ((w - 8) / FONT_WIDTH) as usize;
+            name_lines = wrap_text(&task.name, condensed_chars);
+            for (i, line) in name_lines.iter().enumerate() {
+                Self::draw_text_condensed_centered(fb, name_start_y + (i as u32 * 8), line, theme::TEXT_PRIMARY);
+            }
+        } else {
+            for (i, line) in name_lines.iter().enumerate() {
+                Self::draw_text_centered(fb, name_start_y + (i as u32 * 9), line, theme::TEXT_PRIMARY, 1);
+            }
+        }
 
         // Big number in center
-        let number_y = if name_lines.len() > 1 { 36 } else { 32 };
-
-        // Use scale 2 for big friendly numbers
-        // For 3+ digit numbers, use scale 1 to fit
-        let scale = if days_text.len() >= 3 { 1 } else { 2 };
-        Self::draw_big_number_centered(fb, number_y, &days_text, urgency_color, scale);
-
-        // "DAYS LEFT" or "DAYS OVERDUE" label
-        let days_label = if task.days_until_due < 0 {
-            "DAYS OVERDUE"
-        } else if task.days_until_due == 1 {
-            "DAY LEFT"
+        let number_y = match name_lines.len() {
+            0 | 1 => 32,
+            2 => 36,
+            _ => 42,
+        };
+
+        let date_y = if let Some(ref message) = task.due_message {
+            // Custom callout replaces the number: one big line if short, else two small lines
+            let number_height = BIG_NUM_HEIGHT * 2;
+            if Self::text_width(message, 2) <= w - 8 {
+                let msg_y = number_y + (number_height - FONT_HEIGHT * 2) / 2;
+                let msg_x = w.saturating_sub(Self::text_width(message, 2)) / 2;
+                Self::draw_text(fb, msg_x, msg_y, message, urgency_color, 2);
+                Self::draw_text(fb, msg_x + 1, msg_y, message, urgency_color, 2); // Double-strike for weight
+            } else {
+                let lines = wrap_text(message, max_chars_per_line);
+                let msg_y = number_y + (number_height - 16) / 2;
+                for (i, line) in lines.iter().take(2).enumerate() {
+                    Self::draw_text_centered(fb, msg_y + (i as u32 * 9), line, urgency_color, 1);
+                }
+            }
+
+            // The count moves to the label row so it isn't lost
+            let days_overdue = task.days_until_due.unsigned_abs();
+            let days_label = match days_overdue {
+                0 => String::from("DUE TODAY"),
+                1..=6 => format!("{} {}", days_overdue, overdue_label(days_overdue)),
+                _ => overdue_label(days_overdue),
+            };
+            let label_y = number_y + number_height + 2;
+            Self::draw_text_centered(fb, label_y, &days_label, theme::TEXT_MUTED, 1);
+            label_y + 10
         } else {
-            "DAYS LEFT"
+            match due_headline(task.days_until_due) {
+                (word, None) => {
+                    // Centered in the space the big number would take; due date drops into the label row
+                    let number_height = BIG_NUM_HEIGHT * 2;
+                    let word_y = number_y + (number_height - FONT_HEIGHT * 2) / 2;
+                    let word_x = w.saturating_sub(Self::text_width(&word, 2)) / 2;
+                    Self::draw_text(fb, word_x, word_y, &word, urgency_color, 2);
+                    Self::draw_text(fb, word_x + 1, word_y, &word, urgency_color, 2); // Double-strike for weight
+                    number_y + number_height + 2
+                }
+                (days_text, Some(days_label)) => {
+                    // Use scale 2 for big friendly numbers
+                    // For 3+ digit numbers, use scale 1 to fit
+                    let scale = if days_text.len() >= 3 { 1 } else { 2 };
+                    Self::draw_big_number_centered(fb, number_y, &days_text, urgency_color, scale);
+
+                    let number_height = BIG_NUM_HEIGHT * scale;
+                    let label_y = number_y + number_height + 2;
+                    Self::draw_text_centered(fb, label_y, &days_label, theme::TEXT_MUTED, 1);
+                    label_y + 10
+                }
+            }
         };
-        let number_height = BIG_NUM_HEIGHT * scale;
-        let label_y = number_y + number_height + 2;
-        Self::draw_text_centered(fb, label_y, days_label, theme::TEXT_MUTED, 1);
 
         // Due date
-        Self::draw_text_centered(fb, label_y + 10, &task.next_due_date, theme::TEXT_MUTED, 1);
+        Self::draw_text_centered(fb, date_y, &task.next_due_date, theme::TEXT_MUTED, 1);
+
+        // Recurrence, small and only when it fits above the nav hint
+        if let Some(ref recurrence) = task.recurrence {
+            let recurrence_y = date_y + 10;
+            if recurrence_y + 7 < h - 9 && recurrence.len() <= max_chars_per_line {
+                Self::draw_text_centered(fb, recurrence_y, recurrence, theme::ACCENT, 1);
+            }
+        }
 
         // Navigation hint at bottom
         let nav_text = format!("<< {}/{} >>", index + 1, total);
@@ -251,12 +422,14 @@ impl Renderer {
         let sep_y = if name_lines.len() > 1 { 24 } else { 16 };
         fb.hline(10, sep_y, fb.width() - 20, theme::CARD_BORDER);
 
-        // Menu options
+        // Menu options, scrolled to keep the selection above the hint line
         let start_y = sep_y + 8;
         let item_height: u32 = 14;
+        let visible = ((h - 12 - start_y) / item_height) as usize;
+        let first = (selected + 1).saturating_sub(visible);
 
-        for (i, option) in options.iter().enumerate() {
-            let y = start_y + (i as u32 * item_height);
+        for (i, option) in options.iter().enumerate().skip(first).take(visible) {
+            let y = start_y + ((i - first) as u32 * item_height);
             let is_selected = i == selected;
 
             if is_selected {
@@ -280,19 +453,37 @@ impl Renderer {
 
     /// Render confirmation dialog
     pub fn render_confirm_dialog(fb: &mut FrameBuffer, message: &str, confirm_selected: bool) {
+        Self::render_confirm(fb, "!", message, &[], ("Cancel", "Delete"), theme::DESTRUCTIVE, confirm_selected);
+    }
+
+    /// Shared confirmation layout: icon, title (wrapped), muted detail lines, then the cancel
+    /// and confirm buttons. The selected button is a filled pill (cancel in green, confirm in
+    /// `accent`, which also colors the icon).
+    fn render_confirm(
+        fb: &mut FrameBuffer,
+        icon: &str,
+        title: &str,
+        message: &[&str],
+        labels: (&str, &str),
+        accent: Rgb565,
+        confirmed: bool,
+    ) {
         Self::clear(fb);
 
         let w = fb.width();
         let h = fb.height();
 
-        // Warning icon
-        Self::draw_text_centered(fb, 20, "!", theme::DESTRUCTIVE, 3);
+        Self::draw_text_centered(fb, 15, icon, accent, 3);
 
-        // Message (wrapped)
-        let lines = wrap_text(message, 20);
-        let start_y = 50;
-        for (i, line) in lines.iter().enumerate() {
-            Self::draw_text_centered(fb, start_y + (i as u32 * 10), line, theme::TEXT_PRIMARY, 1);
+        let mut y = 45;
+        for line in wrap_text(title, 20).iter().take(3) {
+            Self::draw_text_centered(fb, y, line, theme::TEXT_PRIMARY, 1);
+            y += 10;
+        }
+        y += 3;
+        for line in message {
+            Self::draw_text_centered(fb, y, line, theme::TEXT_MUTED, 1);
+            y += 10;
         }
 
         // Buttons
@@ -303,21 +494,20 @@ impl Renderer {
 
         let cancel_x = (w - btn_width * 2 - gap) / 2;
         let confirm_x = cancel_x + btn_width + gap;
+        let (cancel_label, confirm_label) = labels;
 
-        // Cancel button
-        if !confirm_selected {
-            Self::draw_button_pill(fb, cancel_x, btn_y, btn_width, btn_height, "Cancel", theme::SUCCESS, theme::TEXT_PRIMARY);
+        if !confirmed {
+            Self::draw_button_pill(fb, cancel_x, btn_y, btn_width, btn_height, cancel_label, theme::SUCCESS, theme::TEXT_PRIMARY);
         } else {
-            let text_x = cancel_x + (btn_width - Self::text_width("Cancel", 1)) / 2;
-            Self::draw_text(fb, text_x, btn_y + 4, "Cancel", theme::TEXT_MUTED, 1);
+            let text_x = cancel_x + (btn_width - Self::text_width(cancel_label, 1)) / 2;
+            Self::draw_text(fb, text_x, btn_y + 4, cancel_label, theme::TEXT_MUTED, 1);
         }
 
-        // Delete button
-        if confirm_selected {
-            Self::draw_button_pill(fb, confirm_x, btn_y, btn_width, btn_height, "Delete", theme::DESTRUCTIVE, theme::TEXT_PRIMARY);
+        if confirmed {
+            Self::draw_button_pill(fb, confirm_x, btn_y, btn_width, btn_height, confirm_label, accent, theme::TEXT_PRIMARY);
         } else {
-            let text_x = confirm_x + (btn_width - Self::text_width("Delete", 1)) / 2;
-            Self::draw_text(fb, text_x, btn_y + 4, "Delete", theme::TEXT_MUTED, 1);
+            let text_x = confirm_x + (btn_width - Self::text_width(confirm_label, 1)) / 2;
+            Self::draw_text(fb, text_x, btn_y + 4, confirm_label, theme::TEXT_MUTED, 1);
         }
     }
 
@@ -351,45 +541,64 @@ impl Renderer {
     }
 
     /// Render history view
-    pub fn render_history(fb: &mut FrameBuffer, task_name: &str, entries: &[HistoryDisplayEntry], selected: usize) {
+    pub fn render_history(
+        fb: &mut FrameBuffer,
+        task_name: &str,
+        entries: &[HistoryDisplayEntry],
+        selected: usize,
+        streak: u32,
+        average_days: Option<f32>,
+    ) {
         Self::clear(fb);
 
         let h = fb.height();
 
-        Self::draw_text_centered(fb, 4, "History", theme::TEXT_PRIMARY, 1);
-
-        // Task name (single line, truncated for history view)
-        let name = if task_name.len() > 18 {
-            let mut s = String::from(&task_name[..15]);
-            s.push_str("...");
-            s
-        } else {
-            String::from(task_name)
-        };
-        Self::draw_text_centered(fb, 14, &name, theme::TEXT_MUTED, 1);
+        // On-time streak rides along in the title so the list keeps its rows
+        let title = if streak > 0 { format!("History - Streak: {}", streak) } else { String::from("History") };
+        let mut start_y = Self::draw_header(fb, &title, Some(task_name));
 
-        fb.hline(10, 24, fb.width() - 20, theme::CARD_BORDER);
+        // Average gap between completions, to compare with the schedule; costs the list a row
+        if let Some(avg) = average_days {
+            Self::draw_text_centered(fb, start_y, &format!("avg: {:.1} d", avg), theme::TEXT_MUTED, 1);
+            start_y += 11;
+        }
 
         if entries.is_empty() {
             Self::draw_text_centered(fb, 50, "No history", theme::TEXT_MUTED, 1);
         } else {
-            let max_visible = 6;
-            let start_idx = if selected >= max_visible {
-                selected - max_visible + 1
-            } else {
-                0
+            let max_visible: u32 = if average_days.is_some() { 5 } else { 6 };
+            let item_height: u32 = 14;
+            let note_height: u32 = 9;
+            let list_height = max_visible * item_height;
+
+            // Entries with a note or an old schedule take extra lines, so size the window by height
+            let entry_height = |e: &HistoryDisplayEntry| {
+                let extra_lines = e.note.is_some() as u32 + e.schedule.is_some() as u32;
+                item_height + extra_lines * note_height
             };
 
-            let item_height: u32 = 14;
-            let start_y: u32 = 30;
+            // Keep the selected entry as the last visible one once scrolled
+            let mut start_idx = selected.min(entries.len() - 1);
+            let mut used = entry_height(&entries[start_idx]);
+            while start_idx > 0 && used + entry_height(&entries[start_idx - 1]) <= list_height {
+                start_idx -= 1;
+                used += entry_height(&entries[start_idx]);
+            }
+
+            let max_note_chars = ((fb.width() - 24) / (FONT_WIDTH + 1)) as usize;
+            let mut y = start_y;
+            let mut shown = 0;
 
-            for (i, entry) in entries.iter().skip(start_idx).take(max_visible).enumerate() {
-                let actual_idx = start_idx + i;
-                let y = start_y + (i as u32 * item_height);
-                let is_selected = actual_idx == selected;
+            for (i, entry) in entries.iter().enumerate().skip(start_idx) {
+                let height = entry_height(entry);
+                if y + height > start_y + list_height {
+                    break;
+                }
+                shown += 1;
+                let is_selected = i == selected;
 
                 if is_selected {
-                    fb.fill_rect(4, y - 2, fb.width() - 8, item_height, theme::SELECTION_BG);
+                    fb.fill_rect(4, y - 2, fb.width() - 8, height, theme::SELECTION_BG);
                 }
 
                 let color = if is_selected { theme::TEXT_PRIMARY } else { theme::TEXT_MUTED };
@@ -401,22 +610,118 @@ impl Renderer {
                     let x = fb.width() - Self::text_width(&days_text, 1) - 8;
                     Self::draw_text(fb, x, y, &days_text, theme::TEXT_MUTED, 1);
                 }
+
+                if let Some(ref note) = entry.note {
+                    let note_text = if note.chars().count() > max_note_chars {
+                        let mut s: String = note.chars().take(max_note_chars - 3).collect();
+                        s.push_str("...");
+                        s
+                    } else {
+                        note.clone()
+                    };
+                    Self::draw_text(fb, 16, y + note_height, &note_text, theme::TEXT_MUTED, 1);
+                }
+
+                if let Some(ref schedule) = entry.schedule {
+                    let schedule_y = if entry.note.is_some() { y + note_height * 2 } else { y + note_height };
+                    let schedule_text: String = schedule.chars().take(max_note_chars).collect();
+                    Self::draw_text(fb, 16, schedule_y, &schedule_text, theme::TEXT_MUTED, 1);
+                }
+
+                y += height;
             }
+
+            Self::draw_scrollbar(fb, start_y - 2, list_height, start_idx, shown, entries.len());
         }
 
         Self::draw_text_centered(fb, h - 10, "long press: back", theme::TEXT_MUTED, 1);
     }
 
+    /// Draw a thin scrollbar along the right edge for a list showing `visible` of `total` items
+    /// from `first`. Nothing is drawn when the whole list fits.
+    fn draw_scrollbar(fb: &mut FrameBuffer, top: u32, height: u32, first: usize, visible: usize, total: usize) {
+        if visible >= total {
+            return;
+        }
+        let x = fb.width() - 3;
+        fb.fill_rect(x, top, 2, height, theme::CARD_BORDER);
+
+        let thumb_h = ((height as usize * visible / total) as u32).clamp(4, height);
+        let max_y = height - thumb_h;
+        // Pin the thumb to the bottom at the end of the list so rounding can't leave a gap
+        let thumb_y = if first + visible >= total { max_y } else { ((height as usize * first / total) as u32).min(max_y) };
+        fb.fill_rect(x, top + thumb_y, 2, thumb_h, theme::TEXT_MUTED);
+    }
+
+    /// Draw a three-bar signal glyph with its baseline at `bottom`; `bars` of them are lit
+    fn draw_signal(fb: &mut FrameBuffer, x: u32, bottom: u32, bars: u8) {
+        for i in 0..3u32 {
+            let bar_h = 4 + i * 3;
+            let color = if (i as u8) < bars { theme::TEXT_PRIMARY } else { theme::CARD_BORDER };
+            fb.fill_rect(x + i * 3, bottom + 1 - bar_h, 2, bar_h, color);
+        }
+    }
+
+    /// Draw the clock in the bottom-right corner, opposite the centered footer hints
+    pub fn draw_footer_clock(fb: &mut FrameBuffer, time: &str) {
+        let text_w = Self::text_width(time, 1);
+        let x = fb.width() - text_w - 1;
+        let y = fb.height() - 9;
+        fb.fill_rect(x - 1, y - 1, text_w + 1, 9, theme::BACKGROUND);
+        Self::draw_text(fb, x, y, time, theme::TEXT_MUTED, 1);
+    }
+
+    /// Draw the button hold indicator along the top edge of the current view.
+    /// Fills while held; turns accent once releasing would count as a long press.
+    pub fn draw_hold_progress(fb: &mut FrameBuffer, progress: f32) {
+        let w = fb.width();
+        let filled = ((w as f32) * progress.clamp(0.0, 1.0)) as u32;
+        let color = if progress >= 1.0 { theme::ACCENT } else { theme::TEXT_MUTED };
+        fb.fill_rect(0, 0, w, 3, theme::CARD_BORDER);
+        fb.fill_rect(0, 0, filled, 3, color);
+    }
+
+    /// Draw a view title, an optional muted subtitle (one line, truncated) and the separator.
+    /// Returns the y where the view's content starts.
+    pub fn draw_header(fb: &mut FrameBuffer, title: &str, subtitle: Option<&str>) -> u32 {
+        Self::draw_text_centered(fb, 4, title, theme::TEXT_PRIMARY, 1);
+
+        let mut sep_y = 16;
+        if let Some(subtitle) = subtitle {
+            let text = if subtitle.chars().count() > 18 {
+                let mut s: String = subtitle.chars().take(15).collect();
+                s.push_str("...");
+                s
+            } else {
+                String::from(subtitle)
+            };
+            Self::draw_text_centered(fb, 14, &text, theme::TEXT_MUTED, 1);
+            sep_y = 24;
+        }
+
+        fb.hline(10, sep_y, fb.width() - 20, theme::CARD_BORDER);
+        sep_y + 6
+    }
+
+    /// Draw a transient message box over the bottom of the current view
+    pub fn render_toast(fb: &mut FrameBuffer, message: &str) {
+        let w = fb.width();
+        let box_h: u32 = 18;
+        let y = fb.height() - box_h - 4;
+
+        fb.fill_rect(8, y, w - 16, box_h, theme::CARD_BORDER);
+        fb.fill_rect(9, y + 1, w - 18, box_h - 2, theme::CARD_BG);
+        Self::draw_text_centered(fb, y + 6, message, theme::TEXT_PRIMARY, 1);
+    }
+
     /// Render settings menu
-    pub fn render_settings(fb: &mut FrameBuffer, selected: usize, screen_timeout_enabled: bool) {
+    pub fn render_settings(fb: &mut FrameBuffer, selected: usize, screen_timeout_enabled: bool, device_name: &str) {
         Self::clear(fb);
 
         let h = fb.height();
 
-        Self::draw_text_centered(fb, 4, "Settings", theme::TEXT_PRIMARY, 1);
-        fb.hline(10, 16, fb.width() - 20, theme::CARD_BORDER);
-
-        let start_y: u32 = 24;
+        // Setting rows are taller than list rows, so leave a little more room under the header
+        let start_y = Self::draw_header(fb, "Settings", None) + 2;
         let item_height: u32 = 16;
 
         // Manage Tasks (index 0)
@@ -431,9 +736,20 @@ impl Renderer {
         let arrow_x = fb.width() - Self::text_width(">", 1) - 8;
         Self::draw_text(fb, arrow_x, manage_y, ">", theme::TEXT_MUTED, 1);
 
-        // Screen Timeout (index 1)
-        let timeout_y = start_y + item_height;
-        let timeout_selected = selected == 1;
+        // Clock (index 1)
+        let clock_y = start_y + item_height;
+        let clock_selected = selected == 1;
+        if clock_selected {
+            fb.fill_rect(4, clock_y - 2, fb.width() - 8, item_height - 2, theme::SELECTION_BG);
+            Self::draw_text(fb, 8, clock_y, ">", theme::ACCENT, 1);
+        }
+        let clock_color = if clock_selected { theme::TEXT_PRIMARY } else { theme::TEXT_MUTED };
+        Self::draw_text(fb, 20, clock_y, "Clock", clock_color, 1);
+        Self::draw_text(fb, arrow_x, clock_y, ">", theme::TEXT_MUTED, 1);
+
+        // Screen Timeout (index 2)
+        let timeout_y = start_y + (2 * item_height);
+        let timeout_selected = selected == 2;
         if timeout_selected {
             fb.fill_rect(4, timeout_y - 2, fb.width() - 8, item_height - 2, theme::SELECTION_BG);
             Self::draw_text(fb, 8, timeout_y, ">", theme::ACCENT, 1);
@@ -445,9 +761,9 @@ impl Renderer {
         let toggle_x = fb.width() - Self::text_width(toggle_text, 1) - 8;
         Self::draw_text(fb, toggle_x, timeout_y, toggle_text, toggle_color, 1);
 
-        // Reset WiFi (index 2)
-        let wifi_y = start_y + (2 * item_height);
-        let wifi_selected = selected == 2;
+        // Reset WiFi (index 3)
+        let wifi_y = start_y + (3 * item_height);
+        let wifi_selected = selected == 3;
         if wifi_selected {
             fb.fill_rect(4, wifi_y - 2, fb.width() - 8, item_height - 2, theme::SELECTION_BG);
             Self::draw_text(fb, 8, wifi_y, ">", theme::ACCENT, 1);
@@ -455,9 +771,9 @@ impl Renderer {
         let wifi_color = if wifi_selected { theme::DESTRUCTIVE } else { theme::TEXT_MUTED };
         Self::draw_text(fb, 20, wifi_y, "Reset WiFi", wifi_color, 1);
 
-        // Back (index 3)
-        let back_y = start_y + (3 * item_height);
-        let back_selected = selected == 3;
+        // Back (index 4)
+        let back_y = start_y + (4 * item_height);
+        let back_selected = selected == 4;
         if back_selected {
             fb.fill_rect(4, back_y - 2, fb.width() - 8, item_height - 2, theme::SELECTION_BG);
             Self::draw_text(fb, 8, back_y, ">", theme::ACCENT, 1);
@@ -465,9 +781,25 @@ impl Renderer {
         let back_color = if back_selected { theme::TEXT_PRIMARY } else { theme::TEXT_MUTED };
         Self::draw_text(fb, 20, back_y, "Back", back_color, 1);
 
+        // Which device this is (households with several units)
+        if !device_name.is_empty() {
+            Self::draw_text_centered(fb, back_y + item_height + 4, device_name, theme::ACCENT, 1);
+        }
+
         Self::draw_text_centered(fb, h - 10, "press to select", theme::TEXT_MUTED, 1);
     }
 
+    /// Render the big clock (HH:MM, or "--:--" until the time is known)
+    pub fn render_clock(fb: &mut FrameBuffer, hh_mm: &str) {
+        Self::clear(fb);
+
+        let scale = 2;
+        let y = (fb.height() - BIG_NUM_HEIGHT * scale) / 2;
+        Self::draw_big_number_centered(fb, y, hh_mm, theme::TEXT_PRIMARY, scale);
+
+        Self::draw_text_centered(fb, fb.height() - 10, "press to return", theme::TEXT_MUTED, 1);
+    }
+
     /// Render empty state (mode-aware)
     pub fn render_empty(fb: &mut FrameBuffer, wifi_mode: &WiFiMode) {
         Self::clear(fb);
@@ -482,17 +814,26 @@ impl Renderer {
         Self::draw_text_centered(fb, fb.height() - 10, hint, theme::TEXT_MUTED, 1);
     }
 
-    /// Render dashboard with metrics and navigation
+    /// Render dashboard with metrics and navigation. `week_days` is the configured horizon of
+    /// the week bucket; anything other than 7 is spelled out in its label. `signal` is the
+    /// station signal in bars (0-3), None in AP mode.
     pub fn render_dashboard(
         fb: &mut FrameBuffer,
-        overdue: u32,
-        today: u32,
-        week: u32,
-        total: u32,
+        counts: &TaskCounts,
+        cells: &[DashboardMetric],
         selected: usize,
+        week_days: i32,
+        clock: Option<&str>,
+        signal: Option<u8>,
     ) {
         Self::clear(fb);
 
+        let overdue = counts.overdue;
+        let today = counts.today;
+        let tomorrow = counts.tomorrow;
+        let week = counts.week;
+        let total = counts.total;
+
         let w = fb.width();
         let h = fb.height();
 
@@ -500,7 +841,17 @@ impl Renderer {
         let bar_y: u32 = 3;
         let bar_h: u32 = 12;
         let bar_margin: u32 = 6;
-        let bar_w = w - (bar_margin * 2);
+        // The nav buttons fill the footer, so the clock and signal live at the end of the bar instead
+        let clock_w = clock.map(|c| Self::text_width(c, 1) + 4).unwrap_or(0);
+        let signal_w = if signal.is_some() { SIGNAL_GLYPH_WIDTH + 3 } else { 0 };
+        let bar_w = w - (bar_margin * 2) - clock_w - signal_w;
+
+        if let Some(clock) = clock {
+            Self::draw_text(fb, w - bar_margin - Self::text_width(clock, 1) + 1, bar_y + 3, clock, theme::TEXT_MUTED, 1);
+        }
+        if let Some(bars) = signal {
+            Self::draw_signal(fb, w - bar_margin - clock_w - SIGNAL_GLYPH_WIDTH + 1, bar_y + bar_h - 2, bars);
+        }
 
         // Draw bar background with border
         fb.fill_rect(bar_margin, bar_y, bar_w, bar_h, theme::CARD_BG);
@@ -518,19 +869,34 @@ impl Renderer {
         if total > 0 {
             let overdue_w = (overdue as f32 / total as f32 * inner_w as f32) as u32;
             let today_w = (today as f32 / total as f32 * inner_w as f32) as u32;
-            let week_only = week.saturating_sub(overdue).saturating_sub(today);
+            let tomorrow_w = (tomorrow as f32 / total as f32 * inner_w as f32) as u32;
+            let week_only = week.saturating_sub(overdue).saturating_sub(today).saturating_sub(tomorrow);
             let week_w = (week_only as f32 / total as f32 * inner_w as f32) as u32;
 
             let mut x = inner_x;
 
             if overdue_w > 0 {
                 fb.fill_rect(x, inner_y, overdue_w, inner_h, theme::URGENCY_OVERDUE);
+                // A large overdue share gets deep-red diagonal hatching so it reads as alarming
+                if overdue * 100 > total * theme::OVERDUE_ALARM_PERCENT {
+                    for dy in 0..inner_h {
+                        for dx in 0..overdue_w {
+                            if (dx + dy) % 4 == 0 {
+                                fb.set_pixel(x + dx, inner_y + dy, theme::URGENCY_OVERDUE_DEEP);
+                            }
+                        }
+                    }
+                }
                 x += overdue_w;
             }
             if today_w > 0 {
                 fb.fill_rect(x, inner_y, today_w, inner_h, theme::URGENCY_TODAY);
                 x += today_w;
             }
+            if tomorrow_w > 0 {
+                fb.fill_rect(x, inner_y, tomorrow_w, inner_h, theme::URGENCY_TOMORROW);
+                x += tomorrow_w;
+            }
             if week_w > 0 {
                 fb.fill_rect(x, inner_y, week_w, inner_h, theme::URGENCY_WEEK);
                 x += week_w;
@@ -541,21 +907,32 @@ impl Renderer {
             }
         }
 
-        // === 2x2 METRIC GRID ===
+        // === METRIC GRID ===
+        // Up to two cells per row on one row; beyond that two rows, top row takes the extra cell
         let grid_y: u32 = 18;
-        let cell_w = (w - 12) / 2;
-        let cell_h: u32 = 38;
+        let grid_h: u32 = 80;
         let gap: u32 = 4;
-
-        let col1_x: u32 = 4;
-        let col2_x = col1_x + cell_w + gap;
-        let row1_y = grid_y;
-        let row2_y = grid_y + cell_h + gap;
-
-        Self::draw_metric_cell(fb, col1_x, row1_y, cell_w, cell_h, "OVERDUE", overdue, theme::URGENCY_OVERDUE, selected == 0);
-        Self::draw_metric_cell(fb, col2_x, row1_y, cell_w, cell_h, "TODAY", today, theme::URGENCY_TODAY, selected == 1);
-        Self::draw_metric_cell(fb, col1_x, row2_y, cell_w, cell_h, "WEEK", week, theme::URGENCY_WEEK, selected == 2);
-        Self::draw_metric_cell(fb, col2_x, row2_y, cell_w, cell_h, "TOTAL", total, theme::URGENCY_UPCOMING, selected == 3);
+        let n = cells.len() as u32;
+        let top_count = if n <= 2 { n } else { n.div_ceil(2) };
+        let rows = if n <= 2 { 1 } else { 2 };
+        let cell_h = (grid_h - gap * (rows - 1)) / rows;
+        let week_label = week_label(week_days);
+
+        for (i, metric) in cells.iter().enumerate() {
+            let i = i as u32;
+            let (row, col, row_count) = if i < top_count { (0, i, top_count) } else { (1, i - top_count, n - top_count) };
+            let cell_w = (w - 8 - gap * (row_count - 1)) / row_count;
+            let x = 4 + col * (cell_w + gap);
+            let y = grid_y + row * (cell_h + gap);
+            let (label, color) = match metric {
+                DashboardMetric::Overdue => ("OVERDUE", theme::URGENCY_OVERDUE),
+                DashboardMetric::Today => ("TODAY", theme::URGENCY_TODAY),
+                DashboardMetric::Tomorrow => ("TOMORROW", theme::URGENCY_TOMORROW),
+                DashboardMetric::Week => (week_label.as_str(), theme::URGENCY_WEEK),
+                DashboardMetric::Total => ("TOTAL", theme::URGENCY_UPCOMING),
+            };
+            Self::draw_metric_cell(fb, x, y, cell_w, cell_h, label, counts.metric(*metric), color, selected == i as usize);
+        }
 
         // === NAVIGATION BAR ===
         let nav_y = h - 24;
@@ -566,10 +943,10 @@ impl Renderer {
         let all_x = (w - btn_w * 2 - nav_gap) / 2;
         let settings_x = all_x + btn_w + nav_gap;
 
-        if selected == 4 {
+        if selected == cells.len() {
             Self::draw_button_pill(fb, all_x, nav_y, btn_w, btn_h, "All Tasks", theme::ACCENT, theme::TEXT_PRIMARY);
             Self::draw_text(fb, settings_x + (btn_w - Self::text_width("Settings", 1)) / 2, nav_y + 5, "Settings", theme::TEXT_MUTED, 1);
-        } else if selected == 5 {
+        } else if selected == cells.len() + 1 {
             Self::draw_text(fb, all_x + (btn_w - Self::text_width("All Tasks", 1)) / 2, nav_y + 5, "All Tasks", theme::TEXT_MUTED, 1);
             Self::draw_button_pill(fb, settings_x, nav_y, btn_w, btn_h, "Settings", theme::ACCENT, theme::TEXT_PRIMARY);
         } else {
@@ -605,11 +982,11 @@ impl Renderer {
             fb.vline(x + w - 2, y, h, color);
         }
 
-        // Draw big number centered
+        // Draw big number centered in the space above the label
         let num_str = format!("{}", count);
         let num_w = Self::big_number_width(&num_str, 1);
         let num_x = x + (w.saturating_sub(num_w)) / 2;
-        let num_y = y + 6;
+        let num_y = y + (h.saturating_sub(10 + BIG_NUM_HEIGHT)) / 2 + 1;
 
         for (i, ch) in num_str.chars().enumerate() {
             Self::draw_big_number(fb, num_x + i as u32 * (BIG_NUM_WIDTH + 2), num_y, ch, color, 1);
@@ -642,6 +1019,81 @@ impl Renderer {
         Self::draw_text_centered(fb, h - 12, &nav_text, theme::TEXT_MUTED, 1);
     }
 
+    /// Render the compact task list: one row per task with a days-left badge, then Back.
+    /// `selected` of None means the Back row.
+    pub fn render_task_list_compact(fb: &mut FrameBuffer, title: &str, rows: &[TaskRowData], selected: Option<usize>) {
+        Self::clear(fb);
+
+        let h = fb.height();
+        let w = fb.width();
+
+        let start_y = Self::draw_header(fb, title, None);
+
+        let item_height: u32 = 13;
+        let max_visible = ((h - 14 - start_y) / item_height) as usize;
+        let back_idx = rows.len();
+        let selected_idx = selected.unwrap_or(back_idx).min(back_idx);
+
+        // Keep the selected row as the last visible one once scrolled
+        let start_idx = (selected_idx + 1).saturating_sub(max_visible);
+
+        for (slot, i) in (start_idx..=back_idx).take(max_visible).enumerate() {
+            let y = start_y + slot as u32 * item_height;
+            let is_selected = i == selected_idx;
+
+            if is_selected {
+                fb.fill_rect(4, y - 2, w - 8, item_height - 1, theme::SELECTION_BG);
+            }
+
+            let color = if is_selected { theme::TEXT_PRIMARY } else { theme::TEXT_MUTED };
+
+            let Some(row) = rows.get(i) else {
+                Self::draw_text(fb, 12, y, "< Back", color, 1);
+                continue;
+            };
+
+            let urgency_color = if row.urgency == "overdue" {
+                theme::overdue_color(row.days_until_due.unsigned_abs())
+            } else {
+                theme::urgency_color(&row.urgency)
+            };
+            fb.fill_rect(6, y - 1, 2, 9, urgency_color);
+
+            let badge = if row.urgency == "paused" {
+                String::from("--")
+            } else {
+                match row.days_until_due {
+                    0 => String::from("today"),
+                    d if d < 0 => format!("-{}d", d.unsigned_abs()),
+                    d => format!("{}d", d),
+                }
+            };
+            let badge_w = Self::text_width(&badge, 1);
+            Self::draw_text(fb, w - badge_w - 8, y, &badge, urgency_color, 1);
+
+            let max_name_chars = ((w - badge_w - 26) / (FONT_WIDTH + 1)) as usize;
+            let name = if row.name.chars().count() > max_name_chars {
+                let mut s: String = row.name.chars().take(max_name_chars.saturating_sub(3)).collect();
+                s.push_str("...");
+                s
+            } else {
+                row.name.clone()
+            };
+            Self::draw_text(fb, 12, y, &name, color, 1);
+        }
+
+        // The Back row counts as a list entry
+        let total_rows = back_idx + 1;
+        let visible = max_visible.min(total_rows - start_idx);
+        Self::draw_scrollbar(fb, start_y - 2, max_visible as u32 * item_height, start_idx, visible, total_rows);
+
+        let position = match selected {
+            Some(i) => format!("{}/{}", i + 1, rows.len()),
+            None => format!("0/{}", rows.len()),
+        };
+        Self::draw_text_centered(fb, h - 10, &position, theme::TEXT_MUTED, 1);
+    }
+
     /// Render empty filtered list message
     pub fn render_empty_filtered(fb: &mut FrameBuffer, filter_name: &str) {
         Self::clear(fb);
@@ -651,12 +1103,13 @@ impl Renderer {
         let msg = match filter_name {
             "overdue" => "Nothing overdue!",
             "today" => "Nothing due today!",
+            "tomorrow" => "Nothing tomorrow!",
             "week" => "Nothing this week!",
             _ => "No tasks found",
         };
         Self::draw_text_centered(fb, 65, msg, theme::SUCCESS, 1);
 
-        Self::draw_text_centered(fb, fb.height() - 10, "long press: back", theme::TEXT_MUTED, 1);
+        Self::draw_text_centered(fb, fb.height() - 10, "press: back", theme::TEXT_MUTED, 1);
     }
 
     /// Render QR code screen (mode-aware: WiFi QR in AP mode, URL QR in STA mode)
@@ -724,6 +1177,16 @@ impl Renderer {
         Self::draw_text_centered(fb, h - 10, "press: back", theme::TEXT_MUTED, 1);
     }
 
+    /// Render the boot splash: logo and product name
+    pub fn render_boot_splash(fb: &mut FrameBuffer) {
+        Self::clear(fb);
+
+        let x = (fb.width() - LOGO_WIDTH) / 2;
+        Self::draw_bitmap(fb, x, 30, LOGO_WIDTH, LOGO_HEIGHT, &LOGO, theme::ACCENT);
+
+        Self::draw_text_centered(fb, 76, "Days Tracker", theme::TEXT_PRIMARY, 1);
+    }
+
     /// Render "connecting" splash screen
     pub fn render_connecting(fb: &mut FrameBuffer, message: &str) {
         Self::clear(fb);
@@ -748,42 +1211,73 @@ impl Renderer {
 
     /// Render Reset WiFi confirmation dialog
     pub fn render_reset_wifi_confirm(fb: &mut FrameBuffer, confirmed: bool) {
+        let message = ["Device will restart", "in setup mode and", "clear the API PIN"];
+        Self::render_confirm(fb, "!", "Reset WiFi?", &message, ("Cancel", "Reset"), theme::DESTRUCTIVE, confirmed);
+    }
+
+    /// Render reset-task confirmation dialog (clears history, restarts the schedule today)
+    pub fn render_reset_task_confirm(fb: &mut FrameBuffer, task_name: &str, confirmed: bool) {
+        let name = if task_name.chars().count() > 14 {
+            let mut s: String = task_name.chars().take(11).collect();
+            s.push_str("...");
+            s
+        } else {
+            String::from(task_name)
+        };
+        let title = format!("Reset '{}'?", name);
+        let message = ["Clears its history,", "due again today"];
+        Self::render_confirm(fb, "!", &title, &message, ("Cancel", "Reset"), theme::DESTRUCTIVE, confirmed);
+    }
+
+    /// Render the reschedule view: candidate due date, large, with its weekday and distance
+    /// from today. `changed` is false while it still matches the current due date.
+    pub fn render_reschedule(fb: &mut FrameBuffer, task_name: &str, date: NaiveDate, today: NaiveDate, changed: bool) {
         Self::clear(fb);
 
         let w = fb.width();
         let h = fb.height();
 
-        // Warning icon
-        Self::draw_text_centered(fb, 15, "!", theme::DESTRUCTIVE, 3);
-
-        Self::draw_text_centered(fb, 45, "Reset WiFi?", theme::TEXT_PRIMARY, 1);
-        Self::draw_text_centered(fb, 58, "Device will restart", theme::TEXT_MUTED, 1);
-        Self::draw_text_centered(fb, 68, "in setup mode", theme::TEXT_MUTED, 1);
-
-        // Buttons
-        let btn_y = h - 28;
-        let btn_width: u32 = 52;
-        let btn_height: u32 = 16;
-        let gap: u32 = 16;
-
-        let cancel_x = (w - btn_width * 2 - gap) / 2;
-        let confirm_x = cancel_x + btn_width + gap;
-
-        // Cancel button
-        if !confirmed {
-            Self::draw_button_pill(fb, cancel_x, btn_y, btn_width, btn_height, "Cancel", theme::SUCCESS, theme::TEXT_PRIMARY);
+        let name = if task_name.chars().count() > 24 {
+            let mut s: String = task_name.chars().take(21).collect();
+            s.push_str("...");
+            s
         } else {
-            let text_x = cancel_x + (btn_width - Self::text_width("Cancel", 1)) / 2;
-            Self::draw_text(fb, text_x, btn_y + 4, "Cancel", theme::TEXT_MUTED, 1);
-        }
+            String::from(task_name)
+        };
+        Self::draw_text_centered(fb, 4, &name, theme::TEXT_MUTED, 1);
+        fb.hline(10, 16, w - 20, theme::CARD_BORDER);
+
+        // Date with arrows hinting at the knob direction
+        let date_text = date.format("%b %d").to_string();
+        let date_color = if changed { theme::ACCENT } else { theme::TEXT_PRIMARY };
+        Self::draw_text_centered(fb, 32, &date_text, date_color, 2);
+        let arrow_y = 32 + (FONT_HEIGHT * 2 - FONT_HEIGHT) / 2;
+        Self::draw_text(fb, 10, arrow_y, "<", theme::TEXT_MUTED, 1);
+        Self::draw_text(fb, w - 10 - FONT_WIDTH, arrow_y, ">", theme::TEXT_MUTED, 1);
+
+        Self::draw_text_centered(fb, 56, &date.format("%A, %Y").to_string(), theme::TEXT_MUTED, 1);
+
+        let days = (date - today).num_days();
+        let relative = match days {
+            0 => String::from("today"),
+            1 => String::from("tomorrow"),
+            -1 => String::from("yesterday"),
+            d if d > 1 => format!("in {} days", d),
+            d => format!("{} days ago", -d),
+        };
+        let relative_color = if days < 0 { theme::URGENCY_OVERDUE } else { theme::TEXT_PRIMARY };
+        Self::draw_text_centered(fb, 72, &relative, relative_color, 1);
 
-        // Reset button
-        if confirmed {
-            Self::draw_button_pill(fb, confirm_x, btn_y, btn_width, btn_height, "Reset", theme::DESTRUCTIVE, theme::TEXT_PRIMARY);
-        } else {
-            let text_x = confirm_x + (btn_width - Self::text_width("Reset", 1)) / 2;
-            Self::draw_text(fb, text_x, btn_y + 4, "Reset", theme::TEXT_MUTED, 1);
-        }
+        let hint = if changed { "press to save" } else { "turn to change" };
+        Self::draw_text_centered(fb, h - 20, hint, theme::TEXT_MUTED, 1);
+        Self::draw_text_centered(fb, h - 10, "hold to cancel", theme::TEXT_MUTED, 1);
+    }
+
+    /// Render snooze-all-overdue confirmation dialog
+    pub fn render_snooze_confirm(fb: &mut FrameBuffer, count: u32, confirmed: bool) {
+        let title = format!("Snooze {} overdue?", count);
+        let message = ["All move to tomorrow", "(not marked done)"];
+        Self::render_confirm(fb, "zZ", &title, &message, ("Cancel", "Snooze"), theme::ACCENT, confirmed);
     }
 
     /// Render station mode "connected" splash
@@ -800,6 +1294,106 @@ impl Renderer {
         Self::draw_text_centered(fb, 80, url, theme::ACCENT, 1);
         Self::draw_text_centered(fb, 100, "Starting...", theme::TEXT_MUTED, 1);
     }
+
+    /// Self-test page 1: color bars (primaries + theme colors)
+    pub fn render_selftest_colors(fb: &mut FrameBuffer) {
+        Self::clear(fb);
+
+        let w = fb.width();
+        let h = fb.height();
+
+        Self::draw_text_centered(fb, 2, "Self-test 1/4: Colors", theme::TEXT_PRIMARY, 1);
+
+        let bars = [
+            Rgb565::new(31, 0, 0),
+            Rgb565::new(0, 63, 0),
+            Rgb565::new(0, 0, 31),
+            Rgb565::new(31, 63, 31),
+            theme::URGENCY_OVERDUE,
+            theme::URGENCY_TODAY,
+            theme::URGENCY_TOMORROW,
+            theme::URGENCY_WEEK,
+            theme::URGENCY_UPCOMING,
+            theme::ACCENT,
+        ];
+        let bar_w = w / bars.len() as u32;
+        let bar_y: u32 = 12;
+        let bar_h = h - 24;
+        for (i, &color) in bars.iter().enumerate() {
+            fb.fill_rect(i as u32 * bar_w, bar_y, bar_w, bar_h, color);
+        }
+
+        Self::draw_text_centered(fb, h - 10, "press: next", theme::TEXT_MUTED, 1);
+    }
+
+    /// Self-test page 2: every 5x7 glyph plus the big number font
+    pub fn render_selftest_glyphs(fb: &mut FrameBuffer) {
+        Self::clear(fb);
+
+        let h = fb.height();
+        let chars_per_line = ((fb.width() - 4) / (FONT_WIDTH + 1)) as usize;
+
+        Self::draw_text_centered(fb, 2, "Self-test 2/4: Glyphs", theme::TEXT_PRIMARY, 1);
+
+        let glyphs: Vec<char> = (' '..='~').collect();
+        for (i, line) in glyphs.chunks(chars_per_line).enumerate() {
+            let text: String = line.iter().collect();
+            Self::draw_text(fb, 2, 14 + i as u32 * 9, &text, theme::TEXT_PRIMARY, 1);
+        }
+
+        Self::draw_big_number_centered(fb, 60, "0123456789-", theme::ACCENT, 1);
+
+        Self::draw_text_centered(fb, h - 10, "press: next", theme::TEXT_MUTED, 1);
+    }
+
+    /// Self-test page 3: live encoder counters
+    pub fn render_selftest_encoder(fb: &mut FrameBuffer, clockwise: u32, counter_clockwise: u32, presses: u32) {
+        Self::clear(fb);
+
+        let h = fb.height();
+
+        Self::draw_text_centered(fb, 2, "Self-test 3/4: Encoder", theme::TEXT_PRIMARY, 1);
+        Self::draw_text_centered(fb, 20, "Turn and press", theme::TEXT_MUTED, 1);
+
+        Self::draw_text(fb, 20, 40, "CW", theme::TEXT_MUTED, 1);
+        Self::draw_text(fb, 20, 60, "CCW", theme::TEXT_MUTED, 1);
+        Self::draw_text(fb, 20, 80, "Press", theme::TEXT_MUTED, 1);
+
+        for (i, count) in [clockwise, counter_clockwise, presses].iter().enumerate() {
+            let text = format!("{}", count);
+            let x = fb.width() - Self::text_width(&text, 2) - 20;
+            Self::draw_text(fb, x, 37 + i as u32 * 20, &text, theme::ACCENT, 2);
+        }
+
+        Self::draw_text_centered(fb, h - 10, "long press: next", theme::TEXT_MUTED, 1);
+    }
+
+    /// Self-test page 4: WiFi scan result (None = scanning)
+    pub fn render_selftest_wifi(fb: &mut FrameBuffer, networks: Option<Result<usize, ()>>) {
+        Self::clear(fb);
+
+        let h = fb.height();
+
+        Self::draw_text_centered(fb, 2, "Self-test 4/4: WiFi", theme::TEXT_PRIMARY, 1);
+
+        match networks {
+            None => {
+                Self::draw_text_centered(fb, 50, "Scanning...", theme::TEXT_MUTED, 1);
+            }
+            Some(Ok(count)) => {
+                let text = format!("{}", count);
+                let color = if count > 0 { theme::SUCCESS } else { theme::DESTRUCTIVE };
+                Self::draw_big_number_centered(fb, 36, &text, color, 2);
+                Self::draw_text_centered(fb, 78, "networks found", theme::TEXT_MUTED, 1);
+            }
+            Some(Err(())) => {
+                Self::draw_text_centered(fb, 50, "Scan failed", theme::DESTRUCTIVE, 2);
+            }
+        }
+
+        Self::draw_text_centered(fb, h - 20, "press: rescan", theme::TEXT_MUTED, 1);
+        Self::draw_text_centered(fb, h - 10, "long press: boot", theme::TEXT_MUTED, 1);
+    }
 }
 
 /// Wrap text to multiple lines
@@ -826,6 +1420,50 @@ pub fn wrap_text(text: &str, max_width: usize) -> Vec<String> {
     lines
 }
 
+/// Due today/tomorrow: a bold word reads faster than a 0 or 1 (None keeps the countdown)
+fn due_word(days_until_due: i32) -> Option<&'static str> {
+    match days_until_due {
+        0 => Some("TODAY"),
+        1 => Some("TOMORROW"),
+        _ => None,
+    }
+}
+
+/// The card's headline when there's no custom message: a due word or the large day count, and
+/// the label under the count ("DAYS LEFT" or an overdue label that escalates with age). No label
+/// with a word, as the due date moves up into that row.
+fn due_headline(days_until_due: i32) -> (String, Option<String>) {
+    if let Some(word) = due_word(days_until_due) {
+        return (String::from(word), None);
+    }
+    let label = if days_until_due < 0 {
+        overdue_label(days_until_due.unsigned_abs())
+    } else {
+        String::from("DAYS LEFT")
+    };
+    (format!("{}", days_until_due.unsigned_abs()), Some(label))
+}
+
+/// Dashboard label for the week bucket: "WEEK" for the default 7-day horizon, else e.g. "14 DAYS"
+fn week_label(week_days: i32) -> String {
+    if week_days == 7 {
+        String::from("WEEK")
+    } else {
+        format!("{} DAYS", week_days)
+    }
+}
+
+/// Overdue phrasing by age: days for the first week, then weeks, then months
+fn overdue_label(days: u32) -> String {
+    let (count, unit) = match days {
+        0..=6 => return String::from(if days == 1 { "DAY OVERDUE" } else { "DAYS OVERDUE" }),
+        7..=29 => (days / 7, "WEEK"),
+        _ => (days / 30, "MONTH"),
+    };
+    let plural = if count == 1 { "" } else { "S" };
+    format!("{} {}{} OVERDUE", count, unit, plural)
+}
+
 /// Helper trait for lowercasing without std
 trait ToAsciiLowercase {
     fn to_ascii_lowercase(&self) -> String;
@@ -836,3 +1474,89 @@ impl ToAsciiLowercase for &str {
         self.chars().map(|c| c.to_ascii_lowercase()).collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn due_today_and_tomorrow_show_a_word() {
+        assert_eq!(due_word(0), Some("TODAY"));
+        assert_eq!(due_word(1), Some("TOMORROW"));
+    }
+
+    #[test]
+    fn later_and_overdue_tasks_keep_the_countdown() {
+        assert_eq!(due_word(5), None);
+        assert_eq!(due_word(-1), None);
+    }
+
+    #[test]
+    fn card_headline_for_today_soon_and_overdue() {
+        assert_eq!(due_headline(0), (String::from("TODAY"), None));
+        assert_eq!(due_headline(1), (String::from("TOMORROW"), None));
+        assert_eq!(
+            due_headline(5),
+            (String::from("5"), Some(String::from("DAYS LEFT")))
+        );
+        assert_eq!(
+            due_headline(-3),
+            (String::from("3"), Some(String::from("DAYS OVERDUE")))
+        );
+    }
+
+    #[test]
+    fn bitmap_sets_exactly_its_bits() {
+        let mut fb = FrameBuffer::new();
+        let (white, black) = (Rgb565::new(31, 63, 31), Rgb565::new(0, 0, 0));
+        // An X: each row has its bits at col and 7 - col
+        let x_pattern = [0x81, 0x42, 0x24, 0x18, 0x18, 0x24, 0x42, 0x81];
+        Renderer::draw_bitmap(&mut fb, 10, 20, 8, 8, &x_pattern, white);
+
+        for row in 0..8 {
+            for col in 0..8 {
+                let expected = if col == row || col == 7 - row {
+                    white
+                } else {
+                    black
+                };
+                assert_eq!(
+                    fb.pixel(10 + col, 20 + row),
+                    expected,
+                    "pixel ({}, {})",
+                    col,
+                    row
+                );
+            }
+        }
+        // Nothing spills past the 8x8 box
+        assert_eq!(fb.pixel(18, 20), black);
+        assert_eq!(fb.pixel(10, 28), black);
+    }
+
+    #[test]
+    fn bitmap_with_a_short_last_row_stays_in_bounds() {
+        let mut fb = FrameBuffer::new();
+        let (white, black) = (Rgb565::new(31, 63, 31), Rgb565::new(0, 0, 0));
+        // 12 px wide (2 bytes per row), but the second row is cut off after one byte
+        Renderer::draw_bitmap(&mut fb, 0, 0, 12, 2, &[0xFF, 0xF0, 0xFF], white);
+
+        for col in 0..12 {
+            assert_eq!(fb.pixel(col, 0), white, "full row, col {}", col);
+        }
+        for col in 0..8 {
+            assert_eq!(fb.pixel(col, 1), white, "short row, col {}", col);
+        }
+        for col in 8..12 {
+            assert_eq!(fb.pixel(col, 1), black, "short row, col {}", col);
+        }
+        assert_eq!(fb.pixel(12, 0), black);
+    }
+
+    #[test]
+    fn week_cell_names_a_custom_horizon() {
+        assert_eq!(week_label(7), "WEEK");
+        assert_eq!(week_label(3), "3 DAYS");
+        assert_eq!(week_label(14), "14 DAYS");
+    }
+}
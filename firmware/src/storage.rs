@@ -1,17 +1,21 @@
 /// JSON-based storage on LittleFS/SPIFFS
 ///
 /// JSON files stored on flash partition, loaded fully into RAM.
-/// All data loaded into RAM; mutations flush to flash immediately.
+/// Mutations are written to flash before the call returns, so an acknowledged change
+/// survives a power cut; no-op updates skip the write, and `batch` shares one write.
+/// Settings are kept here for the code that needs them but saved in NVS (see `device`).
 extern crate alloc;
 
+use alloc::collections::{BTreeMap, BTreeSet};
 use alloc::format;
 use alloc::string::String;
 use alloc::vec::Vec;
+use core::str::FromStr;
 
-use chrono::NaiveDate;
+use chrono::{Datelike, NaiveDate};
 use serde::{Deserialize, Serialize};
 
-use crate::models::{CompletionRecord, RecurrenceType, Task};
+use crate::models::{calculate_next_due_anchored, CompletionQuota, CompletionRecord, RecurrenceType, Settings, Task, Urgency, UrgencyThresholds};
 use crate::views::TaskCounts;
 
 /// Task store (loaded fully into RAM)
@@ -28,17 +32,128 @@ pub struct HistoryStore {
     pub next_id: u32,
 }
 
+/// Format version written by GET /api/backup; restore refuses any other
+pub const BACKUP_VERSION: u32 = 1;
+
+/// Full backup document for GET /api/backup and POST /api/restore
+#[derive(Debug, Deserialize)]
+pub struct Backup {
+    pub version: u32,
+    pub tasks: TaskStore,
+    pub history: HistoryStore,
+}
+
+/// The same document, borrowed from the live stores for serializing
+#[derive(Serialize)]
+struct BackupRef<'a> {
+    version: u32,
+    tasks: &'a TaskStore,
+    history: &'a HistoryStore,
+}
+
+/// Why `Storage::restore` refused or failed
+#[derive(Debug, PartialEq, Eq)]
+pub enum RestoreError {
+    Invalid(String), // Nothing changed: the backup is malformed or from another version
+    SaveFailed,      // The backup is loaded, but it could not be written to flash
+}
+
+/// Fields for `Storage::create_task`; `NewTask::new` fills in the optional ones as unset
+#[derive(Debug)]
+pub struct NewTask {
+    pub name: String,
+    pub recurrence_type: RecurrenceType,
+    pub recurrence_value: u32,
+    pub next_due_date: String,
+    pub catch_up: bool,
+    pub recur_from_completion: bool,
+    pub due_message: Option<String>,
+    pub quota: Option<CompletionQuota>,
+    pub category: Option<String>,
+    pub color: Option<String>,
+    pub pinned: bool,
+}
+
+impl NewTask {
+    pub fn new(name: String, recurrence_type: RecurrenceType, recurrence_value: u32, next_due_date: String) -> Self {
+        Self {
+            name,
+            recurrence_type,
+            recurrence_value,
+            next_due_date,
+            catch_up: false,
+            recur_from_completion: false,
+            due_message: None,
+            quota: None,
+            category: None,
+            color: None,
+            pinned: false,
+        }
+    }
+}
+
+/// Partial task update (None = leave field unchanged)
+#[derive(Debug, Default)]
+pub struct TaskUpdate {
+    pub name: Option<String>,
+    pub recurrence_type: Option<RecurrenceType>,
+    pub recurrence_value: Option<u32>,
+    pub next_due_date: Option<String>,
+    pub catch_up: Option<bool>,
+    pub paused: Option<bool>,
+    pub recur_from_completion: Option<bool>,
+    pub due_message: Option<Option<String>>, // Some(None) clears the message
+    pub quota: Option<Option<CompletionQuota>>, // Some(None) removes the limit
+    pub category: Option<Option<String>>,       // Some(None) clears the tag
+    pub color: Option<Option<String>>,
+    pub pinned: Option<bool>,
+}
+
+/// Outcome of `Storage::complete_task`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompleteResult {
+    Completed,
+    NotFound,
+    QuotaReached, // Nothing recorded: the task's completion quota for this period is used up
+}
+
+/// Task list order for `Storage::get_tasks_sorted`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskSort {
+    Due,
+    Name,
+    Urgency, // Most urgent first, by due date within a level; paused tasks last
+}
+
+impl FromStr for TaskSort {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        match s {
+            "due" => Ok(Self::Due),
+            "name" => Ok(Self::Name),
+            "urgency" => Ok(Self::Urgency),
+            _ => Err(()),
+        }
+    }
+}
+
 /// Combined storage
 pub struct Storage {
     pub task_store: TaskStore,
     pub history_store: HistoryStore,
+    pub settings: Settings,
     tasks_path: String,
     history_path: String,
+    tasks_dirty: bool,
+    history_dirty: bool,
+    batch_depth: u32,              // Inside `batch`: writes wait until it finishes
+    revision: u64,                 // Bumped on every mutation; watched by GET /api/events, part of the tasks ETag
 }
 
 impl Storage {
-    /// Create new storage instance, loading from files if they exist
-    pub fn new(tasks_path: &str, history_path: &str) -> Self {
+    /// Create new storage instance, loading from files if they exist. `settings` come from NVS.
+    pub fn new(tasks_path: &str, history_path: &str, settings: Settings) -> Self {
         let task_store = Self::load_json::<TaskStore>(tasks_path).unwrap_or_default();
         let history_store = Self::load_json::<HistoryStore>(history_path).unwrap_or_default();
 
@@ -51,8 +166,25 @@ impl Storage {
         Self {
             task_store,
             history_store,
+            settings,
             tasks_path: String::from(tasks_path),
             history_path: String::from(history_path),
+            tasks_dirty: false,
+            history_dirty: false,
+            batch_depth: 0,
+            revision: 0,
+        }
+    }
+
+    /// Read settings left in a file by firmware that kept them on the storage partition
+    pub fn load_legacy_settings(path: &str) -> Option<Settings> {
+        Self::load_json::<Settings>(path)
+    }
+
+    /// Delete the legacy settings file (with its .bak/.tmp) once it has been migrated
+    pub fn remove_legacy_settings(path: &str) {
+        for file in [String::from(path), format!("{}.bak", path), format!("{}.tmp", path)] {
+            let _ = std::fs::remove_file(file);
         }
     }
 
@@ -97,18 +229,65 @@ impl Storage {
         }
     }
 
-    /// Save task store to file
-    fn save_tasks(&self) {
-        Self::safe_write_json(&self.tasks_path, &self.task_store);
+    /// Write any dirty stores to flash
+    fn flush(&mut self) {
+        if self.tasks_dirty {
+            self.save_tasks();
+        }
+        if self.history_dirty {
+            self.save_history();
+        }
+        self.tasks_dirty = false;
+        self.history_dirty = false;
+    }
+
+    /// Run several mutations (a bulk API call) with one write per store at the end
+    pub fn batch<R>(&mut self, f: impl FnOnce(&mut Self) -> R) -> R {
+        self.batch_depth += 1;
+        let result = f(self);
+        self.batch_depth -= 1;
+        if self.batch_depth == 0 {
+            self.flush();
+        }
+        result
+    }
+
+    /// Changes whenever tasks, history or settings do (not persisted: restarts at 0 on boot)
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Record a mutation and write it out (unless a `batch` is collecting writes)
+    fn mark_dirty(&mut self) {
+        self.revision += 1;
+        if self.batch_depth == 0 {
+            self.flush();
+        }
+    }
+
+    fn mark_tasks_dirty(&mut self) {
+        self.tasks_dirty = true;
+        self.mark_dirty();
+    }
+
+    fn mark_history_dirty(&mut self) {
+        self.history_dirty = true;
+        self.mark_dirty();
+    }
+
+    /// Save task store to file. Returns false if the write failed (already logged).
+    fn save_tasks(&self) -> bool {
+        Self::safe_write_json(&self.tasks_path, &self.task_store)
     }
 
-    /// Save history store to file
-    fn save_history(&self) {
-        Self::safe_write_json(&self.history_path, &self.history_store);
+    /// Save history store to file. Returns false if the write failed (already logged).
+    fn save_history(&self) -> bool {
+        Self::safe_write_json(&self.history_path, &self.history_store)
     }
 
-    /// Atomic write: serialize → write .tmp → verify → backup old → rename .tmp → primary
-    fn safe_write_json<T: Serialize + for<'de> Deserialize<'de>>(path: &str, data: &T) {
+    /// Atomic write: serialize → write .tmp → verify → backup old → rename .tmp → primary.
+    /// Returns true once the new file is in place.
+    fn safe_write_json<T: Serialize + for<'de> Deserialize<'de>>(path: &str, data: &T) -> bool {
         let tmp_path = format!("{}.tmp", path);
         let bak_path = format!("{}.bak", path);
 
@@ -117,14 +296,14 @@ impl Storage {
             Ok(j) => j,
             Err(e) => {
                 log::error!("Failed to serialize data for {}: {}", path, e);
-                return;
+                return false;
             }
         };
 
         // Write to .tmp
         if let Err(e) = std::fs::write(&tmp_path, &json) {
             log::error!("Failed to write {}: {}", tmp_path, e);
-            return;
+            return false;
         }
 
         // Verify .tmp by reading it back and parsing
@@ -133,12 +312,12 @@ impl Storage {
                 if serde_json::from_str::<T>(&contents).is_err() {
                     log::error!("Verification failed for {}, aborting save", tmp_path);
                     let _ = std::fs::remove_file(&tmp_path);
-                    return;
+                    return false;
                 }
             }
             Err(e) => {
                 log::error!("Failed to read back {}: {}, aborting save", tmp_path, e);
-                return;
+                return false;
             }
         }
 
@@ -152,10 +331,11 @@ impl Storage {
         // Atomic swap: rename .tmp → primary
         if let Err(e) = std::fs::rename(&tmp_path, path) {
             log::error!("Failed to rename {} → {}: {}", tmp_path, path, e);
-            return;
+            return false;
         }
 
         log::info!("Saved {}", path);
+        true
     }
 
     // ========== TASK CRUD ==========
@@ -171,66 +351,83 @@ impl Storage {
         tasks
     }
 
+    /// Get all tasks in the given order
+    pub fn get_tasks_sorted(&self, sort: TaskSort, today: NaiveDate, thresholds: &UrgencyThresholds) -> Vec<Task> {
+        match sort {
+            TaskSort::Due => self.get_all_tasks(true),
+            TaskSort::Name => self.get_all_tasks(false),
+            TaskSort::Urgency => {
+                // Stable sort over the due-date order keeps due order within each group
+                let mut tasks = self.get_all_tasks(true);
+                tasks.sort_by_key(|t| (t.paused, t.urgency(today, thresholds)));
+                tasks
+            }
+        }
+    }
+
     /// Get a single task by ID
     pub fn get_task(&self, task_id: u32) -> Option<&Task> {
         self.task_store.tasks.iter().find(|t| t.id == task_id)
     }
 
     /// Create a new task
-    pub fn create_task(
-        &mut self,
-        name: String,
-        recurrence_type: RecurrenceType,
-        recurrence_value: u32,
-        next_due_date: String,
-        now_iso: &str,
-    ) -> &Task {
+    pub fn create_task(&mut self, new: NewTask, now_iso: &str) -> &Task {
         let id = self.task_store.next_id;
         self.task_store.next_id += 1;
 
         let task = Task {
             id,
-            name,
-            recurrence_type,
-            recurrence_value,
-            next_due_date,
+            name: new.name,
+            recurrence_type: new.recurrence_type,
+            recurrence_value: new.recurrence_value,
+            next_due_date: new.next_due_date,
             created_at: String::from(now_iso),
             updated_at: String::from(now_iso),
+            catch_up: new.catch_up,
+            paused: false,
+            recur_from_completion: new.recur_from_completion,
+            due_message: new.due_message,
+            quota: new.quota,
+            category: new.category,
+            color: new.color,
+            pinned: new.pinned,
+            anchor_day: None,
         };
 
         self.task_store.tasks.push(task);
-        self.save_tasks();
+        self.mark_tasks_dirty();
 
         self.task_store.tasks.last().unwrap()
     }
 
     /// Update an existing task
-    pub fn update_task(
-        &mut self,
-        task_id: u32,
-        name: Option<String>,
-        recurrence_type: Option<RecurrenceType>,
-        recurrence_value: Option<u32>,
-        next_due_date: Option<String>,
-        now_iso: &str,
-    ) -> Option<&Task> {
+    pub fn update_task(&mut self, task_id: u32, update: TaskUpdate, now_iso: &str) -> Option<&Task> {
         let task = self.task_store.tasks.iter_mut().find(|t| t.id == task_id)?;
 
-        if let Some(n) = name {
-            task.name = n;
-        }
-        if let Some(rt) = recurrence_type {
-            task.recurrence_type = rt;
-        }
-        if let Some(rv) = recurrence_value {
-            task.recurrence_value = rv;
-        }
-        if let Some(ndd) = next_due_date {
-            task.next_due_date = ndd;
+        let mut changed = false;
+        changed |= set_if_changed(&mut task.name, update.name);
+        changed |= set_if_changed(&mut task.recurrence_type, update.recurrence_type);
+        changed |= set_if_changed(&mut task.recurrence_value, update.recurrence_value);
+        let due_changed = set_if_changed(&mut task.next_due_date, update.next_due_date);
+        changed |= due_changed;
+        changed |= set_if_changed(&mut task.catch_up, update.catch_up);
+        changed |= set_if_changed(&mut task.paused, update.paused);
+        changed |= set_if_changed(&mut task.recur_from_completion, update.recur_from_completion);
+        changed |= set_if_changed(&mut task.pinned, update.pinned);
+        changed |= set_if_changed(&mut task.due_message, update.due_message);
+        changed |= set_if_changed(&mut task.quota, update.quota);
+        changed |= set_if_changed(&mut task.category, update.category);
+        changed |= set_if_changed(&mut task.color, update.color);
+        if due_changed {
+            // A due date picked by hand starts the schedule over from its day
+            task.anchor_day = None;
         }
-        task.updated_at = String::from(now_iso);
 
-        self.save_tasks();
+        // Identical values (e.g. a PUT that re-sends the same form) don't touch flash
+        if changed {
+            task.updated_at = String::from(now_iso);
+            self.mark_tasks_dirty();
+        }
 
         self.task_store.tasks.iter().find(|t| t.id == task_id)
     }
@@ -244,21 +441,121 @@ impl Storage {
         if deleted {
             // Also delete history
             self.history_store.records.retain(|r| r.task_id != task_id);
-            self.save_tasks();
-            self.save_history();
+            self.mark_tasks_dirty();
+            self.mark_history_dirty();
         }
 
         deleted
     }
 
+    /// All tasks and history as a backup document (same shape as the TaskStore/HistoryStore
+    /// files, plus `version`), for `restore` to read back
+    pub fn backup_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(&BackupRef {
+            version: BACKUP_VERSION,
+            tasks: &self.task_store,
+            history: &self.history_store,
+        })
+    }
+
+    /// Replace all tasks and history with a backup and save both right away. The backup's
+    /// `next_id` counters are kept, raised past any ID in use so new records can't collide.
+    /// A backup from another format version is refused before anything changes.
+    pub fn restore(&mut self, backup: Backup) -> Result<(), RestoreError> {
+        let Backup { version, mut tasks, mut history } = backup;
+        if version != BACKUP_VERSION {
+            return Err(RestoreError::Invalid(format!("Unsupported backup version {}", version)));
+        }
+
+        let mut task_ids = BTreeSet::new();
+        for task in &tasks.tasks {
+            if task.name.trim().is_empty() {
+                return Err(RestoreError::Invalid(format!("Task {} has no name", task.id)));
+            }
+            if task.due_date().is_none() {
+                return Err(RestoreError::Invalid(format!("Task {} has an invalid next_due_date", task.id)));
+            }
+            if !task_ids.insert(task.id) {
+                return Err(RestoreError::Invalid(format!("Duplicate task id {}", task.id)));
+            }
+        }
+        let mut record_ids = BTreeSet::new();
+        for record in &history.records {
+            if !task_ids.contains(&record.task_id) {
+                return Err(RestoreError::Invalid(format!("History record {} references unknown task {}", record.id, record.task_id)));
+            }
+            if record.completed_date().is_none() {
+                return Err(RestoreError::Invalid(format!("History record {} has an invalid completed_at", record.id)));
+            }
+            if !record_ids.insert(record.id) {
+                return Err(RestoreError::Invalid(format!("Duplicate history id {}", record.id)));
+            }
+        }
+
+        if let Some(max_id) = task_ids.last() {
+            tasks.next_id = tasks.next_id.max(max_id + 1);
+        }
+        if let Some(max_id) = record_ids.last() {
+            history.next_id = history.next_id.max(max_id + 1);
+        }
+
+        log::info!("Restoring backup: {} tasks, {} history records", tasks.tasks.len(), history.records.len());
+        self.task_store = tasks;
+        self.history_store = history;
+        self.revision += 1;
+        // Both writes are attempted; a store that failed stays dirty for the next flush
+        let tasks_saved = self.save_tasks();
+        let history_saved = self.save_history();
+        self.tasks_dirty = !tasks_saved;
+        self.history_dirty = !history_saved;
+        if tasks_saved && history_saved {
+            Ok(())
+        } else {
+            Err(RestoreError::SaveFailed)
+        }
+    }
+
+    /// Give a task a clean slate: drop its completion history and restart its schedule
+    /// from `new_due_date`. The task keeps its ID and settings. Returns false if not found.
+    pub fn reset_task(&mut self, task_id: u32, new_due_date: NaiveDate, now_iso: &str) -> bool {
+        let Some(task) = self.task_store.tasks.iter_mut().find(|t| t.id == task_id) else {
+            return false;
+        };
+        task.next_due_date = new_due_date.format("%Y-%m-%d").to_string();
+        task.anchor_day = None;
+        task.updated_at = String::from(now_iso);
+        self.mark_tasks_dirty();
+
+        let before = self.history_store.records.len();
+        self.history_store.records.retain(|r| r.task_id != task_id);
+        if self.history_store.records.len() < before {
+            self.mark_history_dirty();
+        }
+
+        true
+    }
+
     /// Mark a task as completed and update next due date
-    pub fn complete_task(&mut self, task_id: u32, now_iso: &str, today: NaiveDate) -> bool {
+    pub fn complete_task(
+        &mut self,
+        task_id: u32,
+        now_iso: &str,
+        today: NaiveDate,
+        note: Option<String>,
+    ) -> CompleteResult {
         // Find the task
         let task = match self.task_store.tasks.iter().find(|t| t.id == task_id) {
             Some(t) => t.clone(),
-            None => return false,
+            None => return CompleteResult::NotFound,
         };
 
+        if let Some(quota) = task.quota {
+            let since = quota.period_start(today, self.settings.week_start);
+            if self.completions_since(task_id, since) >= quota.max_per_period as usize {
+                return CompleteResult::QuotaReached;
+            }
+        }
+
         // Calculate days since last completion
         let last_completion = self.get_last_completion(task_id);
         let days_since_last = last_completion.and_then(|lc| {
@@ -267,7 +564,8 @@ impl Storage {
                 "%Y-%m-%d",
             )
             .ok()?;
-            Some((today - lc_date).num_days() as i32)
+            // Clamp: a time re-sync to an earlier date must not store negative intervals
+            Some(((today - lc_date).num_days() as i32).max(0))
         });
 
         // Record completion
@@ -278,15 +576,121 @@ impl Storage {
             task_id,
             completed_at: String::from(now_iso),
             days_since_last,
+            note,
+            recurrence_label_at_completion: Some(task.recurrence_label()),
+            previous_due_date: Some(task.next_due_date.clone()),
+            previous_anchor_day: task.anchor_day,
         });
-        self.save_history();
+        self.mark_history_dirty();
+
+        // Calculate next due date from the PREVIOUS due date (fixed schedule), keeping its
+        // day of month through short months, or from today for tasks that recur from completion
+        let base = if task.recur_from_completion {
+            Some((today, today.day()))
+        } else {
+            task.due_date().zip(task.schedule_day())
+        };
+        if let Some((base_date, anchor_day)) = base {
+            let step = |from| calculate_next_due_anchored(from, task.recurrence_type, task.recurrence_value, anchor_day);
+            let mut next_due = step(base_date);
+
+            // Catch-up: skip occurrences missed while overdue so the task lands in the future
+            if task.catch_up && task.recurrence_value > 0 {
+                while next_due <= today {
+                    next_due = step(next_due);
+                }
+            }
+
+            // Remember the anchor only while a short month has clamped the date away from it
+            let anchored = !task.recur_from_completion
+                && matches!(task.recurrence_type, RecurrenceType::Monthly | RecurrenceType::Yearly);
+            let anchor_day = (anchored && next_due.day() != anchor_day).then_some(anchor_day);
+            self.move_due_date(task_id, next_due.format("%Y-%m-%d").to_string(), anchor_day, now_iso);
+        }
+
+        CompleteResult::Completed
+    }
+
+    /// Set the due date a completion (or its undo) moved the task to, with the anchor day
+    /// its schedule keeps. Unlike `update_task` this doesn't start the schedule over.
+    fn move_due_date(&mut self, task_id: u32, next_due_date: String, anchor_day: Option<u32>, now_iso: &str) -> bool {
+        let Some(task) = self.task_store.tasks.iter_mut().find(|t| t.id == task_id) else {
+            return false;
+        };
+        if task.next_due_date != next_due_date || task.anchor_day != anchor_day {
+            task.next_due_date = next_due_date;
+            task.anchor_day = anchor_day;
+            task.updated_at = String::from(now_iso);
+            self.mark_tasks_dirty();
+        }
+        true
+    }
 
-        // Calculate next due date from the PREVIOUS due date (fixed schedule)
-        if let Some(due_date) = task.due_date() {
-            let next_due = calculate_next_due(due_date, task.recurrence_type, task.recurrence_value);
-            self.update_task(task_id, None, None, None, Some(next_due.format("%Y-%m-%d").to_string()), now_iso);
+    /// Take back a task's most recent completion: drop its history record and restore the
+    /// due date it had before. Returns false if there is nothing to undo (no completions,
+    /// or the latest one was recorded before pre-completion due dates were kept).
+    pub fn undo_last_completion(&mut self, task_id: u32, now_iso: &str) -> bool {
+        let Some(last) = self.get_last_completion(task_id) else {
+            return false;
+        };
+        let record_id = last.id;
+        let Some(previous_due) = last.previous_due_date.clone() else {
+            return false;
+        };
+
+        // Back to the due date and anchor day from before the completion
+        let anchor_day = last.previous_anchor_day;
+        if !self.move_due_date(task_id, previous_due, anchor_day, now_iso) {
+            return false;
         }
 
+        self.history_store.records.retain(|r| r.id != record_id);
+        self.mark_history_dirty();
+        true
+    }
+
+    /// Count a task's completions dated on or after `since`
+    pub fn completions_since(&self, task_id: u32, since: NaiveDate) -> usize {
+        self.history_store
+            .records
+            .iter()
+            .filter(|r| r.task_id == task_id)
+            .filter(|r| r.completed_date().is_some_and(|d| d >= since))
+            .count()
+    }
+
+    /// Push every overdue (unpaused) task to tomorrow without recording a completion.
+    /// Returns how many tasks were moved.
+    pub fn snooze_all_overdue(&mut self, today: NaiveDate, now_iso: &str) -> usize {
+        let tomorrow = (today + chrono::Duration::days(1)).format("%Y-%m-%d").to_string();
+        let thresholds = self.settings.urgency;
+        let mut snoozed = 0;
+
+        for task in self.task_store.tasks.iter_mut() {
+            if !task.paused && task.urgency(today, &thresholds) == Urgency::Overdue {
+                task.next_due_date = tomorrow.clone();
+                task.anchor_day = None;
+                task.updated_at = String::from(now_iso);
+                snoozed += 1;
+            }
+        }
+
+        if snoozed > 0 {
+            self.mark_tasks_dirty();
+        }
+        snoozed
+    }
+
+    // ========== SETTINGS ==========
+
+    /// Replace the in-memory settings. Returns true if anything changed; saving them
+    /// (to NVS, `device::save_settings`) is up to the caller.
+    pub fn set_settings(&mut self, settings: Settings) -> bool {
+        if self.settings == settings {
+            return false;
+        }
+        self.settings = settings;
+        self.revision += 1;
         true
     }
 
@@ -306,6 +710,20 @@ impl Storage {
         records
     }
 
+    /// All completions on or after `since` (every one if None), newest first, capped at `limit`
+    pub fn get_history_since(&self, since: Option<NaiveDate>, limit: usize) -> Vec<CompletionRecord> {
+        let mut records: Vec<CompletionRecord> = self
+            .history_store
+            .records
+            .iter()
+            .filter(|r| since.is_none_or(|d| r.completed_date().is_some_and(|done| done >= d)))
+            .cloned()
+            .collect();
+        records.sort_by(|a, b| b.completed_at.cmp(&a.completed_at));
+        records.truncate(limit);
+        records
+    }
+
     /// Get most recent completion for a task
     pub fn get_last_completion(&self, task_id: u32) -> Option<&CompletionRecord> {
         self.history_store
@@ -315,6 +733,54 @@ impl Storage {
             .max_by(|a, b| a.completed_at.cmp(&b.completed_at))
     }
 
+    /// Consecutive on-time completions (done on or before the due date), newest first.
+    /// A task that is overdue right now has no streak. Lateness uses the overdue grace days,
+    /// like the urgency levels, so a completion within the grace still counts as on time.
+    /// Records without a stored due date (made before undo support) end the count, since
+    /// their timeliness is unknown.
+    pub fn current_streak(&self, task_id: u32, today: NaiveDate) -> u32 {
+        let thresholds = self.settings.urgency;
+        let overdue = self
+            .get_task(task_id)
+            .is_some_and(|t| !t.paused && t.urgency(today, &thresholds) == Urgency::Overdue);
+        if overdue {
+            return 0;
+        }
+
+        let mut records: Vec<&CompletionRecord> = self.history_store.records.iter().filter(|r| r.task_id == task_id).collect();
+        records.sort_by(|a, b| b.completed_at.cmp(&a.completed_at));
+
+        let mut streak = 0;
+        for record in records {
+            let on_time = match (record.completed_date(), record.previous_due_date.as_deref()) {
+                (Some(done), Some(due)) => NaiveDate::parse_from_str(due, "%Y-%m-%d")
+                    .is_ok_and(|due| Urgency::from_days((due - done).num_days() as i32, &thresholds) != Urgency::Overdue),
+                _ => false,
+            };
+            if !on_time {
+                break;
+            }
+            streak += 1;
+        }
+        streak
+    }
+
+    /// Mean days between completions of a task, from the gaps recorded with each one.
+    /// None until there are at least two completions (the first has no gap).
+    pub fn average_interval(&self, task_id: u32) -> Option<f32> {
+        let gaps: Vec<i32> = self
+            .history_store
+            .records
+            .iter()
+            .filter(|r| r.task_id == task_id)
+            .filter_map(|r| r.days_since_last)
+            .collect();
+        if gaps.is_empty() {
+            return None;
+        }
+        Some(gaps.iter().sum::<i32>() as f32 / gaps.len() as f32)
+    }
+
     // ========== AGGREGATIONS ==========
 
     /// Get task counts by urgency category for dashboard
@@ -323,53 +789,490 @@ impl Storage {
         let mut counts = TaskCounts {
             overdue: 0,
             today: 0,
+            tomorrow: 0,
             week: 0,
             total: tasks.len() as u32,
         };
 
-        for task in &tasks {
-            let days = task.days_until_due(today);
-            if days < 0 {
-                counts.overdue += 1;
-                counts.week += 1;
-            } else if days == 0 {
-                counts.today += 1;
-                counts.week += 1;
-            } else if days <= 7 {
-                counts.week += 1;
+        for task in tasks.iter().filter(|t| !t.paused) {
+            match task.urgency(today, &self.settings.urgency) {
+                Urgency::Overdue => {
+                    counts.overdue += 1;
+                    counts.week += 1;
+                }
+                Urgency::Today => {
+                    counts.today += 1;
+                    counts.week += 1;
+                }
+                Urgency::Tomorrow => {
+                    counts.tomorrow += 1;
+                    counts.week += 1;
+                }
+                Urgency::Week => counts.week += 1,
+                Urgency::Upcoming => {}
             }
         }
 
         counts
     }
 
+    /// Unpaused tasks due within the next `days` days (today included), grouped by due date.
+    /// Overdue tasks are left out; the map iterates in date order.
+    pub fn upcoming(&self, today: NaiveDate, days: i32) -> BTreeMap<NaiveDate, Vec<Task>> {
+        let mut grouped: BTreeMap<NaiveDate, Vec<Task>> = BTreeMap::new();
+        for task in self.get_all_tasks(false).into_iter().filter(|t| !t.paused) {
+            let Some(due) = task.due_date() else { continue };
+            let days_until = (due - today).num_days();
+            if (0..=days as i64).contains(&days_until) {
+                grouped.entry(due).or_default().push(task);
+            }
+        }
+        grouped
+    }
+
     /// Get tasks filtered by urgency category
     pub fn get_tasks_by_urgency(&self, urgency: &str, today: NaiveDate) -> Vec<Task> {
         let tasks = self.get_all_tasks(true);
+        let thresholds = &self.settings.urgency;
+        let level = |t: &Task| t.urgency(today, thresholds);
         match urgency {
             "overdue" => tasks
                 .into_iter()
-                .filter(|t| t.days_until_due(today) < 0)
+                .filter(|t| !t.paused && level(t) == Urgency::Overdue)
                 .collect(),
             "today" => tasks
                 .into_iter()
-                .filter(|t| t.days_until_due(today) == 0)
+                .filter(|t| !t.paused && level(t) == Urgency::Today)
+                .collect(),
+            "tomorrow" => tasks
+                .into_iter()
+                .filter(|t| !t.paused && level(t) == Urgency::Tomorrow)
                 .collect(),
             "week" => tasks
                 .into_iter()
-                .filter(|t| t.days_until_due(today) <= 7)
+                .filter(|t| !t.paused && level(t) != Urgency::Upcoming)
                 .collect(),
             _ => tasks, // "total" or any other value returns all
         }
     }
 }
 
-/// Calculate next due date based on recurrence
-fn calculate_next_due(from_date: NaiveDate, recurrence_type: RecurrenceType, value: u32) -> NaiveDate {
-    match recurrence_type {
-        RecurrenceType::Daily => from_date + chrono::Duration::days(value as i64),
-        RecurrenceType::Weekly => from_date + chrono::Duration::weeks(value as i64),
-        RecurrenceType::Monthly => from_date + chrono::Duration::days(value as i64 * 30),
-        RecurrenceType::Yearly => from_date + chrono::Duration::days(value as i64 * 365),
+/// Overwrite `field` with `value` if given and different. Returns true if it changed.
+fn set_if_changed<T: PartialEq>(field: &mut T, value: Option<T>) -> bool {
+    match value {
+        Some(v) if *field != v => {
+            *field = v;
+            true
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: &str = "2026-03-01T09:00:00";
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    /// Storage backed by a fresh directory under the system temp dir
+    fn temp_storage(name: &str) -> Storage {
+        let dir = std::env::temp_dir().join(format!("days-tracker-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = |file: &str| dir.join(file).to_string_lossy().into_owned();
+        Storage::new(&path("tasks.json"), &path("history.json"), Settings::default())
+    }
+
+    fn add_task(s: &mut Storage, recurrence_type: RecurrenceType, value: u32, due: &str, catch_up: bool) -> u32 {
+        let new = NewTask {
+            catch_up,
+            ..NewTask::new(String::from("Task"), recurrence_type, value, String::from(due))
+        };
+        s.create_task(new, NOW).id
+    }
+
+    #[test]
+    fn catch_up_skips_missed_occurrences() {
+        let mut s = temp_storage("catch-up");
+        let today = date("2026-03-01");
+        let id = add_task(&mut s, RecurrenceType::Daily, 1, "2026-01-30", true);
+
+        assert_eq!(s.complete_task(id, NOW, today, None), CompleteResult::Completed);
+        assert_eq!(s.get_task(id).unwrap().next_due_date, "2026-03-02");
+    }
+
+    #[test]
+    fn without_catch_up_advances_one_interval() {
+        let mut s = temp_storage("no-catch-up");
+        let today = date("2026-03-01");
+        let id = add_task(&mut s, RecurrenceType::Daily, 1, "2026-01-30", false);
+
+        s.complete_task(id, NOW, today, None);
+        assert_eq!(s.get_task(id).unwrap().next_due_date, "2026-01-31");
+    }
+
+    #[test]
+    fn paused_tasks_are_left_out_of_counts_and_filters() {
+        let mut s = temp_storage("paused");
+        let today = date("2026-03-01");
+        let active = add_task(&mut s, RecurrenceType::Daily, 1, "2026-02-20", false);
+        let paused = add_task(&mut s, RecurrenceType::Daily, 1, "2026-02-20", false);
+        let update = TaskUpdate {
+            paused: Some(true),
+            ..Default::default()
+        };
+        s.update_task(paused, update, NOW);
+
+        let counts = s.get_task_counts(today);
+        assert_eq!(counts.overdue, 1);
+        assert_eq!(counts.week, 1);
+        assert_eq!(counts.total, 2); // Still a task, just not due
+
+        let overdue: Vec<u32> = s.get_tasks_by_urgency("overdue", today).iter().map(|t| t.id).collect();
+        assert_eq!(overdue, [active]);
+        let week: Vec<u32> = s.get_tasks_by_urgency("week", today).iter().map(|t| t.id).collect();
+        assert_eq!(week, [active]);
+        assert_eq!(s.get_tasks_by_urgency("total", today).len(), 2);
+    }
+
+    #[test]
+    fn clock_going_backwards_never_stores_a_negative_interval() {
+        let mut s = temp_storage("clock-back");
+        let id = add_task(&mut s, RecurrenceType::Daily, 1, "2026-03-05", false);
+
+        s.complete_task(id, "2026-03-05T09:00:00", date("2026-03-05"), None);
+        // Time re-synced to four days earlier
+        s.complete_task(id, "2026-03-01T09:00:00", date("2026-03-01"), None);
+
+        let history = s.get_task_history(id);
+        let resynced = history.iter().find(|r| r.completed_at.starts_with("2026-03-01")).unwrap();
+        assert_eq!(resynced.days_since_last, Some(0));
+    }
+
+    #[test]
+    fn changes_are_on_flash_before_the_call_returns() {
+        let mut s = temp_storage("persist");
+        let id = add_task(&mut s, RecurrenceType::Daily, 1, "2026-03-01", false);
+        s.complete_task(id, NOW, date("2026-03-01"), None);
+
+        // A fresh load (as after a power cut) sees both the task and its completion
+        let reloaded = Storage::new(&s.tasks_path, &s.history_path, Settings::default());
+        assert_eq!(reloaded.get_task(id).unwrap().next_due_date, "2026-03-02");
+        assert_eq!(reloaded.get_task_history(id).len(), 1);
+    }
+
+    #[test]
+    fn grace_days_keep_a_late_task_out_of_overdue() {
+        let mut s = temp_storage("grace");
+        let today = date("2026-03-01");
+        let mut settings = Settings::default();
+        settings.urgency.overdue_grace_days = 2;
+        s.set_settings(settings);
+        let late = add_task(&mut s, RecurrenceType::Daily, 1, "2026-02-27", false);
+
+        let counts = s.get_task_counts(today);
+        assert_eq!((counts.overdue, counts.today), (0, 1));
+        assert_eq!(s.snooze_all_overdue(today, NOW), 0);
+        assert_eq!(s.get_task(late).unwrap().next_due_date, "2026-02-27");
+    }
+
+    #[test]
+    fn create_task_keeps_the_optional_fields() {
+        let mut s = temp_storage("create-options");
+        let new = NewTask {
+            recur_from_completion: true,
+            due_message: Some(String::from("Water the ferns")),
+            pinned: true,
+            ..NewTask::new(String::from("Plants"), RecurrenceType::Weekly, 1, String::from("2026-03-01"))
+        };
+        let task = s.create_task(new, NOW);
+
+        assert!(task.recur_from_completion);
+        assert!(task.pinned);
+        assert_eq!(task.due_message.as_deref(), Some("Water the ferns"));
+        assert_eq!(task.created_at, task.updated_at);
+    }
+
+    #[test]
+    fn quota_blocks_completions_until_the_next_period() {
+        let mut s = temp_storage("quota");
+        let new = NewTask {
+            quota: Some(CompletionQuota {
+                max_per_period: 1,
+                period: RecurrenceType::Weekly,
+            }),
+            ..NewTask::new(
+                String::from("Vacuum"),
+                RecurrenceType::Daily,
+                1,
+                String::from("2026-03-04"),
+            )
+        };
+        let id = s.create_task(new, NOW).id;
+
+        // Wed, then Fri of the same Monday-based week, then the following Monday
+        assert_eq!(
+            s.complete_task(id, "2026-03-04T09:00:00", date("2026-03-04"), None),
+            CompleteResult::Completed
+        );
+        assert_eq!(
+            s.complete_task(id, "2026-03-06T09:00:00", date("2026-03-06"), None),
+            CompleteResult::QuotaReached
+        );
+        assert_eq!(s.get_task_history(id).len(), 1);
+        assert_eq!(s.get_task(id).unwrap().next_due_date, "2026-03-05");
+        assert_eq!(
+            s.complete_task(id, "2026-03-09T09:00:00", date("2026-03-09"), None),
+            CompleteResult::Completed
+        );
+    }
+
+    #[test]
+    fn reset_clears_only_that_tasks_history() {
+        let mut s = temp_storage("reset");
+        let reset = add_task(&mut s, RecurrenceType::Daily, 1, "2026-03-01", false);
+        let other = add_task(&mut s, RecurrenceType::Daily, 1, "2026-03-01", false);
+        s.complete_task(reset, NOW, date("2026-03-01"), None);
+        s.complete_task(other, NOW, date("2026-03-01"), None);
+
+        assert!(s.reset_task(reset, date("2026-03-10"), "2026-03-02T09:00:00"));
+        let task = s.get_task(reset).unwrap();
+        assert_eq!(task.id, reset);
+        assert_eq!(task.next_due_date, "2026-03-10");
+        assert_eq!(task.updated_at, "2026-03-02T09:00:00");
+        assert!(s.get_task_history(reset).is_empty());
+        assert_eq!(s.get_task_history(other).len(), 1);
+    }
+
+    #[test]
+    fn reset_of_unknown_task_is_refused() {
+        let mut s = temp_storage("reset_unknown");
+        assert!(!s.reset_task(42, date("2026-03-10"), NOW));
+    }
+
+    #[test]
+    fn streak_counts_on_time_completions_until_a_late_one() {
+        let mut s = temp_storage("streak");
+        let id = add_task(&mut s, RecurrenceType::Daily, 1, "2026-03-01", true);
+        s.complete_task(id, "2026-03-01T09:00:00", date("2026-03-01"), None);
+        s.complete_task(id, "2026-03-02T09:00:00", date("2026-03-02"), None);
+        assert_eq!(s.current_streak(id, date("2026-03-03")), 2);
+
+        // Due 03-03 but done 03-05: the late completion breaks the streak
+        s.complete_task(id, "2026-03-05T09:00:00", date("2026-03-05"), None);
+        assert_eq!(s.get_task(id).unwrap().next_due_date, "2026-03-06");
+        assert_eq!(s.current_streak(id, date("2026-03-05")), 0);
+    }
+
+    #[test]
+    fn overdue_task_has_no_streak() {
+        let mut s = temp_storage("streak_overdue");
+        let id = add_task(&mut s, RecurrenceType::Daily, 1, "2026-03-01", false);
+        s.complete_task(id, "2026-03-01T09:00:00", date("2026-03-01"), None);
+        assert_eq!(s.current_streak(id, date("2026-03-02")), 1);
+        assert_eq!(s.current_streak(id, date("2026-03-03")), 0);
+    }
+
+    #[test]
+    fn monthly_completions_stay_on_the_31st() {
+        let mut s = temp_storage("anchor");
+        let id = add_task(&mut s, RecurrenceType::Monthly, 1, "2026-01-31", false);
+        let mut dues = Vec::new();
+        for _ in 0..3 {
+            s.complete_task(id, NOW, date("2026-01-31"), None);
+            dues.push(s.get_task(id).unwrap().next_due_date.clone());
+        }
+        assert_eq!(dues, ["2026-02-28", "2026-03-31", "2026-04-30"]);
+
+        // Undo keeps the schedule's day
+        assert!(s.undo_last_completion(id, NOW));
+        assert!(s.undo_last_completion(id, NOW));
+        assert_eq!(s.get_task(id).unwrap().next_due_date, "2026-02-28");
+        s.complete_task(id, NOW, date("2026-01-31"), None);
+        assert_eq!(s.get_task(id).unwrap().next_due_date, "2026-03-31");
+    }
+
+    #[test]
+    fn picking_a_due_date_moves_the_anchor() {
+        let mut s = temp_storage("anchor_moved");
+        let id = add_task(&mut s, RecurrenceType::Monthly, 1, "2026-01-31", false);
+        s.complete_task(id, NOW, date("2026-01-31"), None);
+        let update = TaskUpdate {
+            next_due_date: Some(String::from("2026-02-27")),
+            ..Default::default()
+        };
+        s.update_task(id, update, NOW);
+        s.complete_task(id, NOW, date("2026-02-27"), None);
+        assert_eq!(s.get_task(id).unwrap().next_due_date, "2026-03-27");
+    }
+
+    #[test]
+    fn backup_round_trips_through_restore() {
+        let mut s = temp_storage("backup");
+        let id = add_task(&mut s, RecurrenceType::Weekly, 1, "2026-03-01", false);
+        s.complete_task(id, NOW, date("2026-03-01"), None);
+        let json = s.backup_json().unwrap();
+
+        let mut other = temp_storage("backup_restored");
+        let backup: Backup = serde_json::from_str(&json).unwrap();
+        assert_eq!(other.restore(backup), Ok(()));
+        assert_eq!(other.get_task(id).unwrap().next_due_date, "2026-03-08");
+        assert_eq!(other.get_task_history(id).len(), 1);
+    }
+
+    #[test]
+    fn restore_refuses_other_backup_versions() {
+        let mut s = temp_storage("backup_version");
+        let id = add_task(&mut s, RecurrenceType::Daily, 1, "2026-03-01", false);
+        let json = s
+            .backup_json()
+            .unwrap()
+            .replacen("\"version\":1", "\"version\":2", 1);
+
+        let backup: Backup = serde_json::from_str(&json).unwrap();
+        assert!(matches!(s.restore(backup), Err(RestoreError::Invalid(_))));
+        assert!(s.get_task(id).is_some());
+
+        // Documents without a version aren't backups at all
+        let unversioned =
+            r#"{"tasks":{"tasks":[],"next_id":1},"history":{"records":[],"next_id":1}}"#;
+        assert!(serde_json::from_str::<Backup>(unversioned).is_err());
+    }
+
+    #[test]
+    fn restore_reports_a_failed_save() {
+        let missing =
+            std::env::temp_dir().join(format!("days-tracker-missing-{}", std::process::id()));
+        let path = |file: &str| missing.join(file).to_string_lossy().into_owned();
+        let mut s = Storage::new(
+            &path("tasks.json"),
+            &path("history.json"),
+            Settings::default(),
+        );
+
+        let backup: Backup = serde_json::from_str(&s.backup_json().unwrap()).unwrap();
+        assert_eq!(s.restore(backup), Err(RestoreError::SaveFailed));
+    }
+
+    #[test]
+    fn week_count_follows_the_configured_horizon() {
+        let mut s = temp_storage("horizon");
+        for due in ["2026-03-03", "2026-03-06", "2026-03-11", "2026-03-20"] {
+            add_task(&mut s, RecurrenceType::Daily, 1, due, false);
+        }
+        let today = date("2026-03-01");
+
+        let mut settings = s.settings.clone();
+        settings.urgency.week_days = 3;
+        s.set_settings(settings.clone());
+        assert_eq!(s.get_task_counts(today).week, 1);
+        assert_eq!(s.get_tasks_by_urgency("week", today).len(), 1);
+
+        settings.urgency.week_days = 14;
+        s.set_settings(settings);
+        assert_eq!(s.get_task_counts(today).week, 3);
+        assert_eq!(s.get_tasks_by_urgency("week", today).len(), 3);
+    }
+
+    #[test]
+    fn snoozing_an_overdue_task_drops_its_anchor() {
+        let mut s = temp_storage("anchor_snoozed");
+        let id = add_task(&mut s, RecurrenceType::Monthly, 1, "2026-01-31", false);
+        s.complete_task(id, NOW, date("2026-01-31"), None);
+        assert_eq!(s.get_task(id).unwrap().anchor_day, Some(31));
+
+        assert_eq!(s.snooze_all_overdue(date("2026-03-05"), NOW), 1);
+        let task = s.get_task(id).unwrap();
+        assert_eq!(
+            (task.next_due_date.as_str(), task.anchor_day),
+            ("2026-03-06", None)
+        );
+        s.complete_task(id, NOW, date("2026-03-06"), None);
+        assert_eq!(s.get_task(id).unwrap().next_due_date, "2026-04-06");
+    }
+
+    #[test]
+    fn undo_restores_the_anchor_from_before_the_completion() {
+        let mut s = temp_storage("anchor_undo");
+        let id = add_task(&mut s, RecurrenceType::Monthly, 1, "2026-01-31", false);
+        let due_and_anchor = |s: &Storage| {
+            let task = s.get_task(id).unwrap();
+            (task.next_due_date.clone(), task.anchor_day)
+        };
+
+        s.complete_task(id, NOW, date("2026-01-31"), None);
+        assert_eq!(due_and_anchor(&s), (String::from("2026-02-28"), Some(31)));
+        assert!(s.undo_last_completion(id, NOW));
+        assert_eq!(due_and_anchor(&s), (String::from("2026-01-31"), None));
+
+        // Undoing the step out of February brings back the clamped date's anchor
+        s.complete_task(id, NOW, date("2026-01-31"), None);
+        s.complete_task(id, NOW, date("2026-02-28"), None);
+        assert_eq!(due_and_anchor(&s), (String::from("2026-03-31"), None));
+        assert!(s.undo_last_completion(id, NOW));
+        assert_eq!(due_and_anchor(&s), (String::from("2026-02-28"), Some(31)));
+        s.complete_task(id, NOW, date("2026-02-28"), None);
+        assert_eq!(due_and_anchor(&s), (String::from("2026-03-31"), None));
+    }
+
+    #[test]
+    fn streak_is_zero_without_history() {
+        let mut s = temp_storage("streak_empty");
+        let id = add_task(&mut s, RecurrenceType::Daily, 1, "2026-03-01", false);
+        assert_eq!(s.current_streak(id, date("2026-03-01")), 0);
+        assert_eq!(s.current_streak(id + 1, date("2026-03-01")), 0);
+    }
+
+    #[test]
+    fn streak_restarts_after_a_late_completion_mid_run() {
+        let mut s = temp_storage("streak_mid_run");
+        let id = add_task(&mut s, RecurrenceType::Daily, 1, "2026-03-01", true);
+        s.complete_task(id, "2026-03-01T09:00:00", date("2026-03-01"), None);
+        // Due 03-02 but done 03-04
+        s.complete_task(id, "2026-03-04T09:00:00", date("2026-03-04"), None);
+        s.complete_task(id, "2026-03-05T09:00:00", date("2026-03-05"), None);
+        s.complete_task(id, "2026-03-06T09:00:00", date("2026-03-06"), None);
+        assert_eq!(s.current_streak(id, date("2026-03-07")), 2);
+    }
+
+    #[test]
+    fn streak_allows_the_overdue_grace() {
+        let mut s = temp_storage("streak_grace");
+        let mut settings = Settings::default();
+        settings.urgency.overdue_grace_days = 2;
+        s.set_settings(settings);
+        let id = add_task(&mut s, RecurrenceType::Daily, 1, "2026-03-01", false);
+
+        // Due 03-01, done 03-03: late, but within the grace
+        s.complete_task(id, "2026-03-03T09:00:00", date("2026-03-03"), None);
+        assert_eq!(s.get_task(id).unwrap().next_due_date, "2026-03-02");
+        assert_eq!(s.current_streak(id, date("2026-03-04")), 1);
+        // Past the grace the task is overdue, and the streak is gone
+        assert_eq!(s.current_streak(id, date("2026-03-05")), 0);
+    }
+
+    #[test]
+    fn a_corrupt_tasks_file_falls_back_to_the_backup() {
+        let mut s = temp_storage("corrupt");
+        let first = add_task(&mut s, RecurrenceType::Daily, 1, "2026-03-01", false);
+        // The second save copies the first one to .bak
+        add_task(&mut s, RecurrenceType::Weekly, 1, "2026-03-02", false);
+        let latest = std::fs::read_to_string(&s.tasks_path).unwrap();
+
+        // Power cut mid-write: the primary is cut short
+        std::fs::write(&s.tasks_path, &latest[..latest.len() / 2]).unwrap();
+        let reloaded = Storage::new(&s.tasks_path, &s.history_path, Settings::default());
+        let ids: Vec<u32> = reloaded.get_all_tasks(false).iter().map(|t| t.id).collect();
+        assert_eq!(ids, [first]);
+
+        // With the backup unreadable too, a verified .tmp left behind is the last resort
+        std::fs::write(format!("{}.bak", s.tasks_path), "").unwrap();
+        std::fs::write(format!("{}.tmp", s.tasks_path), &latest).unwrap();
+        let reloaded = Storage::new(&s.tasks_path, &s.history_path, Settings::default());
+        assert_eq!(reloaded.get_all_tasks(false).len(), 2);
     }
 }
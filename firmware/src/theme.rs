@@ -2,6 +2,7 @@
 ///
 /// Dark theme with friendly accent colors for kitchen display
 use embedded_graphics::pixelcolor::Rgb565;
+use embedded_graphics::prelude::RgbColor;
 
 /// Helper to convert 8-bit RGB to Rgb565
 const fn rgb(r: u8, g: u8, b: u8) -> Rgb565 {
@@ -23,6 +24,13 @@ pub const URGENCY_TODAY: Rgb565 = rgb(255, 159, 67);       // Warm orange #FF9F4
 pub const URGENCY_TOMORROW: Rgb565 = rgb(255, 206, 84);   // Sunny yellow #FFCE54
 pub const URGENCY_WEEK: Rgb565 = rgb(46, 213, 115);       // Fresh green #2ED573
 pub const URGENCY_UPCOMING: Rgb565 = rgb(116, 185, 255);  // Sky blue #74B9FF
+pub const URGENCY_OVERDUE_DEEP: Rgb565 = rgb(200, 20, 20); // Long-overdue red #C81414
+
+/// Days overdue at which the overdue color reaches URGENCY_OVERDUE_DEEP
+const OVERDUE_DEEPEST_DAYS: u32 = 30;
+
+/// Overdue share of all tasks above which the dashboard bar's overdue segment is hatched
+pub const OVERDUE_ALARM_PERCENT: u32 = 25;
 
 // UI accent colors
 pub const ACCENT: Rgb565 = rgb(99, 205, 218);             // Teal accent #63CDDA
@@ -32,6 +40,16 @@ pub const SUCCESS: Rgb565 = rgb(46, 213, 115);            // Fresh green
 // Selection highlight
 pub const SELECTION_BG: Rgb565 = rgb(40, 40, 40);         // Subtle highlight
 
+/// Parse a "#RRGGBB" tag color
+pub fn hex_color(hex: &str) -> Option<Rgb565> {
+    let digits = hex.strip_prefix('#')?;
+    if digits.len() != 6 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&digits[i..i + 2], 16).ok();
+    Some(rgb(channel(0)?, channel(2)?, channel(4)?))
+}
+
 /// Get urgency color from string
 pub fn urgency_color(urgency: &str) -> Rgb565 {
     match urgency {
@@ -39,10 +57,22 @@ pub fn urgency_color(urgency: &str) -> Rgb565 {
         "today" => URGENCY_TODAY,
         "tomorrow" => URGENCY_TOMORROW,
         "week" => URGENCY_WEEK,
+        "paused" => TEXT_MUTED,
         _ => URGENCY_UPCOMING,
     }
 }
 
+/// Overdue color that deepens from URGENCY_OVERDUE toward URGENCY_OVERDUE_DEEP as a task ages
+pub fn overdue_color(days_overdue: u32) -> Rgb565 {
+    let t = days_overdue.min(OVERDUE_DEEPEST_DAYS);
+    let mix = |from: u8, to: u8| -> u8 {
+        let (from, to) = (from as i32, to as i32);
+        (from + (to - from) * t as i32 / OVERDUE_DEEPEST_DAYS as i32) as u8
+    };
+    let (a, b) = (URGENCY_OVERDUE, URGENCY_OVERDUE_DEEP);
+    Rgb565::new(mix(a.r(), b.r()), mix(a.g(), b.g()), mix(a.b(), b.b()))
+}
+
 /// Get urgency label
 pub fn urgency_label(urgency: &str) -> &'static str {
     match urgency {
@@ -50,6 +80,7 @@ pub fn urgency_label(urgency: &str) -> &'static str {
         "today" => "TODAY",
         "tomorrow" => "TOMORROW",
         "week" => "THIS WEEK",
+        "paused" => "PAUSED",
         _ => "UPCOMING",
     }
 }
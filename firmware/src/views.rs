@@ -3,8 +3,14 @@ extern crate alloc;
 
 use alloc::string::String;
 use alloc::vec::Vec;
+use core::str::FromStr;
+use std::time::{Duration, Instant};
 
-use crate::models::{CompletionRecord, Task};
+use chrono::NaiveDate;
+use serde_json::json;
+
+use crate::config;
+use crate::models::{CompletionRecord, DashboardMetric, Settings, Task, UrgencyThresholds};
 use crate::wifi::WiFiMode;
 
 /// Possible view states
@@ -12,6 +18,7 @@ use crate::wifi::WiFiMode;
 pub enum ViewState {
     Dashboard,
     TaskList,
+    TaskListCompact,
     TaskActions,
     DeleteConfirm,
     Completing,
@@ -21,92 +28,172 @@ pub enum ViewState {
     #[allow(dead_code)]
     Empty,
     ResetWifiConfirm,
+    SnoozeConfirm,
+    ResetTaskConfirm,
+    Reschedule,
+    Clock,
+}
+
+impl ViewState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Dashboard => "dashboard",
+            Self::TaskList => "task_list",
+            Self::TaskListCompact => "task_list_compact",
+            Self::TaskActions => "task_actions",
+            Self::DeleteConfirm => "delete_confirm",
+            Self::Completing => "completing",
+            Self::TaskHistory => "task_history",
+            Self::Settings => "settings",
+            Self::QrCode => "qr_code",
+            Self::Empty => "empty",
+            Self::ResetWifiConfirm => "reset_wifi_confirm",
+            Self::SnoozeConfirm => "snooze_confirm",
+            Self::ResetTaskConfirm => "reset_task_confirm",
+            Self::Reschedule => "reschedule",
+            Self::Clock => "clock",
+        }
+    }
+}
+
+impl FromStr for ViewState {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        match s {
+            "dashboard" => Ok(Self::Dashboard),
+            "task_list" => Ok(Self::TaskList),
+            "task_list_compact" => Ok(Self::TaskListCompact),
+            "task_actions" => Ok(Self::TaskActions),
+            "delete_confirm" => Ok(Self::DeleteConfirm),
+            "completing" => Ok(Self::Completing),
+            "task_history" => Ok(Self::TaskHistory),
+            "settings" => Ok(Self::Settings),
+            "qr_code" => Ok(Self::QrCode),
+            "empty" => Ok(Self::Empty),
+            "reset_wifi_confirm" => Ok(Self::ResetWifiConfirm),
+            "snooze_confirm" => Ok(Self::SnoozeConfirm),
+            "reset_task_confirm" => Ok(Self::ResetTaskConfirm),
+            "reschedule" => Ok(Self::Reschedule),
+            "clock" => Ok(Self::Clock),
+            _ => Err(()),
+        }
+    }
 }
 
-/// Dashboard selectable items
+/// Dashboard selectable items: the configured metric cells, then the nav buttons
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DashboardItem {
-    Overdue,   // 0
-    Today,     // 1
-    Week,      // 2
-    Total,     // 3
-    AllTasks,  // 4
-    Settings,  // 5
+    Metric(DashboardMetric),
+    AllTasks,
+    Settings,
 }
 
-const DASHBOARD_ITEMS: [DashboardItem; 6] = [
-    DashboardItem::Overdue,
-    DashboardItem::Today,
-    DashboardItem::Week,
-    DashboardItem::Total,
-    DashboardItem::AllTasks,
-    DashboardItem::Settings,
-];
-
 /// Action menu items
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ActionItem {
     Done,
+    Pause,
+    Resume,
+    Reschedule,
+    Pin,
+    Unpin,
     History,
+    Reset,
     Delete,
     Back,
 }
 
-const ACTION_ITEMS: [ActionItem; 4] = [
-    ActionItem::Done,
-    ActionItem::History,
-    ActionItem::Delete,
-    ActionItem::Back,
-];
-
 impl ActionItem {
     pub fn label(&self) -> &'static str {
         match self {
             Self::Done => "Done",
+            Self::Pause => "Pause",
+            Self::Resume => "Resume",
+            Self::Reschedule => "Reschedule",
+            Self::Pin => "Keep screen on",
+            Self::Unpin => "Allow screen off",
             Self::History => "History",
+            Self::Reset => "Reset",
             Self::Delete => "Delete",
             Self::Back => "Back",
         }
     }
+
+    /// Menu options that apply to a task in its current state
+    pub fn for_task(task: &Task) -> Vec<ActionItem> {
+        let mut items = Vec::new();
+        if task.paused {
+            items.push(ActionItem::Resume);
+        } else {
+            items.push(ActionItem::Done);
+            items.push(ActionItem::Pause);
+        }
+        items.push(ActionItem::Reschedule);
+        items.push(if task.pinned { ActionItem::Unpin } else { ActionItem::Pin });
+        items.push(ActionItem::History);
+        items.push(ActionItem::Reset);
+        items.push(ActionItem::Delete);
+        items.push(ActionItem::Back);
+        items
+    }
 }
 
 /// Settings menu items
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SettingItem {
     ManageTasks,
+    Clock,
     ScreenTimeout,
     ResetWifi,
     Back,
 }
 
-const SETTING_ITEMS: [SettingItem; 4] = [
+const SETTING_ITEMS: [SettingItem; 5] = [
     SettingItem::ManageTasks,
+    SettingItem::Clock,
     SettingItem::ScreenTimeout,
     SettingItem::ResetWifi,
     SettingItem::Back,
 ];
 
+/// Delete confirmation buttons, left to right
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeleteChoice {
+    Cancel,
+    Delete,
+}
+
+/// Toast shown after a device completion; a long press undoes it while it's up
+const UNDO_TOAST: &str = "Hold to undo";
+
 /// Current view context data
 pub struct ViewContext {
     pub state: ViewState,
 
     // Dashboard state
     pub dashboard_index: usize,
+    pub dashboard_cells: Vec<DashboardMetric>,
+    pub urgency_thresholds: UrgencyThresholds,
     pub task_counts: TaskCounts,
     pub filtered_urgency: Option<String>,
 
     // Task list state
     pub tasks: Vec<Task>,
     pub task_index: i32,  // -1 = back option
+    pub compact_list: bool, // Rows instead of one card per task
+    pub name_scroll: u32,   // Marquee offset (pixels) for a card name too long to fit
 
     // Action menu state
     pub action_index: usize,
 
-    // Delete confirmation
-    pub delete_confirmed: bool,
+    // Delete confirmation: selected button, always starts on Cancel
+    pub delete_choice: DeleteChoice,
 
     // Completing animation
     pub completing_progress: f32,
+    pub completing_started: Option<Instant>,
+    pub last_completed: Option<(u32, Instant)>, // Task id + when, for the double-completion lock
 
     // History view
     pub history: Vec<CompletionRecord>,
@@ -118,12 +205,28 @@ pub struct ViewContext {
 
     // Network info
     pub ap_url: String,
+    pub device_name: String,
 
     // WiFi mode (determines QR code behavior and display)
     pub wifi_mode: WiFiMode,
 
     // Reset WiFi confirmation
     pub reset_wifi_confirmed: bool,
+
+    // Snooze-all-overdue confirmation
+    pub snooze_confirmed: bool,
+
+    // Reset task (clear history, due today) confirmation
+    pub reset_task_confirmed: bool,
+
+    // Reschedule: candidate due date, starting from the task's current one
+    pub reschedule_date: Option<NaiveDate>,
+
+    // Transient message overlay and when it was shown
+    pub toast: Option<(String, Instant)>,
+
+    // View to go back to when any input leaves the clock
+    pub clock_return: ViewState,
 }
 
 /// Task counts for dashboard
@@ -131,29 +234,55 @@ pub struct ViewContext {
 pub struct TaskCounts {
     pub overdue: u32,
     pub today: u32,
+    pub tomorrow: u32,
     pub week: u32,
     pub total: u32,
 }
 
+impl TaskCounts {
+    /// Count shown in a dashboard metric cell
+    pub fn metric(&self, metric: DashboardMetric) -> u32 {
+        match metric {
+            DashboardMetric::Overdue => self.overdue,
+            DashboardMetric::Today => self.today,
+            DashboardMetric::Tomorrow => self.tomorrow,
+            DashboardMetric::Week => self.week,
+            DashboardMetric::Total => self.total,
+        }
+    }
+}
+
 impl ViewContext {
     pub fn new() -> Self {
         Self {
             state: ViewState::Dashboard,
             dashboard_index: 0,
+            dashboard_cells: Settings::default().dashboard_cells,
+            urgency_thresholds: UrgencyThresholds::default(),
             task_counts: TaskCounts::default(),
             filtered_urgency: None,
             tasks: Vec::new(),
             task_index: 0,
+            compact_list: false,
+            name_scroll: 0,
             action_index: 0,
-            delete_confirmed: false,
+            delete_choice: DeleteChoice::Cancel,
             completing_progress: 0.0,
+            completing_started: None,
+            last_completed: None,
             history: Vec::new(),
             history_index: 0,
             setting_index: 0,
             screen_timeout_enabled: true,
             ap_url: String::new(),
+            device_name: String::new(),
             wifi_mode: WiFiMode::AccessPoint { ip: [192, 168, 4, 1] },
             reset_wifi_confirmed: false,
+            snooze_confirmed: false,
+            reset_task_confirmed: false,
+            reschedule_date: None,
+            toast: None,
+            clock_return: ViewState::Dashboard,
         }
     }
 
@@ -166,9 +295,80 @@ impl ViewContext {
         }
     }
 
+    /// Action menu options for the currently selected task
+    pub fn action_items(&self) -> Vec<ActionItem> {
+        match self.current_task() {
+            Some(task) => ActionItem::for_task(task),
+            None => alloc::vec![ActionItem::Back],
+        }
+    }
+
+    /// Task list view for the user's list style
+    pub fn list_state(&self) -> ViewState {
+        if self.compact_list {
+            ViewState::TaskListCompact
+        } else {
+            ViewState::TaskList
+        }
+    }
+
+    /// Task completed on the device while its undo toast is still up (a long press undoes it).
+    /// Once the toast is gone nothing says a long press would undo, so it leaves the list instead.
+    pub fn undo_candidate(&self) -> Option<u32> {
+        match (&self.toast, self.last_completed) {
+            (Some((message, shown_at)), Some((id, _)))
+                if message == UNDO_TOAST && shown_at.elapsed() < Duration::from_millis(config::TOAST_DURATION_MS) =>
+            {
+                Some(id)
+            }
+            _ => None,
+        }
+    }
+
+    /// True if this task was completed on the device within the last COMPLETE_LOCK_MS
+    pub fn completion_locked(&self, task_id: u32) -> bool {
+        matches!(
+            self.last_completed,
+            Some((id, at)) if id == task_id && at.elapsed() < Duration::from_millis(config::COMPLETE_LOCK_MS)
+        )
+    }
+
+    /// A pinned task is selected in the list (its card, or its row in the compact list), so
+    /// the idle timeout shouldn't turn the screen off. Moving to another task or leaving the
+    /// list lets the timeout run again.
+    pub fn showing_pinned_task(&self) -> bool {
+        matches!(self.state, ViewState::TaskList | ViewState::TaskListCompact)
+            && self.current_task().is_some_and(|t| t.pinned)
+    }
+
+    /// No tasks exist at all (first run): the dashboard grid would be all zeros
+    pub fn has_no_tasks(&self) -> bool {
+        self.task_counts.total == 0
+    }
+
+    /// Rotating an empty filtered list has nothing to move to; say how to leave instead
+    fn hint_empty_filter(&mut self) {
+        if self.filtered_urgency.is_some() {
+            self.toast = Some((String::from("Press to go back"), Instant::now()));
+        }
+    }
+
+    /// Any input on the clock goes back to where it was opened from
+    fn leave_clock(&mut self) {
+        self.state = self.clock_return;
+    }
+
+    /// Selectable dashboard items in navigation order
+    pub fn dashboard_items(&self) -> Vec<DashboardItem> {
+        let mut items: Vec<DashboardItem> = self.dashboard_cells.iter().map(|m| DashboardItem::Metric(*m)).collect();
+        items.push(DashboardItem::AllTasks);
+        items.push(DashboardItem::Settings);
+        items
+    }
+
     /// Get currently selected dashboard item
     pub fn current_dashboard_item(&self) -> Option<DashboardItem> {
-        DASHBOARD_ITEMS.get(self.dashboard_index).copied()
+        self.dashboard_items().get(self.dashboard_index).copied()
     }
 }
 
@@ -176,17 +376,21 @@ impl ViewContext {
 pub enum RenderCommand {
     Dashboard {
         counts: TaskCounts,
+        cells: Vec<DashboardMetric>,
         selected: usize,
     },
     TaskCard {
         task_index: usize,
         total: usize,
-        #[allow(dead_code)]
         filtered: Option<String>,
     },
     BackCard {
         total: usize,
     },
+    TaskListCompact {
+        selected: i32, // -1 = back row
+        filtered: Option<String>,
+    },
     EmptyFiltered {
         filter_name: String,
     },
@@ -202,6 +406,15 @@ pub enum RenderCommand {
         task_name: String,
         confirm_selected: bool,
     },
+    ResetTaskConfirm {
+        task_name: String,
+        confirmed: bool,
+    },
+    Reschedule {
+        task_name: String,
+        date: NaiveDate,
+        original: Option<NaiveDate>,
+    },
     Completing {
         task_name: String,
         progress: f32,
@@ -213,6 +426,7 @@ pub enum RenderCommand {
     Settings {
         selected: usize,
         screen_timeout_enabled: bool,
+        device_name: String,
     },
     QrCode {
         wifi_mode: WiFiMode,
@@ -221,6 +435,11 @@ pub enum RenderCommand {
     ResetWifiConfirm {
         confirmed: bool,
     },
+    SnoozeConfirm {
+        count: u32,
+        confirmed: bool,
+    },
+    Clock,
 }
 
 /// Handles navigation between views based on encoder input
@@ -246,6 +465,15 @@ impl ViewNavigator {
         }
     }
 
+    /// Pick up user settings that affect navigation and display
+    pub fn apply_settings(&mut self, settings: &Settings) {
+        self.ctx.urgency_thresholds = settings.urgency;
+        self.ctx.dashboard_cells = settings.dashboard_cells.clone();
+        self.ctx.compact_list = settings.compact_task_list;
+        let max_idx = self.ctx.dashboard_items().len() - 1;
+        self.ctx.dashboard_index = self.ctx.dashboard_index.min(max_idx);
+    }
+
     /// Update task counts for dashboard
     pub fn set_task_counts(&mut self, counts: TaskCounts) {
         self.ctx.task_counts = counts;
@@ -257,13 +485,17 @@ impl ViewNavigator {
         self.ctx.history_index = 0;
     }
 
-    /// Handle clockwise encoder rotation (scroll down)
-    pub fn handle_clockwise(&mut self) {
+    /// Handle clockwise encoder rotation (scroll down).
+    /// `steps` > 1 (a fast spin) skips ahead in lists but still stops before Back.
+    pub fn handle_clockwise(&mut self, steps: u8) {
         let ctx = &mut self.ctx;
+        let steps = steps.max(1) as i32;
 
         match ctx.state {
             ViewState::Dashboard => {
-                ctx.dashboard_index = (ctx.dashboard_index + 1) % DASHBOARD_ITEMS.len();
+                if !ctx.has_no_tasks() {
+                    ctx.dashboard_index = (ctx.dashboard_index + 1) % ctx.dashboard_items().len();
+                }
             }
             ViewState::TaskList => {
                 if !ctx.tasks.is_empty() {
@@ -273,41 +505,74 @@ impl ViewNavigator {
                     } else if ctx.task_index == len - 1 {
                         ctx.task_index = -1; // Last task -> back
                     } else {
-                        ctx.task_index += 1;
+                        ctx.task_index = (ctx.task_index + steps).min(len - 1);
                     }
+                } else {
+                    ctx.hint_empty_filter();
+                }
+            }
+            ViewState::TaskListCompact => {
+                // Back is the row after the last task; the list doesn't wrap
+                if !ctx.tasks.is_empty() {
+                    let last = ctx.tasks.len() as i32 - 1;
+                    if ctx.task_index == last {
+                        ctx.task_index = -1;
+                    } else if ctx.task_index != -1 {
+                        ctx.task_index = (ctx.task_index + steps).min(last);
+                    }
+                } else {
+                    ctx.hint_empty_filter();
                 }
             }
             ViewState::TaskActions => {
-                ctx.action_index = (ctx.action_index + 1) % ACTION_ITEMS.len();
+                ctx.action_index = (ctx.action_index + 1) % ctx.action_items().len();
             }
-            ViewState::DeleteConfirm | ViewState::ResetWifiConfirm => {
-                ctx.delete_confirmed = !ctx.delete_confirmed;
+            ViewState::DeleteConfirm => {
+                // Clockwise always moves right (Delete), so extra detents can't flip back
+                ctx.delete_choice = DeleteChoice::Delete;
+            }
+            ViewState::ResetWifiConfirm => {
                 ctx.reset_wifi_confirmed = !ctx.reset_wifi_confirmed;
             }
+            ViewState::SnoozeConfirm => {
+                ctx.snooze_confirmed = true; // Right button
+            }
+            ViewState::ResetTaskConfirm => {
+                ctx.reset_task_confirmed = true; // Right button
+            }
+            ViewState::Reschedule => {
+                // A fast spin moves several days per detent
+                ctx.reschedule_date = ctx.reschedule_date.and_then(|d| d.checked_add_days(chrono::Days::new(steps as u64)));
+            }
             ViewState::TaskHistory => {
                 if !ctx.history.is_empty() {
-                    ctx.history_index = (ctx.history_index + 1).min(ctx.history.len() - 1);
+                    ctx.history_index = (ctx.history_index + steps as usize).min(ctx.history.len() - 1);
                 }
             }
             ViewState::Settings => {
                 let max_idx = SETTING_ITEMS.len() - 1;
                 ctx.setting_index = (ctx.setting_index + 1).min(max_idx);
             }
+            ViewState::Clock => ctx.leave_clock(),
             ViewState::Empty | ViewState::QrCode | ViewState::Completing => {}
         }
     }
 
-    /// Handle counter-clockwise encoder rotation (scroll up)
-    pub fn handle_counter_clockwise(&mut self) {
+    /// Handle counter-clockwise encoder rotation (scroll up).
+    /// `steps` > 1 (a fast spin) skips back in lists but still stops before Back.
+    pub fn handle_counter_clockwise(&mut self, steps: u8) {
         let ctx = &mut self.ctx;
+        let steps = steps.max(1) as i32;
 
         match ctx.state {
             ViewState::Dashboard => {
-                ctx.dashboard_index = if ctx.dashboard_index == 0 {
-                    DASHBOARD_ITEMS.len() - 1
-                } else {
-                    ctx.dashboard_index - 1
-                };
+                if !ctx.has_no_tasks() {
+                    ctx.dashboard_index = if ctx.dashboard_index == 0 {
+                        ctx.dashboard_items().len() - 1
+                    } else {
+                        ctx.dashboard_index - 1
+                    };
+                }
             }
             ViewState::TaskList => {
                 if !ctx.tasks.is_empty() {
@@ -317,27 +582,52 @@ impl ViewNavigator {
                     } else if ctx.task_index == 0 {
                         ctx.task_index = -1; // First task -> back
                     } else {
-                        ctx.task_index -= 1;
+                        ctx.task_index = (ctx.task_index - steps).max(0);
+                    }
+                } else {
+                    ctx.hint_empty_filter();
+                }
+            }
+            ViewState::TaskListCompact => {
+                if !ctx.tasks.is_empty() {
+                    if ctx.task_index == -1 {
+                        ctx.task_index = ctx.tasks.len() as i32 - 1;
+                    } else {
+                        ctx.task_index = (ctx.task_index - steps).max(0);
                     }
+                } else {
+                    ctx.hint_empty_filter();
                 }
             }
             ViewState::TaskActions => {
                 ctx.action_index = if ctx.action_index == 0 {
-                    ACTION_ITEMS.len() - 1
+                    ctx.action_items().len() - 1
                 } else {
                     ctx.action_index - 1
                 };
             }
-            ViewState::DeleteConfirm | ViewState::ResetWifiConfirm => {
-                ctx.delete_confirmed = !ctx.delete_confirmed;
+            ViewState::DeleteConfirm => {
+                ctx.delete_choice = DeleteChoice::Cancel;
+            }
+            ViewState::ResetWifiConfirm => {
                 ctx.reset_wifi_confirmed = !ctx.reset_wifi_confirmed;
             }
+            ViewState::SnoozeConfirm => {
+                ctx.snooze_confirmed = false; // Left button
+            }
+            ViewState::ResetTaskConfirm => {
+                ctx.reset_task_confirmed = false; // Left button
+            }
+            ViewState::Reschedule => {
+                ctx.reschedule_date = ctx.reschedule_date.and_then(|d| d.checked_sub_days(chrono::Days::new(steps as u64)));
+            }
             ViewState::TaskHistory => {
-                ctx.history_index = ctx.history_index.saturating_sub(1);
+                ctx.history_index = ctx.history_index.saturating_sub(steps as usize);
             }
             ViewState::Settings => {
                 ctx.setting_index = ctx.setting_index.saturating_sub(1);
             }
+            ViewState::Clock => ctx.leave_clock(),
             ViewState::Empty | ViewState::QrCode | ViewState::Completing => {}
         }
     }
@@ -349,12 +639,18 @@ impl ViewNavigator {
 
         match ctx.state {
             ViewState::Dashboard => {
+                // No tasks yet: the dashboard shows the add-tasks prompt, press goes to the QR code
+                if ctx.has_no_tasks() {
+                    ctx.state = ViewState::QrCode;
+                    return Some("show_qr");
+                }
+
                 let item = ctx.current_dashboard_item()?;
                 match item {
                     DashboardItem::AllTasks => {
                         ctx.filtered_urgency = None;
                         ctx.task_index = 0;
-                        ctx.state = ViewState::TaskList;
+                        ctx.state = ctx.list_state();
                         return Some("show_all_tasks");
                     }
                     DashboardItem::Settings => {
@@ -362,22 +658,15 @@ impl ViewNavigator {
                         ctx.state = ViewState::Settings;
                         return Some("show_settings");
                     }
-                    DashboardItem::Overdue | DashboardItem::Today | DashboardItem::Week | DashboardItem::Total => {
-                        let filter = match item {
-                            DashboardItem::Overdue => "overdue",
-                            DashboardItem::Today => "today",
-                            DashboardItem::Week => "week",
-                            DashboardItem::Total => "total",
-                            _ => unreachable!(),
-                        };
-                        ctx.filtered_urgency = Some(String::from(filter));
+                    DashboardItem::Metric(metric) => {
+                        ctx.filtered_urgency = Some(String::from(metric.as_str()));
                         ctx.task_index = 0;
-                        ctx.state = ViewState::TaskList;
+                        ctx.state = ctx.list_state();
                         return Some("filter_tasks");
                     }
                 }
             }
-            ViewState::TaskList => {
+            ViewState::TaskList | ViewState::TaskListCompact => {
                 if ctx.task_index == -1 {
                     // Back selected
                     ctx.filtered_urgency = None;
@@ -387,6 +676,12 @@ impl ViewNavigator {
                 } else if !ctx.tasks.is_empty() {
                     ctx.action_index = 0;
                     ctx.state = ViewState::TaskActions;
+                } else if ctx.filtered_urgency.is_some() {
+                    // Empty filter: nothing to pick, so a press is the way out
+                    ctx.filtered_urgency = None;
+                    ctx.task_index = 0;
+                    ctx.state = ViewState::Dashboard;
+                    return Some("go_dashboard");
                 } else {
                     // No tasks — go to QR code so user can add tasks
                     ctx.state = ViewState::QrCode;
@@ -394,35 +689,80 @@ impl ViewNavigator {
                 }
             }
             ViewState::TaskActions => {
-                let action = ACTION_ITEMS[ctx.action_index];
+                let action = ctx.action_items().get(ctx.action_index).copied()?;
                 match action {
                     ActionItem::Done => {
+                        // A bounce or impatient second press right after completing: don't
+                        // start another completion
+                        if ctx.current_task().is_some_and(|t| ctx.completion_locked(t.id)) {
+                            ctx.toast = Some((String::from("Already done"), Instant::now()));
+                            return None;
+                        }
                         ctx.completing_progress = 0.0;
+                        ctx.completing_started = Some(Instant::now());
                         ctx.state = ViewState::Completing;
                         return Some("complete");
                     }
+                    ActionItem::Pause => {
+                        ctx.state = ctx.list_state();
+                        return Some("pause");
+                    }
+                    ActionItem::Resume => {
+                        ctx.state = ctx.list_state();
+                        return Some("resume");
+                    }
+                    ActionItem::Reschedule => {
+                        ctx.reschedule_date = ctx.current_task().and_then(|t| t.due_date());
+                        if ctx.reschedule_date.is_some() {
+                            ctx.state = ViewState::Reschedule;
+                        }
+                    }
+                    ActionItem::Pin => {
+                        ctx.state = ctx.list_state();
+                        return Some("pin");
+                    }
+                    ActionItem::Unpin => {
+                        ctx.state = ctx.list_state();
+                        return Some("unpin");
+                    }
                     ActionItem::History => {
                         ctx.history_index = 0;
                         ctx.state = ViewState::TaskHistory;
                         return Some("load_history");
                     }
+                    ActionItem::Reset => {
+                        ctx.reset_task_confirmed = false;
+                        ctx.state = ViewState::ResetTaskConfirm;
+                    }
                     ActionItem::Delete => {
-                        ctx.delete_confirmed = false;
+                        ctx.delete_choice = DeleteChoice::Cancel;
                         ctx.state = ViewState::DeleteConfirm;
                     }
                     ActionItem::Back => {
-                        ctx.state = ViewState::TaskList;
+                        ctx.state = ctx.list_state();
                     }
                 }
             }
             ViewState::DeleteConfirm => {
-                if ctx.delete_confirmed {
-                    ctx.state = ViewState::TaskList;
+                if ctx.delete_choice == DeleteChoice::Delete {
+                    ctx.state = ctx.list_state();
                     return Some("delete");
                 } else {
                     ctx.state = ViewState::TaskActions;
                 }
             }
+            ViewState::ResetTaskConfirm => {
+                if ctx.reset_task_confirmed {
+                    ctx.state = ctx.list_state();
+                    return Some("reset_task");
+                } else {
+                    ctx.state = ViewState::TaskActions;
+                }
+            }
+            ViewState::Reschedule => {
+                ctx.state = ctx.list_state();
+                return Some("reschedule");
+            }
             ViewState::TaskHistory => {
                 ctx.state = ViewState::TaskActions;
             }
@@ -433,6 +773,10 @@ impl ViewNavigator {
                         ctx.state = ViewState::QrCode;
                         return Some("show_qr");
                     }
+                    SettingItem::Clock => {
+                        ctx.clock_return = ViewState::Settings;
+                        ctx.state = ViewState::Clock;
+                    }
                     SettingItem::ScreenTimeout => {
                         ctx.screen_timeout_enabled = !ctx.screen_timeout_enabled;
                         return Some("toggle_timeout");
@@ -453,6 +797,12 @@ impl ViewNavigator {
                     ctx.state = ViewState::Settings;
                 }
             }
+            ViewState::SnoozeConfirm => {
+                ctx.state = ViewState::Dashboard;
+                if ctx.snooze_confirmed {
+                    return Some("snooze_overdue");
+                }
+            }
             ViewState::Empty => {
                 ctx.state = ViewState::QrCode;
                 return Some("show_qr");
@@ -461,6 +811,7 @@ impl ViewNavigator {
                 ctx.state = ViewState::Dashboard;
                 return Some("go_dashboard");
             }
+            ViewState::Clock => ctx.leave_clock(),
             ViewState::Completing => {}
         }
 
@@ -473,9 +824,32 @@ impl ViewNavigator {
 
         match ctx.state {
             ViewState::Dashboard => {
-                // Already at home
+                // The grid (and its Settings button) is hidden while there are no tasks
+                if ctx.has_no_tasks() {
+                    ctx.setting_index = 0;
+                    ctx.state = ViewState::Settings;
+                    return Some("show_settings");
+                }
+
+                // Long-press on OVERDUE offers to push the whole pile to tomorrow
+                if ctx.current_dashboard_item() == Some(DashboardItem::Metric(DashboardMetric::Overdue))
+                    && ctx.task_counts.overdue > 0
+                {
+                    ctx.snooze_confirmed = false;
+                    ctx.state = ViewState::SnoozeConfirm;
+                }
+
+                // Long-press on All Tasks switches between cards and the compact list
+                if ctx.current_dashboard_item() == Some(DashboardItem::AllTasks) {
+                    ctx.compact_list = !ctx.compact_list;
+                    return Some("toggle_list_style");
+                }
             }
-            ViewState::TaskList => {
+            ViewState::TaskList | ViewState::TaskListCompact => {
+                // Right after a completion, long press takes it back instead of leaving
+                if ctx.undo_candidate().is_some() {
+                    return Some("undo_complete");
+                }
                 ctx.filtered_urgency = None;
                 ctx.state = ViewState::Dashboard;
                 return Some("go_dashboard");
@@ -488,12 +862,20 @@ impl ViewNavigator {
                 ctx.state = ViewState::Dashboard;
                 return Some("go_dashboard");
             }
-            ViewState::TaskActions | ViewState::DeleteConfirm | ViewState::TaskHistory => {
-                ctx.state = ViewState::TaskList;
+            ViewState::TaskActions
+            | ViewState::DeleteConfirm
+            | ViewState::ResetTaskConfirm
+            | ViewState::TaskHistory
+            | ViewState::Reschedule => {
+                ctx.state = ctx.list_state();
             }
             ViewState::ResetWifiConfirm => {
                 ctx.state = ViewState::Settings;
             }
+            ViewState::SnoozeConfirm => {
+                ctx.state = ViewState::Dashboard;
+            }
+            ViewState::Clock => ctx.leave_clock(),
             ViewState::Completing => {
                 // Can't cancel completion
             }
@@ -506,9 +888,80 @@ impl ViewNavigator {
         None
     }
 
+    /// Double press from anywhere: straight back to the dashboard
+    pub fn handle_double_press(&mut self) -> Option<&'static str> {
+        let ctx = &mut self.ctx;
+
+        match ctx.state {
+            // Already there, or mid-completion (can't be cancelled)
+            ViewState::Dashboard | ViewState::Completing => None,
+            _ => {
+                ctx.filtered_urgency = None;
+                ctx.state = ViewState::Dashboard;
+                Some("go_dashboard")
+            }
+        }
+    }
+
+    /// Screensaver: switch to the clock, remembering the current view to return to.
+    /// Skipped mid-completion and on the QR code (it's being scanned). Returns true if switched.
+    pub fn show_clock(&mut self) -> bool {
+        if matches!(self.ctx.state, ViewState::Clock | ViewState::Completing | ViewState::QrCode) {
+            return false;
+        }
+        self.ctx.clock_return = self.ctx.state;
+        self.ctx.state = ViewState::Clock;
+        true
+    }
+
+    /// Show a short message over the current view
+    pub fn show_toast(&mut self, message: &str) {
+        self.ctx.toast = Some((String::from(message), Instant::now()));
+    }
+
+    /// Offer to undo the completion just recorded, for as long as the toast stays up
+    pub fn show_undo_toast(&mut self) {
+        self.show_toast(UNDO_TOAST);
+    }
+
+    /// Drop the toast once it has been visible long enough. Returns true if it was cleared.
+    pub fn clear_expired_toast(&mut self) -> bool {
+        let expired = matches!(
+            self.ctx.toast,
+            Some((_, shown_at)) if shown_at.elapsed() >= Duration::from_millis(config::TOAST_DURATION_MS)
+        );
+        if expired {
+            self.ctx.toast = None;
+        }
+        expired
+    }
+
+    /// Remember a completion so a bounce or impatient second press can't repeat it
+    pub fn record_completion(&mut self, task_id: u32) {
+        self.ctx.last_completed = Some((task_id, Instant::now()));
+    }
+
     /// Called when completion animation finishes
     pub fn complete_animation_done(&mut self) {
-        self.ctx.state = ViewState::TaskList;
+        self.ctx.completing_started = None;
+        self.ctx.state = self.ctx.list_state();
+    }
+
+    /// Leave the completing view if it was never finished (e.g. the completion path failed).
+    /// Returns true if the navigator had to bail out.
+    pub fn check_completing_timeout(&mut self) -> bool {
+        if self.ctx.state != ViewState::Completing {
+            return false;
+        }
+        let stuck = match self.ctx.completing_started {
+            Some(started) => started.elapsed() >= Duration::from_millis(config::COMPLETING_TIMEOUT_MS),
+            None => true,
+        };
+        if stuck {
+            log::warn!("Completing view timed out, returning to task list");
+            self.complete_animation_done();
+        }
+        stuck
     }
 
     /// Get render command for current view
@@ -516,8 +969,12 @@ impl ViewNavigator {
         let ctx = &self.ctx;
 
         match ctx.state {
+            ViewState::Dashboard if ctx.has_no_tasks() => RenderCommand::Empty {
+                wifi_mode: ctx.wifi_mode.clone(),
+            },
             ViewState::Dashboard => RenderCommand::Dashboard {
                 counts: ctx.task_counts.clone(),
+                cells: ctx.dashboard_cells.clone(),
                 selected: ctx.dashboard_index,
             },
             ViewState::TaskList => {
@@ -541,6 +998,22 @@ impl ViewNavigator {
                     }
                 }
             }
+            ViewState::TaskListCompact => {
+                if !ctx.tasks.is_empty() {
+                    RenderCommand::TaskListCompact {
+                        selected: ctx.task_index,
+                        filtered: ctx.filtered_urgency.clone(),
+                    }
+                } else if let Some(ref filtered) = ctx.filtered_urgency {
+                    RenderCommand::EmptyFiltered {
+                        filter_name: filtered.clone(),
+                    }
+                } else {
+                    RenderCommand::Empty {
+                        wifi_mode: ctx.wifi_mode.clone(),
+                    }
+                }
+            }
             ViewState::TaskActions => {
                 let task_name = ctx
                     .current_task()
@@ -549,7 +1022,7 @@ impl ViewNavigator {
                 RenderCommand::ActionMenu {
                     task_name,
                     selected: ctx.action_index,
-                    options: ACTION_ITEMS.iter().map(|a| String::from(a.label())).collect(),
+                    options: ctx.action_items().iter().map(|a| String::from(a.label())).collect(),
                 }
             }
             ViewState::DeleteConfirm => {
@@ -559,7 +1032,25 @@ impl ViewNavigator {
                     .unwrap_or_default();
                 RenderCommand::ConfirmDialog {
                     task_name,
-                    confirm_selected: ctx.delete_confirmed,
+                    confirm_selected: ctx.delete_choice == DeleteChoice::Delete,
+                }
+            }
+            ViewState::ResetTaskConfirm => {
+                let task_name = ctx
+                    .current_task()
+                    .map(|t| t.name.clone())
+                    .unwrap_or_default();
+                RenderCommand::ResetTaskConfirm {
+                    task_name,
+                    confirmed: ctx.reset_task_confirmed,
+                }
+            }
+            ViewState::Reschedule => {
+                let task = ctx.current_task();
+                RenderCommand::Reschedule {
+                    task_name: task.map(|t| t.name.clone()).unwrap_or_default(),
+                    date: ctx.reschedule_date.unwrap_or_default(),
+                    original: task.and_then(|t| t.due_date()),
                 }
             }
             ViewState::Completing => {
@@ -585,6 +1076,7 @@ impl ViewNavigator {
             ViewState::Settings => RenderCommand::Settings {
                 selected: ctx.setting_index,
                 screen_timeout_enabled: ctx.screen_timeout_enabled,
+                device_name: ctx.device_name.clone(),
             },
             ViewState::QrCode => RenderCommand::QrCode {
                 wifi_mode: ctx.wifi_mode.clone(),
@@ -596,6 +1088,223 @@ impl ViewNavigator {
             ViewState::ResetWifiConfirm => RenderCommand::ResetWifiConfirm {
                 confirmed: ctx.reset_wifi_confirmed,
             },
+            ViewState::SnoozeConfirm => RenderCommand::SnoozeConfirm {
+                count: ctx.task_counts.overdue,
+                confirmed: ctx.snooze_confirmed,
+            },
+            ViewState::Clock => RenderCommand::Clock,
         }
     }
+
+    /// Compact JSON description of what's on screen (for the web mirror)
+    pub fn screen_state(&self, today: NaiveDate) -> serde_json::Value {
+        let ctx = &self.ctx;
+        let task_json = |t: &Task| {
+            json!({
+                "id": t.id,
+                "name": t.name,
+                "daysUntilDue": t.days_until_due(today),
+                "urgency": if t.paused { "paused" } else { t.urgency(today, &ctx.urgency_thresholds).as_str() },
+                "nextDueDate": t.next_due_date,
+            })
+        };
+
+        let detail = match self.get_render_command() {
+            RenderCommand::Dashboard { counts, cells, selected } => json!({
+                "counts": {
+                    "overdue": counts.overdue,
+                    "today": counts.today,
+                    "tomorrow": counts.tomorrow,
+                    "week": counts.week,
+                    "total": counts.total,
+                },
+                "cells": cells.iter().map(|c| c.as_str()).collect::<Vec<_>>(),
+                "selected": selected,
+            }),
+            RenderCommand::TaskCard { task_index, total, filtered } => json!({
+                "index": task_index,
+                "total": total,
+                "filter": filtered,
+                "task": ctx.tasks.get(task_index).map(task_json),
+            }),
+            RenderCommand::BackCard { total } => json!({ "total": total, "back": true }),
+            RenderCommand::TaskListCompact { selected, filtered } => json!({
+                "selected": selected,
+                "total": ctx.tasks.len(),
+                "filter": filtered,
+                "tasks": ctx.tasks.iter().map(task_json).collect::<Vec<_>>(),
+            }),
+            RenderCommand::EmptyFiltered { filter_name } => json!({ "filter": filter_name }),
+            RenderCommand::Empty { wifi_mode } => json!({ "wifiMode": wifi_mode.mode_str() }),
+            RenderCommand::ActionMenu { task_name, selected, options } => json!({
+                "taskName": task_name,
+                "selected": selected,
+                "options": options,
+            }),
+            RenderCommand::ConfirmDialog { task_name, confirm_selected } => json!({
+                "taskName": task_name,
+                "confirmSelected": confirm_selected,
+            }),
+            RenderCommand::ResetTaskConfirm { task_name, confirmed } => json!({
+                "taskName": task_name,
+                "confirmed": confirmed,
+            }),
+            RenderCommand::Reschedule { task_name, date, original } => json!({
+                "taskName": task_name,
+                "date": date.format("%Y-%m-%d").to_string(),
+                "changed": original != Some(date),
+            }),
+            RenderCommand::Completing { task_name, progress } => json!({
+                "taskName": task_name,
+                "progress": progress,
+            }),
+            RenderCommand::History { task_name, selected } => json!({
+                "taskName": task_name,
+                "selected": selected,
+                "entries": ctx.history.len(),
+            }),
+            RenderCommand::Settings { selected, screen_timeout_enabled, device_name } => json!({
+                "selected": selected,
+                "screenTimeoutEnabled": screen_timeout_enabled,
+                "deviceName": device_name,
+            }),
+            RenderCommand::QrCode { wifi_mode, url } => json!({
+                "wifiMode": wifi_mode.mode_str(),
+                "url": url,
+            }),
+            RenderCommand::ResetWifiConfirm { confirmed } => json!({ "confirmed": confirmed }),
+            RenderCommand::SnoozeConfirm { count, confirmed } => json!({
+                "count": count,
+                "confirmed": confirmed,
+            }),
+            RenderCommand::Clock => json!({ "returnTo": ctx.clock_return.as_str() }),
+        };
+
+        json!({
+            "view": ctx.state.as_str(),
+            "detail": detail,
+            "toast": ctx.toast.as_ref().map(|(msg, _)| msg),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::RecurrenceType;
+
+    fn task(id: u32, paused: bool) -> Task {
+        Task {
+            id,
+            name: alloc::format!("Task {}", id),
+            recurrence_type: RecurrenceType::Daily,
+            recurrence_value: 1,
+            next_due_date: String::from("2026-03-01"),
+            created_at: String::from("2026-01-01T00:00:00"),
+            updated_at: String::from("2026-01-01T00:00:00"),
+            catch_up: false,
+            paused,
+            recur_from_completion: false,
+            due_message: None,
+            quota: None,
+            category: None,
+            color: None,
+            pinned: false,
+            anchor_day: None,
+        }
+    }
+
+    #[test]
+    fn action_menu_follows_task_state() {
+        let active = ActionItem::for_task(&task(1, false));
+        assert_eq!(active[..2], [ActionItem::Done, ActionItem::Pause]);
+        assert!(!active.contains(&ActionItem::Resume));
+
+        let paused = ActionItem::for_task(&task(2, true));
+        assert_eq!(paused[0], ActionItem::Resume);
+        assert!(!paused.contains(&ActionItem::Done));
+        assert!(!paused.contains(&ActionItem::Pause));
+    }
+
+    #[test]
+    fn press_dispatches_on_the_selected_item() {
+        let mut nav = ViewNavigator::new();
+        nav.set_tasks(alloc::vec![task(1, true)]);
+        nav.ctx.state = ViewState::TaskActions;
+
+        // Index 0 is Resume for a paused task, not Done
+        assert_eq!(nav.handle_press(), Some("resume"));
+        assert_eq!(nav.ctx.state, ViewState::TaskList);
+    }
+
+    #[test]
+    fn done_starts_the_completion_animation() {
+        let mut nav = ViewNavigator::new();
+        nav.set_tasks(alloc::vec![task(1, false)]);
+        nav.ctx.state = ViewState::TaskActions;
+
+        assert_eq!(nav.handle_press(), Some("complete"));
+        assert_eq!(nav.ctx.state, ViewState::Completing);
+
+        nav.complete_animation_done();
+        assert_eq!(nav.ctx.state, ViewState::TaskList);
+        assert!(!nav.check_completing_timeout());
+    }
+
+    #[test]
+    fn empty_filtered_list_hints_and_returns_to_dashboard() {
+        let mut nav = ViewNavigator::new();
+        nav.ctx.filtered_urgency = Some(String::from("overdue"));
+        nav.ctx.state = ViewState::TaskList;
+
+        nav.handle_clockwise(1);
+        assert_eq!(nav.ctx.toast.as_ref().map(|(msg, _)| msg.as_str()), Some("Press to go back"));
+
+        assert_eq!(nav.handle_press(), Some("go_dashboard"));
+        assert_eq!(nav.ctx.state, ViewState::Dashboard);
+        assert_eq!(nav.ctx.filtered_urgency, None);
+    }
+
+    #[test]
+    fn empty_unfiltered_list_press_still_shows_the_qr_code() {
+        let mut nav = ViewNavigator::new();
+        nav.ctx.state = ViewState::TaskList;
+
+        nav.handle_counter_clockwise(1);
+        assert!(nav.ctx.toast.is_none());
+
+        nav.handle_press();
+        assert_eq!(nav.ctx.state, ViewState::QrCode);
+    }
+
+    #[test]
+    fn done_right_after_a_completion_is_ignored() {
+        let mut nav = ViewNavigator::new();
+        nav.set_tasks(alloc::vec![task(1, false), task(2, false)]);
+        nav.record_completion(1);
+        nav.ctx.state = ViewState::TaskActions;
+
+        assert_eq!(nav.handle_press(), None);
+        assert_eq!(nav.ctx.state, ViewState::TaskActions);
+        assert_eq!(nav.ctx.toast.as_ref().map(|(msg, _)| msg.as_str()), Some("Already done"));
+
+        // Another task isn't held back
+        nav.ctx.task_index = 1;
+        assert_eq!(nav.handle_press(), Some("complete"));
+    }
+
+    #[test]
+    fn long_press_undoes_only_while_the_undo_toast_is_up() {
+        let mut nav = ViewNavigator::new();
+        nav.set_tasks(alloc::vec![task(1, false)]);
+        nav.ctx.state = ViewState::TaskList;
+        nav.record_completion(1);
+
+        nav.show_undo_toast();
+        assert_eq!(nav.handle_long_press(), Some("undo_complete"));
+
+        // Another message replaced the hint: a long press leaves the list as usual
+        nav.show_toast("Task changed");
+        assert_eq!(nav.handle_long_press(), Some("go_dashboard"));
+    }
 }
@@ -7,6 +7,7 @@ extern crate alloc;
 use alloc::format;
 use alloc::string::String;
 use alloc::vec::Vec;
+use std::time::Duration;
 
 use esp_idf_svc::wifi::{
     AccessPointConfiguration, AuthMethod, BlockingWifi, ClientConfiguration, Configuration, EspWifi,
@@ -16,8 +17,12 @@ use esp_idf_svc::nvs::{EspDefaultNvsPartition, EspNvs};
 use esp_idf_hal::modem::Modem;
 
 use esp_idf_svc::handle::RawHandle;
+use esp_idf_svc::mdns::EspMdns;
+use esp_idf_svc::sntp::{EspSntp, SntpConf};
 
 use crate::config;
+use crate::device;
+use crate::http_server::{lock_time, SharedTime, SyncedTime};
 
 /// Type alias for the WiFi handle that must be kept alive
 pub type BlockingWifiHandle = BlockingWifi<EspWifi<'static>>;
@@ -103,6 +108,9 @@ pub fn save_wifi_creds(
     let mut nvs = EspNvs::new(nvs_partition.clone(), config::NVS_NAMESPACE, true)?;
     nvs.set_str(config::NVS_KEY_SSID, ssid)?;
     nvs.set_str(config::NVS_KEY_PASSWORD, password)?;
+    // New creds are unproven until a station connect succeeds
+    let _ = nvs.remove(config::NVS_KEY_STA_VERIFIED);
+    let _ = nvs.remove(config::NVS_KEY_STA_FAILED);
     log::info!("Saved WiFi credentials for SSID: {}", ssid);
     Ok(())
 }
@@ -114,16 +122,103 @@ pub fn clear_wifi_creds(
     let mut nvs = EspNvs::new(nvs_partition.clone(), config::NVS_NAMESPACE, true)?;
     let _ = nvs.remove(config::NVS_KEY_SSID);
     let _ = nvs.remove(config::NVS_KEY_PASSWORD);
+    let _ = nvs.remove(config::NVS_KEY_STA_VERIFIED);
+    let _ = nvs.remove(config::NVS_KEY_STA_FAILED);
     log::info!("Cleared WiFi credentials from NVS");
     Ok(())
 }
 
-/// Initialize WiFi in Station mode (connect to user's home WiFi)
+/// Read a station bookkeeping flag (NVS_KEY_STA_VERIFIED / NVS_KEY_STA_FAILED)
+pub fn get_wifi_flag(nvs_partition: &EspDefaultNvsPartition, key: &str) -> bool {
+    EspNvs::new(nvs_partition.clone(), config::NVS_NAMESPACE, true)
+        .ok()
+        .and_then(|nvs| nvs.get_u8(key).ok().flatten())
+        .is_some_and(|v| v != 0)
+}
+
+/// Set or clear a station bookkeeping flag
+pub fn set_wifi_flag(nvs_partition: &EspDefaultNvsPartition, key: &str, value: bool) {
+    if let Ok(mut nvs) = EspNvs::new(nvs_partition.clone(), config::NVS_NAMESPACE, true) {
+        let _ = if value { nvs.set_u8(key, 1).map(|_| ()) } else { nvs.remove(key).map(|_| ()) };
+    }
+}
+
+/// Number of phones currently joined to the SoftAP
+pub fn ap_client_count() -> u32 {
+    let mut list: esp_idf_svc::sys::wifi_sta_list_t = unsafe { core::mem::zeroed() };
+    let err = unsafe { esp_idf_svc::sys::esp_wifi_ap_get_sta_list(&mut list) };
+    if err == esp_idf_svc::sys::ESP_OK {
+        list.num.max(0) as u32
+    } else {
+        0
+    }
+}
+
+/// Signal strength (dBm) of the home network in Station mode; None when not connected
+pub fn rssi() -> Option<i8> {
+    let mut info: esp_idf_svc::sys::wifi_ap_record_t = unsafe { core::mem::zeroed() };
+    let err = unsafe { esp_idf_svc::sys::esp_wifi_sta_get_ap_info(&mut info) };
+    if err == esp_idf_svc::sys::ESP_OK {
+        Some(info.rssi)
+    } else {
+        None
+    }
+}
+
+/// Bars (0-3) for the on-screen signal indicator
+pub fn signal_bars(rssi: i8) -> u8 {
+    config::RSSI_BAR_THRESHOLDS_DBM.iter().filter(|&&t| rssi >= t).count() as u8
+}
+
+/// Wait before the next station connect attempt, after `failures` failed ones:
+/// STA_RETRY_BASE_MS, doubling each time
+pub fn backoff_delay(failures: u32) -> Duration {
+    Duration::from_millis(config::STA_RETRY_BASE_MS << failures.saturating_sub(1).min(6))
+}
+
+/// One station connect attempt: associate and wait for an IP
+fn connect_once(wifi: &mut BlockingWifiHandle) -> Result<[u8; 4], Box<dyn std::error::Error>> {
+    wifi.connect()?;
+    wifi.wait_netif_up()?;
+    let ip_info = wifi.wifi().sta_netif().get_ip_info()?;
+    Ok(ip_info.ip.octets())
+}
+
+/// Connect to the configured network, making up to STA_CONNECT_ATTEMPTS tries with
+/// `backoff_delay` between them, so a brief router hiccup isn't treated as bad credentials.
+/// `on_attempt` gets the 1-based attempt number before each try (for the connecting screen).
+pub fn connect_with_backoff(
+    wifi: &mut BlockingWifiHandle,
+    mut on_attempt: impl FnMut(u32),
+) -> Result<[u8; 4], Box<dyn std::error::Error>> {
+    let mut attempt = 1;
+    loop {
+        on_attempt(attempt);
+        match connect_once(wifi) {
+            Ok(ip) => return Ok(ip),
+            Err(e) if attempt < config::STA_CONNECT_ATTEMPTS => {
+                log::warn!(
+                    "WiFi attempt {}/{} failed: {}",
+                    attempt,
+                    config::STA_CONNECT_ATTEMPTS,
+                    e
+                );
+                let _ = wifi.disconnect();
+                std::thread::sleep(backoff_delay(attempt));
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Initialize WiFi in Station mode (connect to user's home WiFi), retrying with backoff
 pub fn init_station(
     modem: Modem,
     sysloop: EspSystemEventLoop,
     nvs: Option<EspDefaultNvsPartition>,
     creds: &WiFiCredentials,
+    on_attempt: impl FnMut(u32),
 ) -> Result<(BlockingWifi<EspWifi<'static>>, [u8; 4]), Box<dyn std::error::Error>> {
     let mut wifi = BlockingWifi::wrap(
         EspWifi::new(modem, sysloop.clone(), nvs)?,
@@ -142,13 +237,7 @@ pub fn init_station(
 
     log::info!("WiFi STA started, connecting to '{}'...", creds.ssid);
 
-    wifi.connect()?;
-
-    // Wait for connection with timeout
-    wifi.wait_netif_up()?;
-
-    let ip_info = wifi.wifi().sta_netif().get_ip_info()?;
-    let ip = ip_info.ip.octets();
+    let ip = connect_with_backoff(&mut wifi, on_attempt)?;
 
     log::info!(
         "WiFi STA connected to '{}', IP: {}.{}.{}.{}",
@@ -159,6 +248,35 @@ pub fn init_station(
     Ok((wifi, ip))
 }
 
+/// Start NTP time sync (Station mode, once connected). Each sync stores UTC into `time`,
+/// keeping the current UTC offset since NTP has none; before any other sync that is the one
+/// saved in NVS at the time of the response, so an offset set from the web UI while waiting
+/// still counts. `time` stays None until the first response. Syncing stops when the returned
+/// handle is dropped.
+pub fn start_sntp(
+    time: SharedTime,
+    nvs_partition: Option<EspDefaultNvsPartition>,
+) -> Result<EspSntp<'static>, esp_idf_svc::sys::EspError> {
+    EspSntp::new_with_callback(&SntpConf::default(), move |since_epoch| {
+        let mut synced = lock_time(&time);
+        let offset = synced
+            .map(|t| t.utc_offset_secs())
+            .unwrap_or_else(|| device::load_tz_offset(&nvs_partition).unwrap_or(0) as i64 * 60);
+        *synced = Some(SyncedTime::new(since_epoch.as_secs() as i64, offset));
+        log::info!("Time synced via NTP: {}", since_epoch.as_secs());
+    })
+}
+
+/// Start the mDNS responder (Station mode): answers for `MDNS_HOSTNAME.local` and advertises
+/// the web UI as an `_http._tcp` service. Stops when the returned handle is dropped.
+pub fn start_mdns() -> Result<EspMdns, esp_idf_svc::sys::EspError> {
+    let mut mdns = EspMdns::take()?;
+    mdns.set_hostname(config::MDNS_HOSTNAME)?;
+    mdns.set_instance_name(config::AP_SSID)?;
+    mdns.add_service(None, "_http", "_tcp", config::HTTP_PORT, &[])?;
+    Ok(mdns)
+}
+
 /// Initialize WiFi in SoftAP mode (for provisioning)
 pub fn init_softap(
     modem: Modem,
@@ -197,7 +315,7 @@ pub fn init_softap(
 
 /// Scan for available WiFi networks (must be called while WiFi is started)
 pub fn scan_networks(
-    wifi: &mut BlockingWifi<EspWifi<'static>>,
+    wifi: &mut BlockingWifi<EspWifi<'_>>,
 ) -> Vec<ScannedNetwork> {
     match wifi.scan() {
         Ok(aps) => {
@@ -235,6 +353,27 @@ pub fn scan_networks(
     }
 }
 
+/// One-off scan for the boot self-test: bring up a temporary STA, count visible
+/// networks, then tear it down so the modem can be reused for normal startup.
+pub fn self_test_scan(
+    modem: &mut Modem,
+    sysloop: EspSystemEventLoop,
+    nvs: Option<EspDefaultNvsPartition>,
+) -> Result<usize, Box<dyn std::error::Error>> {
+    let mut wifi = BlockingWifi::wrap(
+        EspWifi::new(modem, sysloop.clone(), nvs)?,
+        sysloop,
+    )?;
+
+    wifi.set_configuration(&Configuration::Client(ClientConfiguration::default()))?;
+    wifi.start()?;
+    let count = scan_networks(&mut wifi).len();
+    let _ = wifi.stop();
+
+    log::info!("Self-test scan found {} networks", count);
+    Ok(count)
+}
+
 /// Get the AP's actual IP address and configure DHCP to advertise it as DNS server.
 /// This enables captive portal detection on phones.
 /// Returns the IP as [u8; 4].
@@ -296,6 +435,16 @@ pub fn web_url_from_ip(ip: [u8; 4]) -> String {
     format!("http://{}.{}.{}.{}", ip[0], ip[1], ip[2], ip[3])
 }
 
+/// Web UI URL in Station mode: the mDNS hostname when the responder is running
+/// (stable across DHCP leases), otherwise the IP
+pub fn station_url(ip: [u8; 4], mdns_active: bool) -> String {
+    if mdns_active {
+        format!("http://{}.local", config::MDNS_HOSTNAME)
+    } else {
+        web_url_from_ip(ip)
+    }
+}
+
 /// Stop WiFi for power saving (call before entering light sleep)
 pub fn stop_wifi(wifi: &mut BlockingWifiHandle) -> Result<(), Box<dyn std::error::Error>> {
     let _ = wifi.disconnect();
@@ -304,13 +453,15 @@ pub fn stop_wifi(wifi: &mut BlockingWifiHandle) -> Result<(), Box<dyn std::error
     Ok(())
 }
 
-/// Restart WiFi after waking from light sleep (STA mode)
+/// Restart WiFi after waking from light sleep (STA mode). A single attempt: the main loop
+/// schedules retries with `backoff_delay` so the UI stays responsive between them.
 pub fn restart_wifi(wifi: &mut BlockingWifiHandle) -> Result<[u8; 4], Box<dyn std::error::Error>> {
-    wifi.start()?;
-    wifi.connect()?;
-    wifi.wait_netif_up()?;
-    let ip_info = wifi.wifi().sta_netif().get_ip_info()?;
-    let ip = ip_info.ip.octets();
+    if !wifi.is_started()? {
+        wifi.start()?;
+    }
+    let ip = connect_once(wifi).inspect_err(|_| {
+        let _ = wifi.disconnect();
+    })?;
     log::info!("WiFi restarted, IP: {}.{}.{}.{}", ip[0], ip[1], ip[2], ip[3]);
     Ok(ip)
 }